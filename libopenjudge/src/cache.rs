@@ -0,0 +1,55 @@
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// On-disk store of GET response bodies along with their validators, used to
+/// make conditional requests with `If-None-Match` / `If-Modified-Since`.
+pub struct HttpCache {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CacheEntry {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+impl HttpCache {
+    pub fn new<P>(dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self { dir: dir.into() }
+    }
+
+    /// File names come from a SHA-256 of the URL, which unlike `DefaultHasher`
+    /// stays the same across Rust releases.
+    fn path_for(&self, url: &str) -> PathBuf {
+        let digest = Sha256::digest(url.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        self.dir.join(format!("{}.json", digest))
+    }
+
+    pub(crate) fn load(&self, url: &str) -> Option<CacheEntry> {
+        let content = fs::read_to_string(self.path_for(url)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        // guard against hash collisions
+        (entry.url == url).then_some(entry)
+    }
+
+    /// Failing to write the cache is not an error, the next request simply
+    /// goes without validators.
+    pub(crate) fn store(&self, entry: &CacheEntry) {
+        if fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        if let Ok(content) = serde_json::to_string(entry) {
+            let _ = fs::write(self.path_for(&entry.url), content);
+        }
+    }
+}
@@ -0,0 +1,395 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow};
+use reqwest::{
+    RequestBuilder, Response, StatusCode,
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+use url::Url;
+
+use crate::{
+    cache::{CacheEntry, HttpCache},
+    fetcher::{FetchResponse, Fetcher, FormFile},
+};
+
+pub const ROOT_HOST: &str = "openjudge.cn";
+
+/// Time a GET may take before it counts as timed out, when there are other
+/// hosts to fail over to.
+const FAILOVER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Attempts on a host before moving on to the next one.
+const FAILOVER_ATTEMPTS: usize = 2;
+
+/// Pages kept by [`Client::recent_page`].
+const RECENT_PAGES: usize = 8;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Scheme {
+    #[default]
+    #[serde(rename = "https")]
+    Https,
+    #[serde(rename = "http")]
+    Http,
+}
+
+impl Scheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scheme::Https => "https",
+            Scheme::Http => "http",
+        }
+    }
+}
+
+/// HTTP client for OpenJudge.
+///
+/// Requests to OpenJudge hosts are upgraded to the preferred scheme. When
+/// HTTPS is preferred but a host refuses it, the request is retried over HTTP
+/// and the host is served over HTTP for the rest of the session.
+///
+/// URLs always name [`ROOT_HOST`], whichever host serves them. With several
+/// base hosts, requests go to the same subdomain of the first one that works;
+/// when a request keeps timing out, it is retried on the next host, which then
+/// serves the rest of the session. Cookies are kept per host, so a failover
+/// may need logging in again.
+pub struct Client {
+    http: reqwest::Client,
+    scheme: Scheme,
+    cache: Option<HttpCache>,
+    http_only_hosts: Mutex<HashSet<String>>,
+    hosts: Vec<String>,
+    active_host: AtomicUsize,
+    /// Bodies of the last pages fetched, newest last.
+    recent_pages: Mutex<VecDeque<(String, String)>>,
+}
+
+impl Client {
+    pub fn new(http: reqwest::Client, scheme: Scheme, cache: Option<HttpCache>) -> Self {
+        Self {
+            http,
+            scheme,
+            cache,
+            http_only_hosts: Mutex::new(HashSet::new()),
+            hosts: vec![ROOT_HOST.to_string()],
+            active_host: AtomicUsize::new(0),
+            recent_pages: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Base hosts to fail over between, in order. Ignored if empty.
+    pub fn with_hosts(mut self, hosts: Vec<String>) -> Self {
+        if !hosts.is_empty() {
+            self.hosts = hosts;
+        }
+        self
+    }
+
+    /// The body last fetched from `url`, if it is among the last
+    /// few pages. Lets callers keep the page a scraper failed
+    /// on, see [`crate::ParseError`].
+    pub fn recent_page(&self, url: &str) -> Option<String> {
+        self.recent_pages
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|(page_url, _)| page_url == url)
+            .map(|(_, body)| body.clone())
+    }
+
+    fn remember_page(&self, url: &str, body: &str) {
+        let mut pages = self.recent_pages.lock().unwrap();
+        if pages.len() == RECENT_PAGES {
+            pages.pop_front();
+        }
+        pages.push_back((url.to_string(), body.to_string()));
+    }
+
+    /// The base host currently serving requests.
+    pub fn active_host(&self) -> &str {
+        &self.hosts[self.active_host.load(Ordering::Relaxed)]
+    }
+
+    pub fn scheme(&self) -> Scheme {
+        self.scheme
+    }
+
+    /// The underlying reqwest client, for requests outside of OpenJudge.
+    pub fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    fn scheme_for(&self, host: &str) -> Scheme {
+        if self.http_only_hosts.lock().unwrap().contains(host) {
+            Scheme::Http
+        } else {
+            self.scheme
+        }
+    }
+
+    /// The subdomain part of an OpenJudge `host` on any base host, e.g.
+    /// `"cs101."` for `cs101.openjudge.cn`, and `""` for a base host itself.
+    fn subdomain<'a>(&self, host: &'a str) -> Option<&'a str> {
+        std::iter::once(ROOT_HOST)
+            .chain(self.hosts.iter().map(String::as_str))
+            .find_map(|base| {
+                host.strip_suffix(base)
+                    .filter(|prefix| prefix.is_empty() || prefix.ends_with('.'))
+            })
+    }
+
+    fn apply_scheme(&self, url: &mut Url) {
+        let Some(host) = url.host_str() else {
+            return;
+        };
+        if self.subdomain(host).is_none() {
+            return;
+        }
+        let scheme = self.scheme_for(host);
+        let _ = url.set_scheme(scheme.as_str());
+    }
+
+    /// GETs the body of `url` as text. With a cache configured, the request
+    /// is made conditional on the stored validators, and a `304 Not Modified`
+    /// is answered from the cache.
+    async fn load_text(&self, url: &str) -> Result<String> {
+        let Some(cache) = &self.cache else {
+            let body = self.get(url).await?.text().await?;
+            debug!(bytes = body.len(), "body read");
+            return Ok(body);
+        };
+        let cached = cache.load(url);
+        let response = self
+            .send_with_fallback(url, true, |client, url| {
+                let mut request = client.get(url);
+                if let Some(cached) = &cached {
+                    if let Some(etag) = &cached.etag {
+                        request = request.header(IF_NONE_MATCH, etag);
+                    }
+                    if let Some(last_modified) = &cached.last_modified {
+                        request = request.header(IF_MODIFIED_SINCE, last_modified);
+                    }
+                }
+                request
+            })
+            .await?;
+        if response.status() == StatusCode::NOT_MODIFIED
+            && let Some(cached) = cached
+        {
+            debug!(bytes = cached.body.len(), "not modified, served from cache");
+            return Ok(cached.body);
+        }
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string())
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let cacheable =
+            response.status().is_success() && (etag.is_some() || last_modified.is_some());
+        let body = response.text().await?;
+        debug!(bytes = body.len(), cacheable, "body read");
+        if cacheable {
+            cache.store(&CacheEntry {
+                url: url.to_string(),
+                etag,
+                last_modified,
+                body: body.clone(),
+            });
+        }
+        Ok(body)
+    }
+
+    pub async fn get(&self, url: &str) -> Result<Response> {
+        self.send_with_fallback(url, true, |client, url| client.get(url))
+            .await
+    }
+
+    /// Sends a request to `url` on the active host, failing over to the
+    /// next hosts when it is unreachable. An `idempotent` request is given
+    /// [`FAILOVER_TIMEOUT`] and sent again after timing out. Others, such as
+    /// a submission the server may have accepted before the timeout, only
+    /// move on when the connection failed, so nothing was sent.
+    async fn send_with_fallback<F>(&self, url: &str, idempotent: bool, build: F) -> Result<Response>
+    where
+        F: Fn(&reqwest::Client, Url) -> RequestBuilder,
+    {
+        let url = Url::parse(url).map_err(|e| anyhow!("Invalid URL {}: {}", url, e))?;
+        let subdomain = url
+            .host_str()
+            .and_then(|host| self.subdomain(host))
+            .map(str::to_string);
+        let Some(subdomain) = subdomain else {
+            return self.send(url, &build).await;
+        };
+        // a single host is neither retried nor given a timeout
+        let failover = self.hosts.len() > 1;
+        let attempts = if failover && idempotent {
+            FAILOVER_ATTEMPTS
+        } else {
+            1
+        };
+        let first = self.active_host.load(Ordering::Relaxed);
+        let mut last_error = None;
+        for offset in 0..self.hosts.len() {
+            let index = (first + offset) % self.hosts.len();
+            let mut url = url.clone();
+            url.set_host(Some(&format!("{}{}", subdomain, self.hosts[index])))?;
+            for _ in 0..attempts {
+                let result = self
+                    .send(url.clone(), |client, url| {
+                        if failover && idempotent {
+                            build(client, url).timeout(FAILOVER_TIMEOUT)
+                        } else {
+                            build(client, url)
+                        }
+                    })
+                    .await;
+                match result {
+                    Ok(response) => {
+                        if index != first {
+                            debug!(host = %self.hosts[index], "failed over to another host");
+                            self.active_host.store(index, Ordering::Relaxed);
+                        }
+                        return Ok(response);
+                    }
+                    Err(e) if is_unreachable(&e, idempotent) => {
+                        debug!(%url, error = %e, "host unreachable");
+                        last_error = Some(e);
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow!("No host to send {} to.", url)))
+    }
+
+    /// Sends a request to exactly `url`, falling back to HTTP if HTTPS is
+    /// refused.
+    async fn send<F>(&self, mut url: Url, build: F) -> Result<Response>
+    where
+        F: Fn(&reqwest::Client, Url) -> RequestBuilder,
+    {
+        self.apply_scheme(&mut url);
+        let start = Instant::now();
+        let response = match build(&self.http, url.clone()).send().await {
+            Ok(response) => response,
+            Err(e) if e.is_connect() && url.scheme() == "https" => {
+                let host = url.host_str().unwrap_or_default().to_string();
+                debug!(%host, "HTTPS connection failed, falling back to HTTP");
+                self.http_only_hosts.lock().unwrap().insert(host);
+                let _ = url.set_scheme("http");
+                build(&self.http, url.clone()).send().await?
+            }
+            Err(e) => return Err(e.into()),
+        };
+        debug!(
+            %url,
+            status = response.status().as_u16(),
+            elapsed_ms = start.elapsed().as_millis() as u64,
+            "response received"
+        );
+        Ok(response)
+    }
+}
+
+/// Whether `error` is a refused connection, or a timeout of an `idempotent`
+/// request, after which another host may do better.
+fn is_unreachable(error: &anyhow::Error, idempotent: bool) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .is_some_and(|e| e.is_connect() || (idempotent && e.is_timeout()))
+}
+
+impl Fetcher for Client {
+    /// Absolute URL of `path` on `{group}.openjudge.cn`, or on `openjudge.cn`
+    /// if `group` is `None`.
+    fn site_url(&self, group: Option<&str>, path: &str) -> String {
+        let host = match group {
+            Some(group) => format!("{}.{}", group, ROOT_HOST),
+            None => ROOT_HOST.to_string(),
+        };
+        let scheme = self.scheme_for(&host);
+        format!(
+            "{}://{}/{}",
+            scheme.as_str(),
+            host,
+            path.trim_start_matches('/')
+        )
+    }
+
+    /// Resolves a scraped `href` against the page it was found on, and
+    /// rewrites OpenJudge URLs to [`ROOT_HOST`] and the scheme used for their
+    /// host.
+    fn normalize_url(&self, base: &str, href: &str) -> Result<String> {
+        let mut url = Url::parse(base)?.join(href.trim())?;
+        if let Some(subdomain) = url.host_str().and_then(|host| self.subdomain(host)) {
+            let host = format!("{}{}", subdomain, ROOT_HOST);
+            url.set_host(Some(&host))?;
+        }
+        self.apply_scheme(&mut url);
+        Ok(url.to_string())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_text(&self, url: &str) -> Result<String> {
+        let body = self.load_text(url).await?;
+        self.remember_page(url, &body);
+        Ok(body)
+    }
+
+    // form fields may hold credentials and source code, keep them out of logs
+    #[instrument(skip(self, form), err)]
+    async fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<FetchResponse> {
+        let response = self
+            .send_with_fallback(url, false, |client, url| client.post(url).form(form))
+            .await?;
+        let status = response.status();
+        let body = response.text().await?;
+        debug!(bytes = body.len(), "body read");
+        Ok(FetchResponse { status, body })
+    }
+
+    #[instrument(skip(self, form, files), fields(files = files.len()), err)]
+    async fn post_multipart(
+        &self,
+        url: &str,
+        form: &[(&str, &str)],
+        files: &[FormFile],
+    ) -> Result<FetchResponse> {
+        let response = self
+            .send_with_fallback(url, false, |client, url| {
+                // rebuilt for the HTTP fallback, as a form can only be sent once
+                let mut multipart = reqwest::multipart::Form::new();
+                for (name, value) in form {
+                    multipart = multipart.text(name.to_string(), value.to_string());
+                }
+                for file in files {
+                    multipart = multipart.part(
+                        file.field.clone(),
+                        reqwest::multipart::Part::bytes(file.content.clone())
+                            .file_name(file.file_name.clone()),
+                    );
+                }
+                client.post(url).multipart(multipart)
+            })
+            .await?;
+        let status = response.status();
+        let body = response.text().await?;
+        debug!(bytes = body.len(), "body read");
+        Ok(FetchResponse { status, body })
+    }
+}
@@ -0,0 +1,67 @@
+use std::future::Future;
+
+use anyhow::Result;
+use futures::{
+    Stream, StreamExt,
+    stream::{self, FuturesUnordered},
+};
+
+/// Number of requests kept in flight by multi-page operations.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Awaits `futures` with at most `limit` of them in flight at once, returning
+/// their outputs in the original order.
+pub async fn join_bounded<F, T>(futures: impl IntoIterator<Item = F>, limit: usize) -> Vec<T>
+where
+    F: Future<Output = T>,
+{
+    let indexed = |(index, future): (usize, F)| async move { (index, future.await) };
+    let mut pending = futures.into_iter().enumerate();
+    let mut running = pending
+        .by_ref()
+        .take(limit.max(1))
+        .map(indexed)
+        .collect::<FuturesUnordered<_>>();
+    let mut results = Vec::new();
+    while let Some(result) = running.next().await {
+        results.push(result);
+        if let Some(next) = pending.next() {
+            running.push(indexed(next));
+        }
+    }
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, output)| output).collect()
+}
+
+/// Fetches page 1 with `fetch_page`, then the remaining pages up to the last
+/// one `max_page` reports, with at most `limit` of them in flight at once.
+/// Pages are yielded in order, each as soon as it and those before it have
+/// arrived. The stream ends after the first error.
+pub fn stream_pages<'a, P, F, Fut>(
+    fetch_page: F,
+    max_page: fn(&P) -> u32,
+    limit: usize,
+) -> impl Stream<Item = Result<P>> + 'a
+where
+    P: 'a,
+    F: Fn(u32) -> Fut + Clone + 'a,
+    Fut: Future<Output = Result<P>> + 'a,
+{
+    let first = fetch_page.clone();
+    stream::once(async move { first(1).await })
+        .flat_map(move |first| {
+            let last = first.as_ref().map_or(1, max_page);
+            stream::once(async { first }).chain(
+                stream::iter(2..=last)
+                    .map(fetch_page.clone())
+                    .buffered(limit.max(1)),
+            )
+        })
+        .scan(false, |failed, page| {
+            let item = (!*failed).then(|| {
+                *failed = page.is_err();
+                page
+            });
+            async move { item }
+        })
+}
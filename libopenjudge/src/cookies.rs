@@ -0,0 +1,77 @@
+use std::{
+    fs,
+    io::{BufReader, BufWriter},
+    path::Path,
+    sync::Arc,
+};
+
+use anyhow::{Result, anyhow};
+use cookie_store::CookieStore;
+use reqwest_cookie_store::CookieStoreMutex;
+
+/// Cookie jar that can be saved to and loaded from disk, so that a login
+/// outlives the process. Clones share the same underlying store.
+#[derive(Clone, Default)]
+pub struct CookieJar {
+    store: Arc<CookieStoreMutex>,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a jar saved with [`CookieJar::save`], or an empty one if `path`
+    /// does not exist.
+    pub fn load<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = match fs::File::open(path.as_ref()) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let store = cookie_store::serde::json::load_all(BufReader::new(file))
+            .map_err(|e| anyhow!("Cannot load cookies: {}", e))?;
+        Ok(Self {
+            store: Arc::new(CookieStoreMutex::new(store)),
+        })
+    }
+
+    /// Writes every cookie to `path`, including session cookies, which is
+    /// where OpenJudge keeps the login. On unix the file is only readable by
+    /// its owner.
+    pub fn save<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        if let Some(parent) = path.as_ref().parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(0o600);
+            // the mode only applies to new files, jars saved before keep theirs
+            if path.as_ref().exists() {
+                fs::set_permissions(path.as_ref(), fs::Permissions::from_mode(0o600))?;
+            }
+        }
+        let mut writer = BufWriter::new(options.open(path.as_ref())?);
+        let store = self.store.lock().unwrap();
+        cookie_store::serde::json::save_incl_expired_and_nonpersistent(&store, &mut writer)
+            .map_err(|e| anyhow!("Cannot save cookies: {}", e))
+    }
+
+    /// Removes all cookies, e.g. to log out.
+    pub fn clear(&self) {
+        *self.store.lock().unwrap() = CookieStore::default();
+    }
+
+    pub(crate) fn provider(&self) -> Arc<CookieStoreMutex> {
+        self.store.clone()
+    }
+}
@@ -0,0 +1,57 @@
+use std::future::Future;
+
+use anyhow::Result;
+use reqwest::StatusCode;
+use url::Url;
+
+use crate::ROOT_HOST;
+
+pub struct FetchResponse {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+/// A file sent in a multipart form.
+pub struct FormFile {
+    /// Name of the form field.
+    pub field: String,
+    pub file_name: String,
+    pub content: Vec<u8>,
+}
+
+/// Transport used by the scraping functions. [`crate::Client`] implements it
+/// over reqwest; tests and offline modes can supply recorded pages instead.
+pub trait Fetcher {
+    /// GETs `url` and returns the body as text.
+    fn get_text(&self, url: &str) -> impl Future<Output = Result<String>> + Send;
+
+    /// POSTs `form` url-encoded to `url`.
+    fn post_form(
+        &self,
+        url: &str,
+        form: &[(&str, &str)],
+    ) -> impl Future<Output = Result<FetchResponse>> + Send;
+
+    /// POSTs `form` and `files` as `multipart/form-data` to `url`.
+    fn post_multipart(
+        &self,
+        url: &str,
+        form: &[(&str, &str)],
+        files: &[FormFile],
+    ) -> impl Future<Output = Result<FetchResponse>> + Send;
+
+    /// Absolute URL of `path` on `{group}.openjudge.cn`, or on `openjudge.cn`
+    /// if `group` is `None`.
+    fn site_url(&self, group: Option<&str>, path: &str) -> String {
+        let host = match group {
+            Some(group) => format!("{}.{}", group, ROOT_HOST),
+            None => ROOT_HOST.to_string(),
+        };
+        format!("https://{}/{}", host, path.trim_start_matches('/'))
+    }
+
+    /// Resolves a scraped `href` against the page it was found on.
+    fn normalize_url(&self, base: &str, href: &str) -> Result<String> {
+        Ok(Url::parse(base)?.join(href.trim())?.to_string())
+    }
+}
@@ -0,0 +1,1306 @@
+//! Client and scrapers for OpenJudge (openjudge.cn).
+
+mod cache;
+mod client;
+mod concurrent;
+mod cookies;
+mod fetcher;
+mod parse;
+mod problem_url;
+mod selectors;
+mod tests;
+use std::{convert::Infallible, str::FromStr};
+
+use anyhow::{Result, anyhow};
+use base64::prelude::*;
+use chrono::{DateTime, FixedOffset};
+use futures::{Stream, StreamExt, stream};
+use scraper::{self, CaseSensitivity, ElementRef};
+use selectors::*;
+
+pub use cache::HttpCache;
+pub use client::{Client, ROOT_HOST, Scheme};
+pub use concurrent::{DEFAULT_CONCURRENCY, join_bounded, stream_pages};
+pub use cookies::CookieJar;
+pub use fetcher::{FetchResponse, Fetcher, FormFile};
+pub use parse::{ParseError, SERVER_UTC_OFFSET_SECONDS, parse_server_time};
+use parse::{parse_text, parse_time, select_attr, select_first};
+pub use problem_url::ProblemUrl;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+#[derive(Clone)]
+pub struct Problem {
+    pub title: String,
+    pub group: String,
+    pub probset: String,
+    pub limits: ProblemLimits,
+    pub description: String,
+    pub input: Option<String>,
+    pub output: Option<String>,
+    pub sample_input: Option<String>,
+    pub sample_output: Option<String>,
+    pub hint: Option<String>,
+    pub source: Option<String>,
+}
+
+/// Limits listed above the problem description. Times are in milliseconds and
+/// memory in kilobytes, as OpenJudge states them.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ProblemLimits {
+    /// 总时间限制
+    pub total_time: Option<u32>,
+    /// 单个测试点时间限制
+    pub case_time: Option<u32>,
+    /// 内存限制
+    pub memory: Option<u32>,
+    pub special_judge: bool,
+}
+
+/// Generic `{ result, message }` response of OpenJudge's JSON APIs.
+#[derive(Serialize, Deserialize)]
+pub struct ApiResponse {
+    pub result: String,
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LoginResponse {
+    pub result: String,
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SubmitResponse {
+    pub result: String,
+    pub message: Option<String>,
+    pub redirect: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SubmissionResult {
+    PresentationError,
+    Accepted,
+    CompileError { message: Option<String> },
+    WrongAnswer,
+    RuntimeError,
+    TimeLimitExceeded,
+    OutputLimitExceeded,
+    MemoryLimitExceeded,
+    Waiting,
+    SystemError,
+    Unknown,
+}
+
+impl SubmissionResult {
+    /// Attaches the compiler output to a compile error, other verdicts are
+    /// returned unchanged.
+    pub fn with_compile_message(self, message: String) -> Self {
+        match self {
+            SubmissionResult::CompileError { .. } => SubmissionResult::CompileError {
+                message: Some(message),
+            },
+            other => other,
+        }
+    }
+}
+
+/// Maps the verdict text shown by OpenJudge, unrecognized text becomes
+/// [`SubmissionResult::Unknown`].
+impl FromStr for SubmissionResult {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim() {
+            "Accepted" => SubmissionResult::Accepted,
+            "Compile Error" => SubmissionResult::CompileError { message: None },
+            "Presentation Error" => SubmissionResult::PresentationError,
+            "Wrong Answer" => SubmissionResult::WrongAnswer,
+            "Runtime Error" => SubmissionResult::RuntimeError,
+            "Time Limit Exceeded" => SubmissionResult::TimeLimitExceeded,
+            "Output Limit Exceeded" => SubmissionResult::OutputLimitExceeded,
+            "Memory Limit Exceeded" => SubmissionResult::MemoryLimitExceeded,
+            "Waiting" => SubmissionResult::Waiting,
+            "System Error" => SubmissionResult::SystemError,
+            _ => SubmissionResult::Unknown,
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Language {
+    Gcc,
+    Gpp,
+    Python3,
+    PyPy3,
+    /// Free Pascal.
+    Pascal,
+    /// Any other language a group accepts, by the value its submit form uses,
+    /// e.g. "Java".
+    Custom(String),
+}
+
+impl Language {
+    /// Value of the language field of the submit form.
+    pub fn submit_value(&self) -> &str {
+        match self {
+            Language::Gcc => "GCC",
+            Language::Gpp => "G++",
+            Language::Python3 => "Python3",
+            Language::PyPy3 => "PyPy3",
+            Language::Pascal => "Pascal",
+            Language::Custom(value) => value,
+        }
+    }
+}
+
+pub struct Submission {
+    pub result: SubmissionResult,
+    pub id: String,
+    pub author: String,
+    pub lang: String,
+    pub code: String,
+    /// `None` if the page does not show a time OpenJudge is known to use.
+    pub submission_time: Option<DateTime<FixedOffset>>,
+    pub memory: Option<String>,
+    pub time: Option<String>,
+}
+
+pub struct ProblemListEntry {
+    pub problem_number: String,
+    pub title: String,
+    pub accepted_population: u32,
+    pub submitters: u32,
+    pub url: String,
+    pub solved: Option<bool>,
+}
+
+pub struct ProblemSearchResult {
+    pub title: String,
+    pub url: String,
+    pub group: String,
+    pub probset: String,
+    pub problem_number: String,
+    pub accepted_cnt: u32,
+    pub submission_cnt: u32,
+}
+
+pub struct User {
+    pub id: String,
+    pub username: String,
+    pub school: String,
+    pub sex: String,
+    pub register_time: String,
+}
+
+/// A problem listed on a user's home page.
+pub struct UserProblemEntry {
+    pub title: String,
+    pub url: String,
+    /// `None` if the link does not point to a problem page.
+    pub problem: Option<ProblemUrl>,
+    /// Whether the problem is solved, rather than only attempted.
+    pub solved: bool,
+}
+
+pub struct SubmissionHistoryEntry {
+    pub result: SubmissionResult,
+    /// Time of submission.
+    pub time: DateTime<FixedOffset>,
+    pub url: String,
+}
+
+pub struct Group {
+    pub name: String,
+    pub description: String,
+    pub url: String,
+    pub probsets: Vec<ProblemSetEntry>,
+}
+
+pub struct ProblemSetEntry {
+    pub name: String,
+    pub url: String,
+    /// Opening time of a contest, `None` for problem sets always open.
+    pub start: Option<DateTime<FixedOffset>>,
+    /// Closing time of a contest, `None` for problem sets always open.
+    pub end: Option<DateTime<FixedOffset>>,
+}
+
+/// A page of topics on the discussion board of a problem.
+pub struct BoardPage {
+    pub url: String,
+    pub page: u32,
+    pub max_page: u32,
+    pub topics: Vec<BoardTopic>,
+}
+
+pub struct BoardTopic {
+    pub title: String,
+    pub url: String,
+    pub author: String,
+    pub replies: u32,
+    pub last_post: Option<DateTime<FixedOffset>>,
+}
+
+/// A message on the board, the first one of a topic opening it.
+pub struct BoardPost {
+    pub author: String,
+    pub time: Option<DateTime<FixedOffset>>,
+    /// HTML of the message.
+    pub content: String,
+}
+
+pub struct ProblemSetPartial {
+    pub name: String,
+    pub group_name: String,
+    pub url: String,
+    pub page: u32,
+    pub max_page: u32,
+    pub problems: Vec<ProblemListEntry>,
+}
+
+pub struct Standings {
+    pub name: String,
+    pub group_name: String,
+    pub url: String,
+    pub page: u32,
+    pub max_page: u32,
+    /// Column labels of the problems, in the order of [`StandingsRow::cells`].
+    pub problems: Vec<String>,
+    pub rows: Vec<StandingsRow>,
+}
+
+pub struct StandingsRow {
+    pub rank: u32,
+    pub user: String,
+    pub user_url: Option<String>,
+    pub solved: u32,
+    /// Total time including penalties, as displayed.
+    pub penalty: Option<String>,
+    /// One cell per problem, `None` if the user has not attempted it.
+    pub cells: Vec<Option<StandingsCell>>,
+}
+
+/// A problem to add to a problem set. Text fields are HTML, limits are in
+/// milliseconds and kilobytes.
+#[derive(Default)]
+pub struct NewProblem {
+    pub title: String,
+    pub description: String,
+    pub input: Option<String>,
+    pub output: Option<String>,
+    pub sample_input: Option<String>,
+    pub sample_output: Option<String>,
+    pub hint: Option<String>,
+    pub source: Option<String>,
+    pub time_limit: u32,
+    pub memory_limit: u32,
+}
+
+/// Response of the problem creation API, a [`ApiResponse`] with the path of
+/// the new problem.
+#[derive(Deserialize)]
+struct CreateProblemResponse {
+    result: String,
+    message: Option<String>,
+    url: Option<String>,
+}
+
+/// A pending application to join a group, as listed to its admins.
+pub struct JoinRequest {
+    /// Identifier used to approve or reject the request.
+    pub id: String,
+    pub user: String,
+    pub user_url: Option<String>,
+    pub message: Option<String>,
+    pub time: DateTime<FixedOffset>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StandingsCell {
+    pub accepted: bool,
+    pub failed_attempts: u32,
+    /// Time of the accepted submission, as displayed.
+    pub time: Option<String>,
+}
+
+#[derive(Default)]
+pub struct ClientOptions {
+    pub scheme: Scheme,
+    /// Directory for the conditional-request cache of GET responses, no
+    /// caching if `None`.
+    pub cache_dir: Option<std::path::PathBuf>,
+    /// `User-Agent` header sent with every request, [`DEFAULT_USER_AGENT`] if
+    /// `None`.
+    pub user_agent: Option<String>,
+    /// Cookie jar to use, e.g. one loaded from disk to resume a login. A
+    /// fresh in-memory jar is used if `None`.
+    pub cookie_jar: Option<CookieJar>,
+    /// Base hosts serving OpenJudge, e.g. mirrors, tried in order when
+    /// requests keep timing out. Only [`ROOT_HOST`] if empty.
+    pub hosts: Vec<String>,
+    /// Idle connections kept per host for reuse, [`DEFAULT_POOL_SIZE`] if
+    /// `None`.
+    pub pool_max_idle_per_host: Option<usize>,
+    /// How long an idle connection is kept for reuse,
+    /// [`DEFAULT_POOL_IDLE_TIMEOUT`] if `None`.
+    pub pool_idle_timeout: Option<std::time::Duration>,
+}
+
+pub const DEFAULT_USER_AGENT: &str = concat!("libopenjudge/", env!("CARGO_PKG_VERSION"));
+
+/// Idle connections kept per host, enough for [`DEFAULT_CONCURRENCY`]
+/// requests with room for image downloads alongside them.
+pub const DEFAULT_POOL_SIZE: usize = 16;
+
+/// Long enough for connections to outlive the pauses between the requests of
+/// batch operations, such as waiting for a verdict.
+pub const DEFAULT_POOL_IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Interval of TCP keep-alive probes, which keep idle pooled connections from
+/// being dropped by NAT gateways.
+const TCP_KEEPALIVE: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[instrument(skip_all, err)]
+pub async fn create_client(options: ClientOptions) -> Result<Client> {
+    let cookie_jar = options.cookie_jar.unwrap_or_default();
+    let http = reqwest::Client::builder()
+        .cookie_provider(cookie_jar.provider())
+        // sends a matching Accept-Encoding and decodes transparently
+        .gzip(true)
+        .brotli(true)
+        .user_agent(options.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT))
+        .pool_max_idle_per_host(options.pool_max_idle_per_host.unwrap_or(DEFAULT_POOL_SIZE))
+        .pool_idle_timeout(
+            options
+                .pool_idle_timeout
+                .unwrap_or(DEFAULT_POOL_IDLE_TIMEOUT),
+        )
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .build()?;
+    let client = Client::new(http, options.scheme, options.cache_dir.map(HttpCache::new))
+        .with_hosts(options.hosts);
+    // we do this so that following requests will have the cookies
+    client.get(&client.site_url(None, "/")).await?;
+    Ok(client)
+}
+
+fn query_selector_inner_text(dom: &scraper::Html, selector: &scraper::Selector) -> String {
+    let selector_target = dom.select(selector).next();
+    if let Some(selector_target) = selector_target {
+        selector_target
+            .text()
+            .collect::<Vec<&str>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    } else {
+        String::new()
+    }
+}
+
+async fn get_and_parse_html(http_client: &impl Fetcher, url: &str) -> Result<scraper::Html> {
+    let html = http_client.get_text(url).await?;
+    Ok(scraper::html::Html::parse_document(&html))
+}
+
+#[instrument(skip_all, fields(problem = %problem), err)]
+pub async fn get_problem(http_client: &impl Fetcher, problem: &ProblemUrl) -> Result<Problem> {
+    let dom = get_and_parse_html(http_client, &problem.page_url(http_client)).await?;
+    let group = query_selector_inner_text(&dom, &PAGE_HEADER_GROUP_SELECTOR);
+    let probset = query_selector_inner_text(&dom, &PAGE_HEADER_PROBSET_SELECTOR);
+    let problem_content_dts = dom
+        .select(&PROBLEM_PAGE_CONTENT_DTS_SELECTOR)
+        .collect::<Vec<_>>();
+    let title = query_selector_inner_text(&dom, &PROBLEM_PAGE_TITLE_SELECTOR);
+    let limits = parse_problem_limits(&dom);
+    let mut description = String::new();
+    let mut input: Option<String> = None;
+    let mut output: Option<String> = None;
+    let mut sample_input: Option<String> = None;
+    let mut sample_output: Option<String> = None;
+    let mut hint: Option<String> = None;
+    let mut source: Option<String> = None;
+    for dt in problem_content_dts {
+        let dt_text = dt.text().collect::<Vec<&str>>().join("\n");
+        let dd = dt
+            .next_siblings()
+            .find(|element| element.value().is_element());
+        if let Some(dd) = dd {
+            let Some(dd) = ElementRef::wrap(dd) else {
+                continue;
+            };
+            let dd_text = dd.html();
+            match dt_text.as_str() {
+                "描述" => description = dd_text,
+                "输入" => input = Some(dd_text),
+                "输出" => output = Some(dd_text),
+                "样例输入" => sample_input = Some(dd_text),
+                "样例输出" => sample_output = Some(dd_text),
+                "提示" => hint = Some(dd_text),
+                "来源" => source = Some(dd_text),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(Problem {
+        title,
+        group,
+        probset,
+        limits,
+        description,
+        input,
+        output,
+        sample_input,
+        sample_output,
+        hint,
+        source,
+    })
+}
+
+fn parse_problem_limits(dom: &scraper::Html) -> ProblemLimits {
+    let mut limits = ProblemLimits::default();
+    for dt in dom.select(&PROBLEM_PAGE_PARAMS_DTS_SELECTOR) {
+        let dt_text = dt.text().collect::<String>();
+        let dt_text = dt_text.trim().trim_end_matches([':', '：']).trim();
+        let dd_text = dt
+            .next_siblings()
+            .find_map(ElementRef::wrap)
+            .map(|dd| dd.text().collect::<String>())
+            .unwrap_or_default();
+        match dt_text {
+            "总时间限制" => {
+                limits.total_time = parse_quantity(&dd_text, &[("ms", 1), ("s", 1000)])
+            }
+            "单个测试点时间限制" => {
+                limits.case_time = parse_quantity(&dd_text, &[("ms", 1), ("s", 1000)])
+            }
+            "内存限制" => {
+                limits.memory = parse_quantity(&dd_text, &[("kB", 1), ("KB", 1), ("MB", 1024)])
+            }
+            _ => {}
+        }
+        if is_special_judge_marker(dt_text) || is_special_judge_marker(&dd_text) {
+            limits.special_judge = true;
+        }
+    }
+    limits
+}
+
+fn is_special_judge_marker(text: &str) -> bool {
+    text.contains("特殊判题")
+        || text.contains("特判")
+        || text.to_lowercase().contains("special judge")
+}
+
+/// Parses values like `1000ms` or `64MB`, scaling by the factor of the first
+/// matching unit suffix.
+fn parse_quantity(text: &str, units: &[(&str, u32)]) -> Option<u32> {
+    let text = text.trim();
+    units.iter().find_map(|(unit, factor)| {
+        text.strip_suffix(unit)
+            .and_then(|value| value.trim().parse::<u32>().ok())
+            .map(|value| value * factor)
+    })
+}
+
+// credentials stay out of the span, which ends up in crash reports
+#[instrument(skip_all, err)]
+pub async fn login(http_client: &impl Fetcher, email: &str, password: &str) -> Result<()> {
+    let response = http_client
+        .post_form(
+            &http_client.site_url(None, "/api/auth/login"),
+            &[("email", email), ("password", password)],
+        )
+        .await?;
+    if !response.status.is_success() {
+        return Err(anyhow!("Login Failed: {}", response.status.to_string()));
+    }
+    let response_text = response.body;
+    let response: LoginResponse = serde_json::from_str(&response_text)?;
+    if response.result != "SUCCESS" {
+        return Err(anyhow!(
+            response
+                .message
+                .unwrap_or_else(|| "No message provided".to_string())
+        ));
+    }
+    Ok(())
+}
+
+#[instrument(skip_all, fields(problem = %problem), err)]
+pub async fn submit_solution(
+    http_client: &impl Fetcher,
+    problem: &ProblemUrl,
+    code: &str,
+    lang: &Language,
+) -> Result<String> {
+    let url = problem.submit_url(http_client);
+    let dom = get_and_parse_html(http_client, &url).await?;
+    let error = query_selector_inner_text(&dom, &ERROR_SELECTOR);
+    if !error.is_empty() {
+        return Err(anyhow!("Error on submission page: {}", error));
+    }
+    let contest_id = select_attr(
+        select_first(&dom, &SUBMIT_CONTEST_ID_SELECTOR, &url)?,
+        "value",
+        &SUBMIT_CONTEST_ID_SELECTOR,
+        &url,
+    )?;
+    let problem_number = select_attr(
+        select_first(&dom, &SUBMIT_PROBLEM_NUMBER_SELECTOR, &url)?,
+        "value",
+        &SUBMIT_PROBLEM_NUMBER_SELECTOR,
+        &url,
+    )?;
+    let code = BASE64_STANDARD.encode(code);
+    let submit_api = http_client.normalize_url(&url, "/api/solution/submitv2/")?;
+    let response = http_client
+        .post_form(
+            &submit_api,
+            &[
+                ("contestId", contest_id),
+                ("problemNumber", problem_number),
+                ("sourceEncode", "base64"),
+                ("language", lang.submit_value()),
+                ("source", &code),
+            ],
+        )
+        .await?;
+    if !response.status.is_success() {
+        return Err(anyhow!("Submission failed: {}", response.body));
+    }
+
+    let response_text = response.body;
+    let response: SubmitResponse = serde_json::from_str(&response_text)?;
+    if response.result != "SUCCESS" {
+        return Err(anyhow!(
+            response
+                .message
+                .unwrap_or_else(|| "No message provided".to_string())
+        ));
+    }
+    let redirect_url = response
+        .redirect
+        .ok_or_else(|| anyhow!("No redirect URL provided."))?;
+    http_client.normalize_url(&url, &redirect_url)
+}
+
+/// Waits for the submission at `result_page_url` to be judged, checking every
+/// second.
+#[instrument(skip(http_client), err)]
+pub async fn query_submission_result(
+    http_client: &impl Fetcher,
+    result_page_url: &str,
+) -> Result<Submission> {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+    // this finishes instantly
+    interval.tick().await;
+    loop {
+        let submission = get_submission(http_client, result_page_url).await?;
+        if submission.result != SubmissionResult::Waiting {
+            return Ok(submission);
+        }
+        interval.tick().await;
+    }
+}
+
+/// Loads the submission at `result_page_url` once, its result is
+/// [`SubmissionResult::Waiting`] if it is not judged yet.
+#[instrument(skip(http_client), err)]
+pub async fn get_submission(
+    http_client: &impl Fetcher,
+    result_page_url: &str,
+) -> Result<Submission> {
+    let dom = get_and_parse_html(http_client, result_page_url).await?;
+    let status = query_selector_inner_text(&dom, &COMPILE_STATUS_SELECTOR);
+    let mut result: SubmissionResult = status.parse()?;
+    if let SubmissionResult::CompileError { .. } = result {
+        result =
+            result.with_compile_message(query_selector_inner_text(&dom, &COMPILER_INFO_SELECTOR));
+    }
+    let mut id = String::new();
+    let mut author = String::new();
+    let mut lang = String::new();
+    let mut submission_time = None;
+    let mut memory: Option<String> = None;
+    let mut time: Option<String> = None;
+    let submission_details_dts = dom
+        .select(&SUBMISSION_DETAILS_DTS_SELECTOR)
+        .collect::<Vec<_>>();
+    for dt in submission_details_dts {
+        let dt_text = dt.text().collect::<Vec<&str>>().join("\n");
+        let dd = dt
+            .next_siblings()
+            .find(|element| element.value().is_element());
+        if let Some(dd) = dd {
+            let Some(dd) = ElementRef::wrap(dd) else {
+                continue;
+            };
+            let dd_text = dd.text().collect::<Vec<&str>>().join("\n");
+            match dt_text.as_str() {
+                "#:" => id = dd_text,
+                "提交人:" => author = dd_text,
+                "语言:" => lang = dd_text,
+                "提交时间:" => submission_time = parse_server_time(&dd_text),
+                "内存:" => memory = Some(dd_text),
+                "时间:" => time = Some(dd_text),
+                _ => {}
+            }
+        }
+    }
+
+    let code = query_selector_inner_text(&dom, &SUBMISSION_CODE_SELECTOR);
+
+    Ok(Submission {
+        result,
+        id,
+        author,
+        lang,
+        code,
+        submission_time,
+        memory,
+        time,
+    })
+}
+
+#[instrument(skip(http_client), err)]
+pub async fn search(
+    http_client: &impl Fetcher,
+    group: &str,
+    query: &str,
+) -> Result<Vec<ProblemSearchResult>> {
+    let url = http_client.site_url(Some(group), &format!("/search/?q={}", query));
+    let dom = get_and_parse_html(http_client, &url).await?;
+    let mut results = Vec::new();
+    for element in dom.select(&PROBLEM_LIST_ROW) {
+        let title_anchor = select_first(element, &ROW_TITLE_SELECTOR, &url)?;
+        let title = title_anchor.inner_html();
+        let problem_url = http_client.normalize_url(
+            &url,
+            select_attr(title_anchor, "href", &ROW_TITLE_SELECTOR, &url)?,
+        )?;
+        let id = select_first(element, &ROW_NUMBER_SELECTOR, &url)?.inner_html();
+        let accepted_cnt = parse_text(
+            select_first(element, &ROW_ACCEPTED_CNT_SELECTOR, &url)?,
+            &ROW_ACCEPTED_CNT_SELECTOR,
+            &url,
+        )?;
+        let submit_cnt = parse_text(
+            select_first(element, &ROW_SUBMISSION_CNT_SELECTOR, &url)?,
+            &ROW_SUBMISSION_CNT_SELECTOR,
+            &url,
+        )?;
+        let group = select_first(element, &ROW_GROUP_SELECTOR, &url)?.inner_html();
+        let probset = select_first(element, &ROW_PROBSET_SELECTOR, &url)?.inner_html();
+        results.push(ProblemSearchResult {
+            title,
+            url: problem_url,
+            group,
+            probset,
+            problem_number: id,
+            accepted_cnt,
+            submission_cnt: submit_cnt,
+        });
+    }
+
+    Ok(results)
+}
+
+#[instrument(skip_all, err)]
+pub async fn get_user_info(http_client: &impl Fetcher) -> Result<User> {
+    let index_url = http_client.site_url(None, "/");
+    let dom = get_and_parse_html(http_client, &index_url).await?;
+    let user_homepage_anchor = select_first(&dom, &USER_HOMEPAGE_SELECTOR, &index_url)?;
+    if user_homepage_anchor.inner_html() != "个人首页" {
+        return Err(ParseError::new(
+            &index_url,
+            &USER_HOMEPAGE_SELECTOR,
+            format!(
+                "Selected user homepage anchor does not seem to be correct. Selected value: {}, expected value: {}",
+                user_homepage_anchor.inner_html(),
+                "个人首页"
+            ),
+        )
+        .into());
+    }
+    let homepage_url = select_attr(
+        user_homepage_anchor,
+        "href",
+        &USER_HOMEPAGE_SELECTOR,
+        &index_url,
+    )?;
+    let id = homepage_url
+        .trim_end_matches('/')
+        .split('/')
+        .next_back()
+        .ok_or(anyhow!("Cannot strip user id from user homepage url."))?
+        .to_string();
+
+    let homepage_url = http_client.normalize_url(&index_url, homepage_url)?;
+    let dom = get_and_parse_html(http_client, &homepage_url).await?;
+
+    let username = query_selector_inner_text(&dom, &USERHOME_NAME_SELECTOR);
+    let sex = query_selector_inner_text(&dom, &USERHOME_SEX_SELECTOR);
+    let school = query_selector_inner_text(&dom, &USERHOME_SCHOOL_SELECTOR);
+    let register_time = query_selector_inner_text(&dom, &USERHOME_REGISTER_TIME_SELECTOR);
+
+    Ok(User {
+        id,
+        username,
+        sex,
+        school,
+        register_time,
+    })
+}
+
+/// Lists the solved and attempted problems on the home page of `user_id`,
+/// solved ones first.
+#[instrument(skip(http_client), err)]
+pub async fn get_user_problems(
+    http_client: &impl Fetcher,
+    user_id: &str,
+) -> Result<Vec<UserProblemEntry>> {
+    let url = http_client.site_url(None, &format!("/user/{}/", user_id));
+    let dom = get_and_parse_html(http_client, &url).await?;
+    let mut entries = Vec::new();
+    for (selector, solved) in [
+        (&USERHOME_SOLVED_SELECTOR, true),
+        (&USERHOME_ATTEMPTED_SELECTOR, false),
+    ] {
+        for anchor in dom.select(selector) {
+            let problem_url =
+                http_client.normalize_url(&url, select_attr(anchor, "href", selector, &url)?)?;
+            entries.push(UserProblemEntry {
+                title: anchor.text().collect::<String>().trim().to_string(),
+                problem: problem_url.parse().ok(),
+                url: problem_url,
+                solved,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+#[instrument(skip_all, fields(problem = %problem), err)]
+pub async fn list_submissions(
+    http_client: &impl Fetcher,
+    problem: &ProblemUrl,
+) -> Result<Vec<SubmissionHistoryEntry>> {
+    let prob_url = &problem.page_url(http_client);
+    let dom = get_and_parse_html(http_client, prob_url).await?;
+    let entries = dom
+        .select(&PROBLEM_PAGE_SOLUTION_ROW_SELECTOR)
+        .collect::<Vec<_>>();
+    let mut results = Vec::<SubmissionHistoryEntry>::new();
+    for entry in entries {
+        let result_anchor = select_first(entry, &ROW_RESULT_SELECTOR, prob_url)?;
+        let result = result_anchor.inner_html();
+        let url = http_client.normalize_url(
+            prob_url,
+            select_attr(result_anchor, "href", &ROW_RESULT_SELECTOR, prob_url)?,
+        )?;
+        let time = parse_time(
+            select_first(entry, &ROW_TIME_SELECTOR, prob_url)?,
+            &ROW_TIME_SELECTOR,
+            prob_url,
+        )?;
+        results.push(SubmissionHistoryEntry {
+            result: result.parse()?,
+            time,
+            url,
+        })
+    }
+    Ok(results)
+}
+
+/// Joins `group`, or applies to join it if the group requires approval.
+/// `message` is shown to the group admins along with the application.
+///
+/// Returns the message OpenJudge responds with, if any.
+#[instrument(skip(http_client), err)]
+pub async fn join_group(
+    http_client: &impl Fetcher,
+    group: &str,
+    message: Option<&str>,
+) -> Result<Option<String>> {
+    let response = http_client
+        .post_form(
+            &http_client.site_url(Some(group), "/api/group/join"),
+            &[("message", message.unwrap_or_default())],
+        )
+        .await?;
+    if !response.status.is_success() {
+        return Err(anyhow!("Joining group failed: {}", response.status));
+    }
+    api_message(&response.body)
+}
+
+/// The message of a successful [`ApiResponse`], or an error with the message
+/// of a failed one.
+fn api_message(body: &str) -> Result<Option<String>> {
+    let response: ApiResponse = serde_json::from_str(body)?;
+    if response.result != "SUCCESS" {
+        return Err(anyhow!(
+            response
+                .message
+                .unwrap_or_else(|| "No message provided".to_string())
+        ));
+    }
+    Ok(response.message)
+}
+
+/// Adds a problem to a problem set. Requires admin rights of the group.
+///
+/// Returns the URL of the new problem. Experimental, the endpoint is not
+/// verified against OpenJudge.
+#[instrument(skip(http_client, problem), fields(title = %problem.title), err)]
+pub async fn create_problem(
+    http_client: &impl Fetcher,
+    group: &str,
+    probset: &str,
+    problem: &NewProblem,
+) -> Result<ProblemUrl> {
+    let url = http_client.site_url(Some(group), "/api/problem/create");
+    let time_limit = problem.time_limit.to_string();
+    let memory_limit = problem.memory_limit.to_string();
+    let optional = |field: &Option<String>| field.clone().unwrap_or_default();
+    let (input, output) = (optional(&problem.input), optional(&problem.output));
+    let (sample_input, sample_output) = (
+        optional(&problem.sample_input),
+        optional(&problem.sample_output),
+    );
+    let (hint, source) = (optional(&problem.hint), optional(&problem.source));
+    let response = http_client
+        .post_form(
+            &url,
+            &[
+                ("contest", probset),
+                ("title", &problem.title),
+                ("description", &problem.description),
+                ("input", &input),
+                ("output", &output),
+                ("sampleInput", &sample_input),
+                ("sampleOutput", &sample_output),
+                ("hint", &hint),
+                ("source", &source),
+                ("timeLimit", &time_limit),
+                ("memoryLimit", &memory_limit),
+            ],
+        )
+        .await?;
+    if !response.status.is_success() {
+        return Err(anyhow!("Creating problem failed: {}", response.status));
+    }
+    let response: CreateProblemResponse = serde_json::from_str(&response.body)?;
+    if response.result != "SUCCESS" {
+        return Err(anyhow!(
+            response
+                .message
+                .unwrap_or_else(|| "No message provided".to_string())
+        ));
+    }
+    let path = response
+        .url
+        .ok_or_else(|| anyhow!("Creating problem failed: no URL in response"))?;
+    http_client.normalize_url(&url, &path)?.parse()
+}
+
+/// Uploads test data of a problem, replacing files of the same name. Requires
+/// admin rights of the group.
+///
+/// Returns the message OpenJudge responds with, if any. Experimental, the
+/// endpoint is not verified against OpenJudge.
+#[instrument(skip(http_client, files), fields(files = files.len()), err)]
+pub async fn upload_test_data(
+    http_client: &impl Fetcher,
+    problem: &ProblemUrl,
+    files: &[FormFile],
+) -> Result<Option<String>> {
+    let response = http_client
+        .post_multipart(
+            &http_client.site_url(Some(problem.group()), "/api/problem/data/upload"),
+            &[
+                ("contest", problem.probset()),
+                ("problem", problem.number()),
+            ],
+            files,
+        )
+        .await?;
+    if !response.status.is_success() {
+        return Err(anyhow!("Uploading test data failed: {}", response.status));
+    }
+    api_message(&response.body)
+}
+
+/// Lists the pending join requests of a group. Requires admin rights of the
+/// group. Experimental, the page is not verified against OpenJudge.
+#[instrument(skip(http_client), err)]
+pub async fn get_join_requests(
+    http_client: &impl Fetcher,
+    group: &str,
+) -> Result<Vec<JoinRequest>> {
+    let url = http_client.site_url(Some(group), "/admin/apply/");
+    let dom = get_and_parse_html(http_client, &url).await?;
+    let mut requests = Vec::new();
+    for row in dom.select(&JOIN_REQUEST_ROW_SELECTOR) {
+        let id = select_attr(row, "data-id", &JOIN_REQUEST_ROW_SELECTOR, &url)?.to_string();
+        let user = select_first(row, &JOIN_REQUEST_USER_SELECTOR, &url)?
+            .text()
+            .collect::<String>()
+            .trim()
+            .to_string();
+        let user_url = row
+            .select(&JOIN_REQUEST_USER_ANCHOR_SELECTOR)
+            .next()
+            .and_then(|anchor| anchor.attr("href"))
+            .map(|href| http_client.normalize_url(&url, href))
+            .transpose()?;
+        let message = row
+            .select(&JOIN_REQUEST_MESSAGE_SELECTOR)
+            .next()
+            .map(|td| td.text().collect::<String>().trim().to_string())
+            .filter(|message| !message.is_empty());
+        let time = parse_time(
+            select_first(row, &JOIN_REQUEST_TIME_SELECTOR, &url)?,
+            &JOIN_REQUEST_TIME_SELECTOR,
+            &url,
+        )?;
+        requests.push(JoinRequest {
+            id,
+            user,
+            user_url,
+            message,
+            time,
+        });
+    }
+    Ok(requests)
+}
+
+/// Approves or rejects a join request of a group. Requires admin rights of
+/// the group.
+///
+/// Returns the message OpenJudge responds with, if any. Experimental, the
+/// endpoint is not verified against OpenJudge.
+#[instrument(skip(http_client), err)]
+pub async fn review_join_request(
+    http_client: &impl Fetcher,
+    group: &str,
+    id: &str,
+    approve: bool,
+) -> Result<Option<String>> {
+    let action = if approve { "approve" } else { "reject" };
+    let response = http_client
+        .post_form(
+            &http_client.site_url(Some(group), &format!("/api/group/apply/{}", action)),
+            &[("id", id)],
+        )
+        .await?;
+    if !response.status.is_success() {
+        return Err(anyhow!(
+            "Reviewing join request {} failed: {}",
+            id,
+            response.status
+        ));
+    }
+    api_message(&response.body)
+}
+
+#[instrument(skip(http_client), err)]
+pub async fn get_group_info(http_client: &impl Fetcher, group: &str) -> Result<Group> {
+    let url = http_client.site_url(Some(group), "/");
+    let dom = get_and_parse_html(http_client, &url).await?;
+    let group_name = query_selector_inner_text(&dom, &GROUP_PAGE_NAME_SELECTOR);
+    let group_description = query_selector_inner_text(&dom, &GROUP_PAGE_DESCRIPTION_SELECTOR);
+    let mut probsets = Vec::new();
+    for info in dom.select(&GROUP_PAGE_PROBSET_SELECTOR) {
+        let anchor = select_first(info, &GROUP_PAGE_PROBSET_ANCHOR_SELECTOR, &url)?;
+        let name = anchor.inner_html();
+        let url = http_client.normalize_url(
+            &url,
+            select_attr(anchor, "href", &GROUP_PAGE_PROBSET_ANCHOR_SELECTOR, &url)?,
+        )?;
+        let time = |selector| {
+            info.select(selector)
+                .next()
+                .and_then(|element| parse_server_time(&element.text().collect::<String>()))
+        };
+        probsets.push(ProblemSetEntry {
+            name,
+            url,
+            start: time(&GROUP_PAGE_PROBSET_START_SELECTOR),
+            end: time(&GROUP_PAGE_PROBSET_END_SELECTOR),
+        });
+    }
+    Ok(Group {
+        name: group_name,
+        description: group_description,
+        url,
+        probsets,
+    })
+}
+
+#[instrument(skip(http_client), err)]
+pub async fn get_partial_probset_info(
+    http_client: &impl Fetcher,
+    group: &str,
+    probset: &str,
+    page: Option<u32>,
+) -> Result<ProblemSetPartial> {
+    let url = match page {
+        Some(page) => http_client.site_url(Some(group), &format!("/{}/?page={}", probset, page)),
+        None => http_client.site_url(Some(group), &format!("/{}/", probset)),
+    };
+    let dom = get_and_parse_html(http_client, &url).await?;
+    let entries = dom.select(&PROBSET_PROBLEM_ROW).collect::<Vec<_>>();
+    let mut problems = Vec::new();
+    for entry in entries {
+        let problem_number = select_first(entry, &PROBSET_ROW_NUMBER_SELECTOR, &url)?
+            .text()
+            .collect::<Vec<_>>()
+            .concat();
+        let title_anchor = select_first(entry, &PROBSET_ROW_TITLE_SELECTOR, &url)?;
+        let title = title_anchor.inner_html();
+        let problem_url = http_client.normalize_url(
+            &url,
+            select_attr(title_anchor, "href", &PROBSET_ROW_TITLE_SELECTOR, &url)?,
+        )?;
+        let accepted_population = parse_text(
+            select_first(entry, &PROBSET_ROW_ACCEPTED_CNT_SELECTOR, &url)?,
+            &PROBSET_ROW_ACCEPTED_CNT_SELECTOR,
+            &url,
+        )?;
+        let submit_population = parse_text(
+            select_first(entry, &PROBSET_ROW_SUBMISSION_CNT_SELECTOR, &url)?,
+            &PROBSET_ROW_SUBMISSION_CNT_SELECTOR,
+            &url,
+        )?;
+        let solved = entry
+            .select(&PROBSET_ROW_SOLVED_TD_SELECTOR)
+            .next()
+            .map(|td| td.select(&PROBSET_ROW_SOLVED_IMG_SELECTOR).next().is_some());
+        problems.push(ProblemListEntry {
+            problem_number,
+            title,
+            accepted_population,
+            submitters: submit_population,
+            url: problem_url,
+            solved,
+        });
+    }
+    let probset_name = query_selector_inner_text(&dom, &PAGE_HEADER_PROBSET_SELECTOR);
+    let group_name = query_selector_inner_text(&dom, &PAGE_HEADER_GROUP_SELECTOR);
+    let (page, max_page) = parse_page_bar(&dom);
+    Ok(ProblemSetPartial {
+        problems,
+        name: probset_name,
+        group_name,
+        url,
+        page,
+        max_page,
+    })
+}
+
+/// Lists the topics on page `page` of the discussion board of `problem`.
+#[instrument(skip(http_client), fields(problem = %problem), err)]
+pub async fn get_board(
+    http_client: &impl Fetcher,
+    problem: &ProblemUrl,
+    page: Option<u32>,
+) -> Result<BoardPage> {
+    let url = problem.board_url(http_client, page);
+    let dom = get_and_parse_html(http_client, &url).await?;
+    let mut topics = Vec::new();
+    for row in dom.select(&BOARD_TOPIC_ROW_SELECTOR) {
+        let title_anchor = select_first(row, &BOARD_TOPIC_TITLE_SELECTOR, &url)?;
+        let topic_url = http_client.normalize_url(
+            &url,
+            select_attr(title_anchor, "href", &BOARD_TOPIC_TITLE_SELECTOR, &url)?,
+        )?;
+        let replies = parse_text(
+            select_first(row, &BOARD_TOPIC_REPLIES_SELECTOR, &url)?,
+            &BOARD_TOPIC_REPLIES_SELECTOR,
+            &url,
+        )?;
+        topics.push(BoardTopic {
+            title: title_anchor.text().collect::<String>().trim().to_string(),
+            url: topic_url,
+            author: select_first(row, &BOARD_TOPIC_AUTHOR_SELECTOR, &url)?
+                .text()
+                .collect::<String>()
+                .trim()
+                .to_string(),
+            replies,
+            last_post: row
+                .select(&BOARD_TOPIC_LAST_POST_SELECTOR)
+                .next()
+                .and_then(|abbr| parse_server_time(&abbr.text().collect::<String>())),
+        });
+    }
+    let (page, max_page) = parse_page_bar(&dom);
+    Ok(BoardPage {
+        url,
+        page,
+        max_page,
+        topics,
+    })
+}
+
+/// Posts of the board topic at `url`, oldest first.
+#[instrument(skip(http_client), err)]
+pub async fn get_board_posts(http_client: &impl Fetcher, url: &str) -> Result<Vec<BoardPost>> {
+    let dom = get_and_parse_html(http_client, url).await?;
+    let mut posts = Vec::new();
+    for post in dom.select(&BOARD_POST_SELECTOR) {
+        posts.push(BoardPost {
+            author: select_first(post, &BOARD_POST_AUTHOR_SELECTOR, url)?
+                .text()
+                .collect::<String>()
+                .trim()
+                .to_string(),
+            time: post
+                .select(&BOARD_POST_TIME_SELECTOR)
+                .next()
+                .and_then(|time| parse_server_time(&time.text().collect::<String>())),
+            content: select_first(post, &BOARD_POST_CONTENT_SELECTOR, url)?.inner_html(),
+        });
+    }
+    Ok(posts)
+}
+
+/// Current and last page number from the page bar, 1 if there is none.
+fn parse_page_bar(dom: &scraper::Html) -> (u32, u32) {
+    let page = dom
+        .select(&PAGEBAR_CURRENT_SELECTOR)
+        .next()
+        .map_or(1, |element| element.inner_html().parse().unwrap_or(1u32));
+    let max_page = dom
+        .select(&PAGEBAR_LAST_SELECTOR)
+        .next()
+        .map_or(1, |element| element.inner_html().parse().unwrap_or(1u32));
+    (page, max_page.max(page))
+}
+
+#[instrument(skip(http_client), err)]
+pub async fn get_standings(
+    http_client: &impl Fetcher,
+    group: &str,
+    probset: &str,
+    page: Option<u32>,
+) -> Result<Standings> {
+    let url = match page {
+        Some(page) => {
+            http_client.site_url(Some(group), &format!("/{}/ranking/?page={}", probset, page))
+        }
+        None => http_client.site_url(Some(group), &format!("/{}/ranking/", probset)),
+    };
+    let dom = get_and_parse_html(http_client, &url).await?;
+    let problems = dom
+        .select(&STANDINGS_PROBLEM_HEADER_SELECTOR)
+        .map(|th| th.text().collect::<String>().trim().to_string())
+        .collect::<Vec<_>>();
+    let mut rows = Vec::new();
+    for row in dom.select(&STANDINGS_ROW_SELECTOR) {
+        let rank = parse_text(
+            select_first(row, &STANDINGS_RANK_SELECTOR, &url)?,
+            &STANDINGS_RANK_SELECTOR,
+            &url,
+        )?;
+        let user = select_first(row, &STANDINGS_USER_SELECTOR, &url)?
+            .text()
+            .collect::<String>()
+            .trim()
+            .to_string();
+        let user_url = row
+            .select(&STANDINGS_USER_ANCHOR_SELECTOR)
+            .next()
+            .and_then(|anchor| anchor.attr("href"))
+            .map(|href| http_client.normalize_url(&url, href))
+            .transpose()?;
+        let solved = parse_text(
+            select_first(row, &STANDINGS_SOLVED_SELECTOR, &url)?,
+            &STANDINGS_SOLVED_SELECTOR,
+            &url,
+        )?;
+        let penalty = row
+            .select(&STANDINGS_PENALTY_SELECTOR)
+            .next()
+            .map(|td| td.text().collect::<String>().trim().to_string())
+            .filter(|penalty| !penalty.is_empty());
+        let cells = row
+            .select(&STANDINGS_CELL_SELECTOR)
+            .map(parse_standings_cell)
+            .collect();
+        rows.push(StandingsRow {
+            rank,
+            user,
+            user_url,
+            solved,
+            penalty,
+            cells,
+        });
+    }
+    let (page, max_page) = parse_page_bar(&dom);
+    Ok(Standings {
+        name: query_selector_inner_text(&dom, &PAGE_HEADER_PROBSET_SELECTOR),
+        group_name: query_selector_inner_text(&dom, &PAGE_HEADER_GROUP_SELECTOR),
+        url,
+        page,
+        max_page,
+        problems,
+        rows,
+    })
+}
+
+/// Parses cells like `0:25:00 (-2)`: the time of acceptance followed by the
+/// number of rejected attempts in parentheses.
+fn parse_standings_cell(td: ElementRef) -> Option<StandingsCell> {
+    let text = td.text().collect::<Vec<_>>().join(" ");
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let accepted = td
+        .value()
+        .has_class("accepted", CaseSensitivity::AsciiCaseInsensitive);
+    let (time, failed_attempts) = match text.rsplit_once('(') {
+        Some((time, attempts)) => (
+            time.trim(),
+            attempts
+                .trim_end_matches(')')
+                .trim()
+                .trim_start_matches('-')
+                .parse()
+                .unwrap_or(0),
+        ),
+        None => (text, 0),
+    };
+    Some(StandingsCell {
+        accepted,
+        failed_attempts,
+        time: (accepted && !time.is_empty()).then(|| time.to_string()),
+    })
+}
+
+/// Walks all pages of a problem set, fetching up to `concurrency` pages at
+/// once and yielding entries in order as their pages arrive. The stream ends
+/// after the first error.
+pub fn problem_stream<'a>(
+    http_client: &'a impl Fetcher,
+    group: &'a str,
+    probset: &'a str,
+    concurrency: usize,
+) -> impl Stream<Item = Result<ProblemListEntry>> + 'a {
+    stream_pages(
+        move |page| get_partial_probset_info(http_client, group, probset, Some(page)),
+        |partial| partial.max_page,
+        concurrency,
+    )
+    .flat_map(|partial| {
+        stream::iter(match partial {
+            Ok(partial) => partial.problems.into_iter().map(Ok).collect(),
+            Err(e) => vec![Err(e)],
+        })
+    })
+}
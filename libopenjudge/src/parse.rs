@@ -0,0 +1,113 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use scraper::{ElementRef, selectable::Selectable};
+
+use crate::selectors::NamedSelector;
+
+/// Raised when a page does not contain the markup a scraper expects, which
+/// usually means OpenJudge has changed its page layout.
+#[derive(Debug)]
+pub struct ParseError {
+    pub url: String,
+    pub selector: &'static str,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (selector `{}` on {}). OpenJudge may have changed its page layout, please report this with the HTML of the page.",
+            self.message, self.selector, self.url
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+impl ParseError {
+    pub(crate) fn new(url: &str, selector: &NamedSelector, message: impl Into<String>) -> Self {
+        Self {
+            url: url.to_string(),
+            selector: selector.css,
+            message: message.into(),
+        }
+    }
+}
+
+/// First element matching `selector` within `scope`.
+pub(crate) fn select_first<'a>(
+    scope: impl Selectable<'a>,
+    selector: &NamedSelector,
+    url: &str,
+) -> Result<ElementRef<'a>, ParseError> {
+    scope
+        .select(selector)
+        .next()
+        .ok_or_else(|| ParseError::new(url, selector, "Cannot find element"))
+}
+
+/// Attribute `name` of an element matched by `selector`.
+pub(crate) fn select_attr<'a>(
+    element: ElementRef<'a>,
+    name: &str,
+    selector: &NamedSelector,
+    url: &str,
+) -> Result<&'a str, ParseError> {
+    element.value().attr(name).ok_or_else(|| {
+        ParseError::new(
+            url,
+            selector,
+            format!("Element does not have a `{}` attribute", name),
+        )
+    })
+}
+
+/// OpenJudge shows times in China Standard Time, without an offset.
+pub const SERVER_UTC_OFFSET_SECONDS: i32 = 8 * 3600;
+
+/// Parses a time as shown by OpenJudge, e.g. `2025-03-01 12:00:00`.
+pub fn parse_server_time(text: &str) -> Option<DateTime<FixedOffset>> {
+    let text = text.trim();
+    let naive = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M"))
+        .ok()?;
+    let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET_SECONDS)?;
+    naive.and_local_timezone(offset).single()
+}
+
+/// Parses the time shown by an element matched by `selector`.
+pub(crate) fn parse_time(
+    element: ElementRef<'_>,
+    selector: &NamedSelector,
+    url: &str,
+) -> Result<DateTime<FixedOffset>, ParseError> {
+    let text = element.text().collect::<String>();
+    parse_server_time(&text).ok_or_else(|| {
+        ParseError::new(
+            url,
+            selector,
+            format!("Cannot parse `{}` as a time", text.trim()),
+        )
+    })
+}
+
+/// Parses the text of an element matched by `selector`.
+pub(crate) fn parse_text<T>(
+    element: ElementRef<'_>,
+    selector: &NamedSelector,
+    url: &str,
+) -> Result<T, ParseError>
+where
+    T: FromStr,
+{
+    let text = element.text().collect::<String>();
+    text.trim().parse().map_err(|_| {
+        ParseError::new(
+            url,
+            selector,
+            format!("Cannot parse `{}` as a number", text.trim()),
+        )
+    })
+}
@@ -0,0 +1,127 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{Error, Result, anyhow};
+use url::Url;
+
+use crate::{ROOT_HOST, fetcher::Fetcher};
+
+/// Location of a problem, identified by group, problem set and number.
+///
+/// Parsing accepts full URLs with or without a scheme, trailing slashes and a
+/// `/submit/` suffix, as well as the `group/probset/number` shorthand, e.g.
+/// `cs101/practice/02750`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ProblemUrl {
+    group: String,
+    probset: String,
+    number: String,
+}
+
+impl ProblemUrl {
+    pub fn new(group: &str, probset: &str, number: &str) -> Self {
+        Self {
+            group: group.to_string(),
+            probset: probset.to_string(),
+            number: number.to_string(),
+        }
+    }
+
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    pub fn probset(&self) -> &str {
+        &self.probset
+    }
+
+    pub fn number(&self) -> &str {
+        &self.number
+    }
+
+    /// URL of the problem page, using the scheme `http_client` serves the
+    /// group's host with.
+    pub fn page_url(&self, http_client: &impl Fetcher) -> String {
+        http_client.site_url(
+            Some(&self.group),
+            &format!("/{}/{}/", self.probset, self.number),
+        )
+    }
+
+    /// URL of page `page` of the problem's discussion board, the first page
+    /// if `None`.
+    pub fn board_url(&self, http_client: &impl Fetcher, page: Option<u32>) -> String {
+        let path = format!("/{}/{}/board/", self.probset, self.number);
+        match page {
+            Some(page) => {
+                http_client.site_url(Some(&self.group), &format!("{}?page={}", path, page))
+            }
+            None => http_client.site_url(Some(&self.group), &path),
+        }
+    }
+
+    pub fn submit_url(&self, http_client: &impl Fetcher) -> String {
+        http_client.site_url(
+            Some(&self.group),
+            &format!("/{}/{}/submit/", self.probset, self.number),
+        )
+    }
+
+    fn from_segments<'a>(group: &str, mut segments: impl Iterator<Item = &'a str>) -> Result<Self> {
+        let (Some(probset), Some(number)) = (segments.next(), segments.next()) else {
+            return Err(anyhow!("Missing problem set or problem number."));
+        };
+        match segments.next() {
+            None | Some("submit") => {}
+            Some(extra) => return Err(anyhow!("Unexpected path segment \"{}\".", extra)),
+        }
+        if segments.next().is_some() {
+            return Err(anyhow!("Too many path segments."));
+        }
+        Ok(Self::new(group, probset, number))
+    }
+}
+
+impl FromStr for ProblemUrl {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let invalid = |e: Error| anyhow!("Invalid problem URL \"{}\": {}", s, e);
+        // group/probset/number shorthand, no host involved
+        if !s.contains(ROOT_HOST) {
+            let mut segments = s.split('/').filter(|segment| !segment.is_empty());
+            let group = segments
+                .next()
+                .ok_or_else(|| invalid(anyhow!("Empty input.")))?;
+            return Self::from_segments(group, segments).map_err(invalid);
+        }
+        let with_scheme = if s.contains("://") {
+            s.to_string()
+        } else {
+            format!("https://{}", s)
+        };
+        let url = Url::parse(&with_scheme).map_err(|e| invalid(e.into()))?;
+        let host = url.host_str().unwrap_or_default();
+        let group = host
+            .strip_suffix(ROOT_HOST)
+            .and_then(|group| group.strip_suffix('.'))
+            .filter(|group| !group.is_empty())
+            .ok_or_else(|| invalid(anyhow!("\"{}\" is not a group of {}.", host, ROOT_HOST)))?;
+        let segments = url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|segment| !segment.is_empty());
+        Self::from_segments(group, segments).map_err(invalid)
+    }
+}
+
+impl Display for ProblemUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "https://{}.{}/{}/{}/",
+            self.group, ROOT_HOST, self.probset, self.number
+        )
+    }
+}
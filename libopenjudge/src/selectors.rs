@@ -0,0 +1,182 @@
+use scraper::Selector;
+use std::{ops::Deref, sync::LazyLock};
+
+/// A parsed selector which remembers its source, so parse errors can name it.
+pub struct NamedSelector {
+    pub css: &'static str,
+    selector: Selector,
+}
+
+impl NamedSelector {
+    pub fn new(css: &'static str) -> Self {
+        Self {
+            css,
+            selector: Selector::parse(css).unwrap(),
+        }
+    }
+}
+
+impl Deref for NamedSelector {
+    type Target = Selector;
+    fn deref(&self) -> &Self::Target {
+        &self.selector
+    }
+}
+
+macro_rules! def_lazy_selector {
+    ($ident: ident, $selector: expr) => {
+        pub static $ident: LazyLock<NamedSelector> =
+            LazyLock::new(|| NamedSelector::new($selector));
+    };
+}
+
+// Selectors for page headers like
+// <div class="contest-title-tab">
+//   <h2><a>CS101</a></h2>
+//	 <span>/</span>
+//	 <h2>计算思维算法实践</h2>
+// </div>
+def_lazy_selector!(
+    PAGE_HEADER_GROUP_SELECTOR,
+    "#header .wrapper .contest-title-tab h2 a"
+);
+def_lazy_selector!(
+    PAGE_HEADER_PROBSET_SELECTOR,
+    "#header .wrapper .contest-title-tab h2:nth-child(3)"
+);
+
+// Problem details page selectors:
+def_lazy_selector!(PROBLEM_PAGE_TITLE_SELECTOR, "#pageTitle h2");
+def_lazy_selector!(PROBLEM_PAGE_CONTENT_DTS_SELECTOR, ".problem-content dt");
+def_lazy_selector!(PROBLEM_PAGE_PARAMS_DTS_SELECTOR, ".problem-params dt");
+
+// Selects users' solutions on problem details page
+def_lazy_selector!(PROBLEM_PAGE_SOLUTION_ROW_SELECTOR, ".my-solutions tbody tr");
+def_lazy_selector!(ROW_RESULT_SELECTOR, ".result a");
+def_lazy_selector!(ROW_TIME_SELECTOR, ".time abbr");
+
+// Submission page Selectors:
+def_lazy_selector!(COMPILE_STATUS_SELECTOR, ".compile-status a");
+def_lazy_selector!(COMPILER_INFO_SELECTOR, ".submitStatus pre");
+def_lazy_selector!(SUBMISSION_DETAILS_DTS_SELECTOR, ".compile-info dt");
+def_lazy_selector!(SUBMISSION_CODE_SELECTOR, "#pagebody .wrapper pre");
+
+// Selects a row in problem list of search results
+def_lazy_selector!(PROBLEM_LIST_ROW, "#main .problems-list tbody tr");
+// Selects inside a row in problem list
+def_lazy_selector!(ROW_TITLE_SELECTOR, ".problem-title a");
+def_lazy_selector!(ROW_NUMBER_SELECTOR, ".problem-number");
+def_lazy_selector!(ROW_ACCEPTED_CNT_SELECTOR, ".accepted");
+def_lazy_selector!(ROW_SUBMISSION_CNT_SELECTOR, ".submissions");
+def_lazy_selector!(ROW_GROUP_SELECTOR, ".source a:nth-of-type(1)");
+def_lazy_selector!(ROW_PROBSET_SELECTOR, ".source a:nth-of-type(2)");
+
+// Selects a row in problem list of probset details
+def_lazy_selector!(PROBSET_PROBLEM_ROW, "#main #problemsList tbody tr");
+// Selects inside a row in problem list
+def_lazy_selector!(PROBSET_ROW_TITLE_SELECTOR, ".title a");
+def_lazy_selector!(PROBSET_ROW_NUMBER_SELECTOR, ".problem-id");
+def_lazy_selector!(PROBSET_ROW_ACCEPTED_CNT_SELECTOR, ".accepted a");
+def_lazy_selector!(PROBSET_ROW_SUBMISSION_CNT_SELECTOR, ".submissions a");
+def_lazy_selector!(PROBSET_ROW_SOLVED_TD_SELECTOR, ".solved");
+def_lazy_selector!(PROBSET_ROW_SOLVED_IMG_SELECTOR, "img");
+
+// Selects on contest standings page
+def_lazy_selector!(
+    STANDINGS_PROBLEM_HEADER_SELECTOR,
+    "#main .ranking thead th.problem"
+);
+def_lazy_selector!(STANDINGS_ROW_SELECTOR, "#main .ranking tbody tr");
+// Selects inside a row of standings
+def_lazy_selector!(STANDINGS_RANK_SELECTOR, ".rank");
+def_lazy_selector!(STANDINGS_USER_SELECTOR, ".user");
+def_lazy_selector!(STANDINGS_USER_ANCHOR_SELECTOR, ".user a");
+def_lazy_selector!(STANDINGS_SOLVED_SELECTOR, ".solved");
+def_lazy_selector!(STANDINGS_PENALTY_SELECTOR, ".penalty");
+def_lazy_selector!(STANDINGS_CELL_SELECTOR, "td.problem");
+
+// Selects a row on the join requests page of group management
+def_lazy_selector!(JOIN_REQUEST_ROW_SELECTOR, "#main .apply-list tbody tr");
+// Selects inside a row of join requests
+def_lazy_selector!(JOIN_REQUEST_USER_SELECTOR, ".user");
+def_lazy_selector!(JOIN_REQUEST_USER_ANCHOR_SELECTOR, ".user a");
+def_lazy_selector!(JOIN_REQUEST_MESSAGE_SELECTOR, ".message");
+def_lazy_selector!(JOIN_REQUEST_TIME_SELECTOR, ".time");
+
+// Selects user home page anchor on http://openjudge.cn/
+def_lazy_selector!(USER_HOMEPAGE_SELECTOR, "#userMenu li:nth-of-type(2) a");
+
+// Selects on user home page
+def_lazy_selector!(
+    USERHOME_NAME_SELECTOR,
+    ".user-info .owner-info dl dd:nth-of-type(1)"
+);
+def_lazy_selector!(
+    USERHOME_SEX_SELECTOR,
+    ".user-info .owner-info dl dd:nth-of-type(2)"
+);
+def_lazy_selector!(
+    USERHOME_SCHOOL_SELECTOR,
+    ".user-info .owner-info dl dd:nth-of-type(3)"
+);
+def_lazy_selector!(
+    USERHOME_REGISTER_TIME_SELECTOR,
+    ".user-info .owner-info dl dd:nth-of-type(4)"
+);
+
+def_lazy_selector!(
+    USERHOME_SOLVED_SELECTOR,
+    ".user-problems .solved-problems li a"
+);
+def_lazy_selector!(
+    USERHOME_ATTEMPTED_SELECTOR,
+    ".user-problems .attempted-problems li a"
+);
+
+// Selects on group page
+def_lazy_selector!(
+    GROUP_PAGE_PROBSET_SELECTOR,
+    ".current-contest .practice-info"
+);
+// Selects inside a problem set of group page
+def_lazy_selector!(GROUP_PAGE_PROBSET_ANCHOR_SELECTOR, "h3 a");
+def_lazy_selector!(GROUP_PAGE_PROBSET_START_SELECTOR, ".start-time");
+def_lazy_selector!(GROUP_PAGE_PROBSET_END_SELECTOR, ".end-time");
+def_lazy_selector!(GROUP_PAGE_NAME_SELECTOR, ".group-name h1");
+def_lazy_selector!(GROUP_PAGE_DESCRIPTION_SELECTOR, ".group-description");
+
+// Selectors for page bars on problem list page
+def_lazy_selector!(PAGEBAR_LAST_SELECTOR, ".page-bar .pages *:last-child");
+def_lazy_selector!(PAGEBAR_CURRENT_SELECTOR, ".page-bar .pages .current");
+
+// Selectors for the discussion board of a problem like
+// <table class="board-topics"><tbody><tr>
+//   <td class="title"><a href="/practice/02750/board/1234/">...</a></td>
+//   <td class="author">Alice</td>
+//   <td class="replies">3</td>
+//   <td class="last-post"><abbr>2025-03-01 12:00:00</abbr></td>
+// </tr></tbody></table>
+def_lazy_selector!(BOARD_TOPIC_ROW_SELECTOR, ".board-topics tbody tr");
+def_lazy_selector!(BOARD_TOPIC_TITLE_SELECTOR, ".title a");
+def_lazy_selector!(BOARD_TOPIC_AUTHOR_SELECTOR, ".author");
+def_lazy_selector!(BOARD_TOPIC_REPLIES_SELECTOR, ".replies");
+def_lazy_selector!(BOARD_TOPIC_LAST_POST_SELECTOR, ".last-post abbr");
+// Selectors for the posts of a topic, each a
+// <div class="board-post">
+//   <div class="post-author">Alice</div>
+//   <div class="post-time">2025-03-01 12:00:00</div>
+//   <div class="post-content">...</div>
+// </div>
+def_lazy_selector!(BOARD_POST_SELECTOR, ".board-post");
+def_lazy_selector!(BOARD_POST_AUTHOR_SELECTOR, ".post-author");
+def_lazy_selector!(BOARD_POST_TIME_SELECTOR, ".post-time");
+def_lazy_selector!(BOARD_POST_CONTENT_SELECTOR, ".post-content");
+
+def_lazy_selector!(ERROR_SELECTOR, "#error");
+
+// Selects hidden inputs on submission page
+def_lazy_selector!(SUBMIT_CONTEST_ID_SELECTOR, r#"input[name="contestId"]"#);
+def_lazy_selector!(
+    SUBMIT_PROBLEM_NUMBER_SELECTOR,
+    r#"input[name="problemNumber"]"#
+);
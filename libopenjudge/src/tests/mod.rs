@@ -0,0 +1,596 @@
+#![cfg(test)]
+use std::collections::HashMap;
+
+use anyhow::{Result, anyhow};
+use futures::StreamExt;
+
+use crate::*;
+
+/// Serves saved pages keyed by URL instead of hitting the live site.
+struct FixtureFetcher {
+    pages: HashMap<String, &'static str>,
+    posts: HashMap<String, &'static str>,
+}
+
+impl FixtureFetcher {
+    fn new(pages: &[(&str, &'static str)]) -> Self {
+        Self {
+            pages: pages
+                .iter()
+                .map(|(url, page)| (url.to_string(), *page))
+                .collect(),
+            posts: HashMap::new(),
+        }
+    }
+
+    /// Answers POSTs to `url` with `body`.
+    fn with_post(mut self, url: &str, body: &'static str) -> Self {
+        self.posts.insert(url.to_string(), body);
+        self
+    }
+}
+
+impl Fetcher for FixtureFetcher {
+    async fn get_text(&self, url: &str) -> Result<String> {
+        self.pages
+            .get(url)
+            .map(|page| page.to_string())
+            .ok_or_else(|| anyhow!("No fixture for {}", url))
+    }
+
+    async fn post_form(&self, url: &str, _form: &[(&str, &str)]) -> Result<FetchResponse> {
+        self.posts
+            .get(url)
+            .map(|body| FetchResponse {
+                status: reqwest::StatusCode::OK,
+                body: body.to_string(),
+            })
+            .ok_or_else(|| anyhow!("Unexpected POST to {}", url))
+    }
+
+    async fn post_multipart(
+        &self,
+        url: &str,
+        form: &[(&str, &str)],
+        _files: &[FormFile],
+    ) -> Result<FetchResponse> {
+        self.post_form(url, form).await
+    }
+}
+
+const PROBLEM_URL: &str = "https://cs101.openjudge.cn/practice/02750/";
+const PROBLEM_PAGE: &str = include_str!("fixtures/problem.html");
+const SUBMISSION_PAGE: &str = include_str!("fixtures/submission.html");
+const SUBMISSION_CE_PAGE: &str = include_str!("fixtures/submission_compile_error.html");
+const SEARCH_PAGE: &str = include_str!("fixtures/search.html");
+const GROUP_PAGE: &str = include_str!("fixtures/group.html");
+const PROBSET_PAGE_1: &str = include_str!("fixtures/probset_page1.html");
+const PROBSET_PAGE_2: &str = include_str!("fixtures/probset_page2.html");
+const INDEX_PAGE: &str = include_str!("fixtures/index.html");
+const USER_PAGE: &str = include_str!("fixtures/user.html");
+const STANDINGS_PAGE: &str = include_str!("fixtures/standings.html");
+const JOIN_REQUESTS_PAGE: &str = include_str!("fixtures/join_requests.html");
+const BOARD_PAGE: &str = include_str!("fixtures/board.html");
+const BOARD_TOPIC_PAGE: &str = include_str!("fixtures/board_topic.html");
+
+#[tokio::test]
+async fn problem_page() {
+    let fetcher = FixtureFetcher::new(&[(PROBLEM_URL, PROBLEM_PAGE)]);
+    let problem = get_problem(&fetcher, &PROBLEM_URL.parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(problem.title, "02750:鸡兔同笼");
+    assert_eq!(problem.group, "CS101");
+    assert_eq!(problem.probset, "计算思维算法实践");
+    assert_eq!(
+        problem.limits,
+        ProblemLimits {
+            total_time: Some(1000),
+            case_time: None,
+            memory: Some(65536),
+            special_judge: false,
+        }
+    );
+    assert!(problem.description.contains("一个笼子里面关了鸡和兔子"));
+    assert!(problem.input.unwrap().contains("正整数a"));
+    assert!(problem.output.is_some());
+    assert!(problem.sample_input.unwrap().contains("<pre>20</pre>"));
+    assert!(problem.sample_output.unwrap().contains("<pre>5 10</pre>"));
+    assert!(problem.hint.unwrap().contains("注意无解的情况"));
+    assert!(problem.source.unwrap().contains("期末考试"));
+}
+
+#[tokio::test]
+async fn submission_page() {
+    let url = "https://cs101.openjudge.cn/practice/solution/45678901/";
+    let fetcher = FixtureFetcher::new(&[(url, SUBMISSION_PAGE)]);
+    let submission = query_submission_result(&fetcher, url).await.unwrap();
+    assert!(matches!(submission.result, SubmissionResult::Accepted));
+    assert_eq!(submission.id, "45678901");
+    assert_eq!(submission.author, "Alice");
+    assert_eq!(submission.lang, "G++");
+    assert_eq!(
+        submission.submission_time.unwrap().to_rfc3339(),
+        "2025-03-01T12:00:00+08:00"
+    );
+    assert_eq!(submission.memory.as_deref(), Some("128kB"));
+    assert_eq!(submission.time.as_deref(), Some("1ms"));
+    assert_eq!(
+        submission.code,
+        "#include <cstdio>\nint main() { return 0; }"
+    );
+}
+
+#[tokio::test]
+async fn compile_error_submission_page() {
+    let url = "https://cs101.openjudge.cn/practice/solution/45678902/";
+    let fetcher = FixtureFetcher::new(&[(url, SUBMISSION_CE_PAGE)]);
+    let submission = query_submission_result(&fetcher, url).await.unwrap();
+    match submission.result {
+        SubmissionResult::CompileError { message } => {
+            assert!(message.unwrap().contains("Main.cpp:3:5: error"));
+        }
+        _ => panic!("Expected compile error"),
+    }
+    assert!(submission.time.is_none());
+    assert!(submission.memory.is_none());
+}
+
+#[tokio::test]
+async fn submission_list() {
+    let fetcher = FixtureFetcher::new(&[(PROBLEM_URL, PROBLEM_PAGE)]);
+    let submissions = list_submissions(&fetcher, &PROBLEM_URL.parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(submissions.len(), 2);
+    assert!(matches!(submissions[0].result, SubmissionResult::Accepted));
+    assert_eq!(
+        submissions[0].time.to_rfc3339(),
+        "2025-03-01T12:00:00+08:00"
+    );
+    assert_eq!(
+        submissions[0].url,
+        "https://cs101.openjudge.cn/practice/solution/45678901/"
+    );
+    assert!(matches!(
+        submissions[1].result,
+        SubmissionResult::WrongAnswer
+    ));
+}
+
+#[tokio::test]
+async fn search_page() {
+    let fetcher =
+        FixtureFetcher::new(&[("https://cs101.openjudge.cn/search/?q=鸡兔", SEARCH_PAGE)]);
+    let results = search(&fetcher, "cs101", "鸡兔").await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].problem_number, "02750");
+    assert_eq!(results[0].title, "鸡兔同笼");
+    assert_eq!(results[0].url, PROBLEM_URL);
+    assert_eq!(results[0].accepted_cnt, 1234);
+    assert_eq!(results[0].submission_cnt, 5678);
+    assert_eq!(results[0].group, "CS101");
+    assert_eq!(results[0].probset, "计算思维算法实践");
+    assert_eq!(results[1].url, "http://cs101.openjudge.cn/2025hw1/E01001/");
+}
+
+#[tokio::test]
+async fn group_page() {
+    let fetcher = FixtureFetcher::new(&[("https://cs101.openjudge.cn/", GROUP_PAGE)]);
+    let group = get_group_info(&fetcher, "cs101").await.unwrap();
+    assert_eq!(group.name, "CS101");
+    assert_eq!(group.description, "计算概论（B）课程小组");
+    assert_eq!(group.probsets.len(), 2);
+    assert_eq!(group.probsets[0].name, "计算思维算法实践");
+    assert_eq!(
+        group.probsets[0].url,
+        "https://cs101.openjudge.cn/practice/"
+    );
+    assert_eq!(group.probsets[0].end, None);
+    assert_eq!(
+        group.probsets[1].start.map(|start| start.to_rfc3339()),
+        Some("2025-03-01T08:00:00+08:00".to_string())
+    );
+    assert_eq!(
+        group.probsets[1].end.map(|end| end.to_rfc3339()),
+        Some("2025-03-08T23:59:00+08:00".to_string())
+    );
+}
+
+#[tokio::test]
+async fn probset_page() {
+    let fetcher = FixtureFetcher::new(&[("https://cs101.openjudge.cn/practice/", PROBSET_PAGE_1)]);
+    let probset = get_partial_probset_info(&fetcher, "cs101", "practice", None)
+        .await
+        .unwrap();
+    assert_eq!(probset.name, "计算思维算法实践");
+    assert_eq!(probset.group_name, "CS101");
+    assert_eq!(probset.page, 1);
+    assert_eq!(probset.max_page, 2);
+    assert_eq!(probset.problems.len(), 2);
+    let first = &probset.problems[0];
+    assert_eq!(first.problem_number, "02750");
+    assert_eq!(first.title, "鸡兔同笼");
+    assert_eq!(first.url, PROBLEM_URL);
+    assert_eq!(first.accepted_population, 1234);
+    assert_eq!(first.submitters, 1500);
+    assert_eq!(first.solved, Some(true));
+    assert_eq!(probset.problems[1].solved, Some(false));
+}
+
+#[tokio::test]
+async fn probset_stream() {
+    let fetcher = FixtureFetcher::new(&[
+        (
+            "https://cs101.openjudge.cn/practice/?page=1",
+            PROBSET_PAGE_1,
+        ),
+        (
+            "https://cs101.openjudge.cn/practice/?page=2",
+            PROBSET_PAGE_2,
+        ),
+    ]);
+    let numbers = problem_stream(&fetcher, "cs101", "practice", DEFAULT_CONCURRENCY)
+        .map(|entry| entry.unwrap().problem_number)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(numbers, vec!["02750", "02751", "02752"]);
+}
+
+#[tokio::test]
+async fn user_pages() {
+    let fetcher = FixtureFetcher::new(&[
+        ("https://openjudge.cn/", INDEX_PAGE),
+        ("http://openjudge.cn/user/1234567/", USER_PAGE),
+    ]);
+    let user = get_user_info(&fetcher).await.unwrap();
+    assert_eq!(user.id, "1234567");
+    assert_eq!(user.username, "Alice");
+    assert_eq!(user.sex, "女");
+    assert_eq!(user.school, "北京大学");
+    assert_eq!(user.register_time, "2024-09-01");
+}
+
+#[tokio::test]
+async fn user_problems() {
+    let fetcher = FixtureFetcher::new(&[("https://openjudge.cn/user/1234567/", USER_PAGE)]);
+    let problems = get_user_problems(&fetcher, "1234567").await.unwrap();
+    assert_eq!(problems.len(), 3);
+    assert_eq!(problems[0].title, "02750:鸡兔同笼");
+    assert_eq!(
+        problems[0].problem,
+        Some(ProblemUrl::new("cs101", "practice", "02750"))
+    );
+    assert!(problems[1].solved);
+    assert!(!problems[2].solved);
+    assert_eq!(problems[2].url, "http://cs101.openjudge.cn/practice/02752/");
+}
+
+#[test]
+fn problem_url_forms() {
+    let expected = ProblemUrl::new("cs101", "practice", "02750");
+    for input in [
+        "https://cs101.openjudge.cn/practice/02750/",
+        "http://cs101.openjudge.cn/practice/02750",
+        "cs101.openjudge.cn/practice/02750/",
+        "https://cs101.openjudge.cn/practice/02750/submit/",
+        "cs101/practice/02750",
+        " /cs101/practice/02750/ ",
+    ] {
+        assert_eq!(input.parse::<ProblemUrl>().unwrap(), expected, "{}", input);
+    }
+    assert_eq!(
+        expected.to_string(),
+        "https://cs101.openjudge.cn/practice/02750/"
+    );
+}
+
+#[test]
+fn problem_url_rejects_invalid() {
+    for input in [
+        "",
+        "cs101/practice",
+        "https://openjudge.cn/practice/02750/",
+        "https://cs101.openjudge.cn/practice/02750/status/",
+        "cs101/practice/02750/submit/extra",
+    ] {
+        assert!(input.parse::<ProblemUrl>().is_err(), "{}", input);
+    }
+}
+
+#[tokio::test]
+async fn join_bounded_keeps_order_and_limit() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let in_flight = AtomicUsize::new(0);
+    let peak = AtomicUsize::new(0);
+    let futures = (0..10).map(|i| {
+        let (in_flight, peak) = (&in_flight, &peak);
+        async move {
+            let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(current, Ordering::SeqCst);
+            // later items finish first
+            for _ in 0..(10 - i) {
+                tokio::task::yield_now().await;
+            }
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            i
+        }
+    });
+    let results = join_bounded(futures, 3).await;
+    assert_eq!(results, (0..10).collect::<Vec<_>>());
+    assert!(peak.load(Ordering::SeqCst) <= 3);
+}
+
+#[tokio::test]
+async fn stream_pages_in_order() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let (in_flight, peak) = (AtomicUsize::new(0), AtomicUsize::new(0));
+    let (in_flight, peak) = (&in_flight, &peak);
+    // page 6 of 8 fails, so 7 and 8 are never yielded
+    let fetch_page = |page: u32| async move {
+        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        peak.fetch_max(current, Ordering::SeqCst);
+        for _ in 0..(10 - page) {
+            tokio::task::yield_now().await;
+        }
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+        match page {
+            6 => Err(anyhow!("page {} failed", page)),
+            page => Ok((page, 8)),
+        }
+    };
+    let pages = stream_pages(fetch_page, |(_, max_page)| *max_page, 3)
+        .collect::<Vec<_>>()
+        .await;
+    let numbers = pages
+        .iter()
+        .map(|page| page.as_ref().map(|(page, _)| *page).ok())
+        .collect::<Vec<_>>();
+    assert_eq!(numbers, [Some(1), Some(2), Some(3), Some(4), Some(5), None]);
+    assert!(peak.load(Ordering::SeqCst) <= 3);
+}
+
+#[test]
+fn submission_result_from_str() {
+    assert_eq!("Accepted".parse(), Ok(SubmissionResult::Accepted));
+    assert_eq!(
+        " Time Limit Exceeded\n".parse(),
+        Ok(SubmissionResult::TimeLimitExceeded)
+    );
+    assert_eq!("Something new".parse(), Ok(SubmissionResult::Unknown));
+    let compile_error: SubmissionResult = "Compile Error".parse().unwrap();
+    assert_eq!(
+        compile_error.with_compile_message("error".to_string()),
+        SubmissionResult::CompileError {
+            message: Some("error".to_string())
+        }
+    );
+    assert_eq!(
+        SubmissionResult::Accepted.with_compile_message("error".to_string()),
+        SubmissionResult::Accepted
+    );
+}
+
+#[test]
+fn cookie_jar_round_trip() {
+    use reqwest::{cookie::CookieStore, header::HeaderValue};
+
+    let url = url::Url::parse("https://openjudge.cn/").unwrap();
+    let jar = CookieJar::new();
+    let header = HeaderValue::from_static("PHPSESSID=abc123; Path=/");
+    jar.provider().set_cookies(&mut [&header].into_iter(), &url);
+
+    let path =
+        std::env::temp_dir().join(format!("libopenjudge-cookies-{}.json", std::process::id()));
+    jar.save(&path).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    let loaded = CookieJar::load(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(
+        loaded.provider().cookies(&url).unwrap(),
+        HeaderValue::from_static("PHPSESSID=abc123")
+    );
+
+    loaded.clear();
+    assert!(loaded.provider().cookies(&url).is_none());
+    assert!(
+        CookieJar::load(&path)
+            .unwrap()
+            .provider()
+            .cookies(&url)
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn standings_page() {
+    let fetcher = FixtureFetcher::new(&[(
+        "https://cs101.openjudge.cn/midterm/ranking/?page=2",
+        STANDINGS_PAGE,
+    )]);
+    let standings = get_standings(&fetcher, "cs101", "midterm", Some(2))
+        .await
+        .unwrap();
+    assert_eq!(standings.name, "2024期中考试");
+    assert_eq!((standings.page, standings.max_page), (2, 3));
+    assert_eq!(standings.problems, ["A", "B", "C"]);
+    assert_eq!(standings.rows.len(), 2);
+    let first = &standings.rows[0];
+    assert_eq!((first.rank, first.solved), (1, 2));
+    assert_eq!(first.user, "Alice");
+    assert_eq!(
+        first.user_url.as_deref(),
+        Some("http://openjudge.cn/user/1234567/")
+    );
+    assert_eq!(first.penalty.as_deref(), Some("1:05:00"));
+    assert_eq!(
+        first.cells[1],
+        Some(StandingsCell {
+            accepted: true,
+            failed_attempts: 2,
+            time: Some("0:25:00".to_string()),
+        })
+    );
+    assert_eq!(first.cells[2], None);
+    assert_eq!(
+        standings.rows[1].cells[1],
+        Some(StandingsCell {
+            accepted: false,
+            failed_attempts: 3,
+            time: None,
+        })
+    );
+}
+
+#[tokio::test]
+async fn join_group_responses() {
+    let fetcher = FixtureFetcher::new(&[])
+        .with_post(
+            "https://cs101.openjudge.cn/api/group/join",
+            r#"{"result":"SUCCESS","message":"申请已提交，请等待管理员审核"}"#,
+        )
+        .with_post(
+            "https://closed.openjudge.cn/api/group/join",
+            r#"{"result":"ERROR","message":"该小组不接受加入申请"}"#,
+        );
+    assert_eq!(
+        join_group(&fetcher, "cs101", Some("hi")).await.unwrap(),
+        Some("申请已提交，请等待管理员审核".to_string())
+    );
+    let error = join_group(&fetcher, "closed", None).await.unwrap_err();
+    assert_eq!(error.to_string(), "该小组不接受加入申请");
+}
+
+#[tokio::test]
+async fn join_request_management() {
+    let fetcher = FixtureFetcher::new(&[(
+        "https://cs101.openjudge.cn/admin/apply/",
+        JOIN_REQUESTS_PAGE,
+    )])
+    .with_post(
+        "https://cs101.openjudge.cn/api/group/apply/approve",
+        r#"{"result":"SUCCESS"}"#,
+    )
+    .with_post(
+        "https://cs101.openjudge.cn/api/group/apply/reject",
+        r#"{"result":"ERROR","message":"申请不存在"}"#,
+    );
+    let requests = get_join_requests(&fetcher, "cs101").await.unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].id, "8812");
+    assert_eq!(requests[0].user, "Alice");
+    assert_eq!(
+        requests[0].user_url.as_deref(),
+        Some("http://openjudge.cn/user/1234567/")
+    );
+    assert_eq!(requests[0].message.as_deref(), Some("2400012345 张三"));
+    assert_eq!(requests[0].time.to_rfc3339(), "2024-09-02T08:15:30+08:00");
+    assert_eq!(requests[1].message, None);
+    assert_eq!(
+        review_join_request(&fetcher, "cs101", "8812", true)
+            .await
+            .unwrap(),
+        None
+    );
+    let error = review_join_request(&fetcher, "cs101", "1", false)
+        .await
+        .unwrap_err();
+    assert_eq!(error.to_string(), "申请不存在");
+}
+
+#[tokio::test]
+async fn problem_creation() {
+    let fetcher = FixtureFetcher::new(&[])
+        .with_post(
+            "https://cs101.openjudge.cn/api/problem/create",
+            r#"{"result":"SUCCESS","url":"/hw1/E/"}"#,
+        )
+        .with_post(
+            "https://cs101.openjudge.cn/api/problem/data/upload",
+            r#"{"result":"SUCCESS","message":"上传了2个文件"}"#,
+        );
+    let problem = NewProblem {
+        title: "A+B".to_string(),
+        description: "<p>Add two numbers.</p>".to_string(),
+        time_limit: 1000,
+        memory_limit: 65536,
+        ..Default::default()
+    };
+    let url = create_problem(&fetcher, "cs101", "hw1", &problem)
+        .await
+        .unwrap();
+    assert_eq!(url, ProblemUrl::new("cs101", "hw1", "E"));
+    let files = [FormFile {
+        field: "files".to_string(),
+        file_name: "1.in".to_string(),
+        content: b"1 2\n".to_vec(),
+    }];
+    assert_eq!(
+        upload_test_data(&fetcher, &url, &files).await.unwrap(),
+        Some("上传了2个文件".to_string())
+    );
+}
+
+#[test]
+fn server_time_parsing() {
+    let time = parse_server_time("2025-03-01 12:00").unwrap();
+    assert_eq!(time.to_rfc3339(), "2025-03-01T12:00:00+08:00");
+    assert!(parse_server_time("yesterday").is_none());
+}
+
+#[test]
+fn mirror_urls_are_normalized() {
+    let client = Client::new(reqwest::Client::new(), Scheme::Https, None).with_hosts(vec![
+        "openjudge.cn".to_string(),
+        "oj.example.com".to_string(),
+    ]);
+    assert_eq!(client.active_host(), "openjudge.cn");
+    assert_eq!(
+        client
+            .normalize_url("https://cs101.oj.example.com/practice/", "01234/")
+            .unwrap(),
+        "https://cs101.openjudge.cn/practice/01234/"
+    );
+    assert_eq!(
+        client
+            .normalize_url("https://oj.example.com/", "https://notoj.example.com/a")
+            .unwrap(),
+        "https://notoj.example.com/a"
+    );
+}
+
+#[tokio::test]
+async fn board_page() {
+    let fetcher = FixtureFetcher::new(&[
+        (
+            "https://cs101.openjudge.cn/practice/02750/board/",
+            BOARD_PAGE,
+        ),
+        (
+            "https://cs101.openjudge.cn/practice/02750/board/1201/",
+            BOARD_TOPIC_PAGE,
+        ),
+    ]);
+    let board = get_board(&fetcher, &PROBLEM_URL.parse().unwrap(), None)
+        .await
+        .unwrap();
+    assert_eq!((board.page, board.max_page), (1, 2));
+    assert_eq!(board.topics.len(), 2);
+    let topic = &board.topics[0];
+    assert_eq!(topic.title, "输入有多组数据吗？");
+    assert_eq!(topic.author, "Alice");
+    assert_eq!(topic.replies, 2);
+    assert_eq!(topic.last_post, parse_server_time("2025-03-01 12:00:00"));
+    assert_eq!(board.topics[1].last_post, None);
+    let posts = get_board_posts(&fetcher, &topic.url).await.unwrap();
+    assert_eq!(posts.len(), 2);
+    assert_eq!(posts[1].author, "Carol");
+    assert!(posts[1].content.contains("<code>n</code>"));
+}
@@ -0,0 +1,43 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+/// Most invocations kept, older ones are dropped.
+const MAX_ACTIVITIES: usize = 200;
+
+/// Recent invocations of the CLI, kept in `activity.json` in the config root,
+/// oldest first. It is not where `.` comes from: the recent problems in the
+/// config also change when a problem is opened in the TUI, which this log
+/// knows nothing about, while this log holds failed commands too and drops
+/// old entries.
+#[derive(Serialize, Deserialize, Default)]
+pub struct ActivityLog {
+    #[serde(default)]
+    pub entries: Vec<Activity>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Activity {
+    pub time: DateTime<FixedOffset>,
+    /// Arguments after the program name.
+    pub args: Vec<String>,
+    /// Directory the command was run in, relative paths are resolved from it.
+    pub dir: Option<String>,
+    /// Problem the command operated on, if any.
+    pub target: Option<String>,
+    /// `None` on success, the error message on failure.
+    pub error: Option<String>,
+}
+
+impl ActivityLog {
+    /// Appends `activity`, dropping the oldest ones past the limit.
+    pub fn push(&mut self, activity: Activity) {
+        self.entries.push(activity);
+        let excess = self.entries.len().saturating_sub(MAX_ACTIVITIES);
+        self.entries.drain(..excess);
+    }
+
+    /// The `n`th latest activity, counting from 1.
+    pub fn nth_latest(&self, n: usize) -> Option<&Activity> {
+        self.entries.iter().rev().nth(n.checked_sub(1)?)
+    }
+}
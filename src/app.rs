@@ -1,36 +1,194 @@
 use anyhow::{Context, Result};
+use chrono::format::{Item, StrftimeItems};
 use colored::Colorize;
 use keyring::Entry;
 use nanoid::nanoid;
 use onig::{self, Regex};
 use serde::{Deserialize, Serialize};
-use similar::{ChangeTag, TextDiff};
 
+use futures::StreamExt;
 #[cfg(unix)]
 use std::os::unix::process::ExitStatusExt;
-use std::{fmt::Write as fmtWrite, fs, io::Write, process};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt::Write as fmtWrite,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    pin::pin,
+    process,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 use syntect::{
     easy::HighlightLines, highlighting::Style, parsing::SyntaxSet, util::as_24_bit_terminal_escaped,
 };
+use tokio::sync::OnceCell;
+
+use libopenjudge::{
+    self, ClientOptions, Language, ParseError, Problem, ProblemUrl, Scheme, SubmissionResult,
+};
 
 use crate::{
-    code_theme,
+    activity::{Activity, ActivityLog},
+    backup, bundle, code_theme,
+    daily::{self, DailyEntry, DailyLog},
+    diagnostics::{compiler_warnings, render_diagnostic_list, render_diagnostics},
+    diff::{DiffStyle, render_diff},
     display::*,
-    libopenjudge::{self, Language, Problem},
+    doctor,
+    i18n::{self, Lang},
+    manifest::{MANIFEST_FILE_NAME, Manifest, ManifestProblem},
+    pending::{PendingLog, PendingSubmission},
+    problem_spec::ProblemSpec,
+    sanity,
+    solves::{SolveLog, source_hash, streaks},
+    tui,
     utils::{
-        html::{GraphicsProtocol, get_printable_html_text},
-        interactions::{self, select_within},
+        clipboard::{self, Copied},
+        html::{
+            DEFAULT_IMAGE_MAX_ROWS, GraphicsOptions, GraphicsProtocol, RenderMode, SixelDiffusion,
+            SixelOptions, attachment_links, fetch_images, image_name, image_sources, render_html,
+            render_image,
+        },
+        interactions::{self, Selection, StatusBar, select_within},
+        json_file::{load_json, save_json},
+        progress::{Spinner, spin},
+        table::Table,
+        terminal::{screen_reader, set_screen_reader, vertical_bar},
     },
 };
 
-#[derive(Serialize, Deserialize, Default)]
+/// Upper bound of concurrent requests for operations spanning several pages,
+/// unless configured otherwise.
+const FETCH_CONCURRENCY: usize = libopenjudge::DEFAULT_CONCURRENCY;
+
+/// C++ standard OpenJudge compiles G++ submissions with.
+const JUDGE_CPP_STD: &str = "gnu++14";
+
+/// C standard OpenJudge compiles GCC submissions with.
+const JUDGE_C_STD: &str = "gnu99";
+
+/// Problems remembered for `.` and `~N`, older ones are dropped.
+const RECENT_PROBLEMS: usize = 10;
+
+#[derive(Serialize, Deserialize, Default, Clone)]
 struct AppConfig {
     user_email: Option<String>,
+    /// Latest problem as saved by earlier versions, read until
+    /// `recent_problems` is first written.
+    #[serde(skip_serializing)]
     last_problem: Option<String>,
+    /// Recently operated problems, the latest first.
+    recent_problems: Option<Vec<String>>,
     graphics_protocol: Option<GraphicsProtocol>,
+    prefer_https: Option<bool>,
+    user_agent: Option<String>,
+    code_theme: Option<String>,
+    time_format: Option<String>,
+    relative_time: Option<bool>,
+    verdict_style: Option<VerdictStyle>,
+    sixel: Option<SixelOptions>,
+    image_max_rows: Option<u32>,
+    inline_select: Option<bool>,
+    languages: Option<Vec<CustomLanguage>>,
+    cpp_std: Option<String>,
+    /// Compiler warnings failing a local test, by flag such as `format` for
+    /// `-Wformat`.
+    blocking_warnings: Option<Vec<String>>,
+    python: Option<String>,
+    /// Command opening a file for `oj edit`, the file is passed as its last
+    /// argument.
+    editor: Option<String>,
+    /// Directory accepted solutions are copied into, see [`archive_path`].
+    archive_dir: Option<String>,
+    friends: Option<Vec<Friend>>,
+    /// Shell commands checking a source file before it is submitted, see
+    /// [`expand_command_template`] for the placeholders.
+    pre_submit_hooks: Option<Vec<String>>,
+    /// Whether C and C++ solutions are checked for common pitfalls before
+    /// they are submitted.
+    sanity_checks: Option<bool>,
+    /// Whether to ring the terminal bell when a submission is judged or a
+    /// long local test finishes, see [`notify_finished`].
+    bell: Option<bool>,
+    /// Shell command played instead of the bell, such as a sound player.
+    sound_command: Option<String>,
+    /// Language of messages, detected from the system locale when unset.
+    lang: Option<Lang>,
+    screen_reader: Option<bool>,
+    /// Base hosts serving OpenJudge, failed over between in order.
+    hosts: Option<Vec<String>>,
+    /// Requests kept in flight by operations spanning several pages.
+    concurrency: Option<usize>,
+    /// Idle connections kept per host for reuse.
+    pool_size: Option<usize>,
+    /// Seconds an idle connection is kept for reuse.
+    keep_alive: Option<u64>,
+    /// Problem URLs by name, used as `@name` in place of a URL.
+    aliases: Option<BTreeMap<String, String>>,
+}
+
+/// A classmate compared against on `oj leaderboard`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Friend {
+    /// OpenJudge user ID, as in http://openjudge.cn/user/{id}/
+    pub id: String,
+    pub name: Option<String>,
+}
+
+impl FromStr for Friend {
+    type Err = anyhow::Error;
+    /// Parses `ID` or `ID=NAME`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (id, name) = match value.split_once('=') {
+            Some((id, name)) => (id.trim(), Some(name.trim().to_string())),
+            None => (value.trim(), None),
+        };
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(anyhow::format_err!("Invalid value for Friend: {}", value));
+        }
+        Ok(Friend {
+            id: id.to_string(),
+            name: name.filter(|name| !name.is_empty()),
+        })
+    }
+}
+
+/// A language declared in the config file, for judges the CLI has no built-in
+/// support for. Commands are run by the shell, with `{source}`, `{executable}`,
+/// `{dir}` and `{stem}` replaced by the source file, the compiled program, the
+/// directory of the source file and its name without extension, each quoted
+/// for the shell.
+#[derive(Serialize, Deserialize, Clone)]
+struct CustomLanguage {
+    /// Accepted by `--lang`, case insensitive.
+    name: String,
+    /// File extensions, without the dot.
+    #[serde(default)]
+    extensions: Vec<String>,
+    /// Value of the language field sent to OpenJudge, e.g. "Java".
+    submit_as: String,
+    /// Command building the solution, if it needs to be compiled.
+    compile: Option<String>,
+    /// Command running the solution on stdin.
+    run: String,
 }
 
 impl AppConfig {
+    fn custom_languages(&self) -> &[CustomLanguage] {
+        self.languages.as_deref().unwrap_or_default()
+    }
+
+    fn custom_language(&self, lang: &Language) -> Option<&CustomLanguage> {
+        let Language::Custom(submit_as) = lang else {
+            return None;
+        };
+        self.custom_languages()
+            .iter()
+            .find(|language| &language.submit_as == submit_as)
+    }
+
     fn read_config<P>(config_path: P) -> Result<Option<Self>>
     where
         P: AsRef<std::path::Path>,
@@ -63,46 +221,97 @@ impl AppConfig {
     }
 }
 
-fn determine_language(file: &str, specified_lang: Option<String>) -> Result<Language> {
+fn determine_language(
+    file: &str,
+    specified_lang: Option<String>,
+    custom: &[CustomLanguage],
+) -> Result<Language> {
     let file = std::path::PathBuf::from(file);
     if !file.exists() {
-        return Err(anyhow::anyhow!("File does not exist."))
-            .context(format!("Reading {}", file.display()));
+        return Err(anyhow::anyhow!(tr!("file-not-found")))
+            .context(tr!("reading-file", path = file.display().to_string()));
     }
     if !file.is_file() {
-        return Err(
-            anyhow::anyhow!("Path is not a file.").context(format!("Reading {}", file.display()))
-        );
+        return Err(anyhow::anyhow!(tr!("not-a-file"))
+            .context(tr!("reading-file", path = file.display().to_string())));
     }
-    specified_lang.map(|lang| {
-        match lang.to_lowercase()
-            .as_str() {
+    let custom_by = |matches: &dyn Fn(&CustomLanguage) -> bool| {
+        custom
+            .iter()
+            .find(|language| matches(language))
+            .map(|language| Language::Custom(language.submit_as.clone()))
+    };
+    specified_lang
+        .map(|lang| {
+            if let Some(language) = custom_by(&|language| language.name.eq_ignore_ascii_case(&lang))
+            {
+                return Ok(language);
+            }
+            match lang.to_lowercase().as_str() {
                 "c" | "gcc" => Ok(Language::Gcc),
                 "cpp" | "g++" => Ok(Language::Gpp),
                 "py" | "python" | "py3" | "python3" => Ok(Language::Python3),
                 "pypy" | "pypy3" => Ok(Language::PyPy3),
-                _ => Err(anyhow::anyhow!("Invalid language. Supported values: C, GCC, C++, G++, Py, Python, Py3, Python3, PyPy, PyPy3")).context(format!("Reading {}", file.display())),
+                "pas" | "pascal" | "fpc" => Ok(Language::Pascal),
+                _ => Err(anyhow::anyhow!(tr!("invalid-language")))
+                    .context(tr!("reading-file", path = file.display().to_string())),
             }
-    })
-    .unwrap_or_else(|| {
-        match file
-            .extension()
-            .expect("Source code must provide an extension of '.c', '.cpp' or '.py', or specify the language with the --lang flag.")
-            .to_str()
-            .to_owned()
-            .unwrap()
-            .to_lowercase()
-            .as_str()
-        {
-            "cpp" => Ok(Language::Gpp),
-            "c" => Ok(Language::Gcc),
-            "py" => Ok(Language::Python3),
-            _ => Err(anyhow::anyhow!("Invalid file extension. Supported values: '.c', '.cpp', '.py', or specify the language with the --lang flag.").context(format!("Reading {}", file.display())))
-        }
-    })
+        })
+        .unwrap_or_else(|| {
+            let extension = file.extension().and_then(|extension| extension.to_str());
+            if let Some(extension) = extension
+                && let Some(language) = custom_by(&|language| {
+                    language.extensions.iter().any(|custom| {
+                        custom
+                            .trim_start_matches('.')
+                            .eq_ignore_ascii_case(extension)
+                    })
+                })
+            {
+                return Ok(language);
+            }
+            let Some(extension) = extension else {
+                let first_line = fs::read_to_string(&file)
+                    .ok()
+                    .and_then(|source| source.lines().next().map(str::to_string));
+                return first_line
+                    .and_then(|line| language_from_shebang(&line))
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(tr!("unknown-language"))
+                            .context(tr!("reading-file", path = file.display().to_string()))
+                    });
+            };
+            match extension.to_lowercase().as_str() {
+                "cpp" => Ok(Language::Gpp),
+                "c" => Ok(Language::Gcc),
+                "py" => Ok(Language::Python3),
+                "pas" => Ok(Language::Pascal),
+                _ => Err(anyhow::anyhow!(tr!("invalid-extension")))
+                    .context(tr!("reading-file", path = file.display().to_string())),
+            }
+        })
 }
 
-fn get_config_dir() -> std::path::PathBuf {
+/// Infers the language of a script from its `#!` line, such as
+/// `#!/usr/bin/env python3` or `#!/usr/bin/pypy3`.
+pub fn language_from_shebang(line: &str) -> Option<Language> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut interpreter = words.next()?;
+    if interpreter.rsplit('/').next() == Some("env") {
+        // skip flags of env, e.g. `-S`
+        interpreter = words.find(|word| !word.starts_with('-'))?;
+    }
+    let name = interpreter.rsplit('/').next()?;
+    if name.starts_with("pypy") {
+        Some(Language::PyPy3)
+    } else if name.starts_with("python") && !name.starts_with("python2") {
+        Some(Language::Python3)
+    } else {
+        None
+    }
+}
+
+fn get_config_root() -> std::path::PathBuf {
     let config_root = dirs::home_dir().map_or_else(
         || std::env::current_dir().unwrap().join(".openjudge-cli"),
         |home| home.join(".openjudge-cli"),
@@ -110,528 +319,4351 @@ fn get_config_dir() -> std::path::PathBuf {
     if !config_root.exists() {
         fs::create_dir_all(&config_root).expect("Failed to create config directory.");
     }
-    config_root.join("config.json")
+    config_root
 }
 
-fn ensure_account(config: &Option<AppConfig>) -> Result<(&str, String)> {
-    let email = config
-        .as_ref()
-        .and_then(|config| config.user_email.as_ref())
-        .ok_or_else(|| anyhow::anyhow!(NO_CREDENTIALS_FOUND))?;
-    let entry = Entry::new("openjudge-cli", email)?;
-    let password = entry.get_password().expect(NO_CREDENTIALS_FOUND);
-    Ok((email, password))
+fn get_config_dir() -> std::path::PathBuf {
+    get_config_root().join("config.json")
 }
 
-fn ensure_last_problem<'a>(specified: &'a str, config: &'a Option<AppConfig>) -> Result<&'a str> {
-    if specified == "." {
-        return match config {
-            Some(config) => Ok(config
-                .last_problem
-                .as_ref()
-                .ok_or_else(|| anyhow::anyhow!(NO_LAST_PROBLEM_FOUND))?),
-            None => Err(anyhow::anyhow!(NO_LAST_PROBLEM_FOUND)),
-        };
+fn get_cache_dir() -> std::path::PathBuf {
+    get_config_root().join("cache")
+}
+
+pub fn get_crash_dir() -> std::path::PathBuf {
+    get_config_root().join("crashes")
+}
+
+/// Fields of the config that identify the user or may hold credentials.
+const PRIVATE_CONFIG_FIELDS: &[&str] = &["user_email", "friends", "pre_submit_hooks"];
+
+/// The saved config for crash reports, with private fields blanked out and
+/// unset ones left out.
+pub fn config_summary() -> String {
+    let config = match AppConfig::read_config(get_config_dir()) {
+        Ok(config) => config.unwrap_or_default(),
+        Err(e) => return format!("(unreadable: {})", e),
+    };
+    let Ok(serde_json::Value::Object(mut fields)) = serde_json::to_value(&config) else {
+        return String::new();
+    };
+    fields.retain(|_, value| !value.is_null());
+    for field in PRIVATE_CONFIG_FIELDS {
+        if let Some(value) = fields.get_mut(*field) {
+            *value = "<redacted>".into();
+        }
     }
-    Ok(specified)
+    serde_json::to_string_pretty(&fields).unwrap_or_default()
+}
+
+/// The HTML of a page a scraper failed on, headed by a comment naming the
+/// page, the selector tried and the error.
+pub fn parse_snapshot(
+    error: &ParseError,
+    html: &str,
+    time: chrono::DateTime<chrono::Local>,
+) -> String {
+    format!(
+        "<!--\nurl: {}\nselector: {}\nerror: {}\ntime: {}\nversion: {}\n-->\n{}",
+        error.url,
+        error.selector,
+        error.message,
+        time.to_rfc3339(),
+        env!("CARGO_PKG_VERSION"),
+        html
+    )
 }
 
-pub async fn process_credentials(email: String) -> Result<()> {
-    let password = rpassword::prompt_password("Enter your password: ")?;
-    println!("Validating credentials with OpenJudge...");
-    let client = libopenjudge::create_client().await?;
-    libopenjudge::login(&client, &email, &password).await?;
-    let config_old = AppConfig::read_config(get_config_dir())?;
-    if let Some(ref config) = config_old {
-        if let Some(ref user_email) = config.user_email {
-            let entry = Entry::new("openjudge-cli", user_email)?;
-            let _ = entry.delete_credential();
+impl AppConfig {
+    fn scheme(&self) -> Scheme {
+        if self.prefer_https.unwrap_or(true) {
+            Scheme::Https
+        } else {
+            Scheme::Http
         }
     }
-    let config = AppConfig {
-        user_email: Some(email.clone()),
-        ..config_old.unwrap_or_default()
-    };
+
+    fn user_agent(&self) -> String {
+        self.user_agent
+            .clone()
+            .unwrap_or_else(|| format!("openjudge-cli/{}", env!("CARGO_PKG_VERSION")))
+    }
+
+    /// Recently operated problems, the latest first.
+    fn recent_problems(&self) -> Vec<String> {
+        self.recent_problems
+            .clone()
+            .or_else(|| self.last_problem.clone().map(|url| vec![url]))
+            .unwrap_or_default()
+    }
+
+    fn concurrency(&self) -> usize {
+        self.concurrency.unwrap_or(FETCH_CONCURRENCY)
+    }
+
+    fn graphics(&self) -> GraphicsOptions {
+        GraphicsOptions {
+            protocol: if screen_reader() {
+                GraphicsProtocol::Disabled
+            } else {
+                self.graphics_protocol.unwrap_or(GraphicsProtocol::Auto)
+            },
+            sixel: self.sixel.unwrap_or_default(),
+            max_rows: self.image_max_rows.unwrap_or(DEFAULT_IMAGE_MAX_ROWS),
+        }
+    }
+}
+
+async fn create_client(config: &AppConfig) -> Result<libopenjudge::Client> {
+    libopenjudge::create_client(ClientOptions {
+        scheme: config.scheme(),
+        cache_dir: Some(get_cache_dir().join("http")),
+        user_agent: Some(config.user_agent()),
+        cookie_jar: None,
+        hosts: config.hosts.clone().unwrap_or_default(),
+        pool_max_idle_per_host: config.pool_size,
+        pool_idle_timeout: config.keep_alive.map(std::time::Duration::from_secs),
+    })
+    .await
+}
+
+fn ensure_account(config: &AppConfig) -> Result<(String, String)> {
+    let email = config
+        .user_email
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!(no_credentials_found()))?;
     let entry = Entry::new("openjudge-cli", &email)?;
-    entry.set_password(&password)?;
-    config.write_config(get_config_dir())?;
-    println!("Credentials saved.");
-    Ok(())
+    let password = entry
+        .get_password()
+        .map_err(|_| anyhow::anyhow!(no_credentials_found()))?;
+    Ok((email, password))
 }
 
-pub async fn view_problem(url: &str) -> Result<()> {
-    println!("Fetching problem details...");
-    let config = AppConfig::read_config(get_config_dir())?;
-    let url = ensure_last_problem(url, &config)?;
-    let client = libopenjudge::create_client().await?;
-    let problem = libopenjudge::get_problem(&client, url).await?;
-    let graphics_protocol = config
-        .as_ref()
-        .map(|x| x.graphics_protocol.unwrap_or(GraphicsProtocol::Auto))
-        .unwrap_or(GraphicsProtocol::Auto);
-    macro_rules! map_optional_printable {
-        ($field: expr) => {
-            if let Some(s) = $field {
-                Some(get_printable_html_text(s, graphics_protocol).await)
-            } else {
-                None
-            }
-        };
+/// How many problems before the latest one `reference` points at: 0 for
+/// ".", N for "~N" and one per caret for "^", "^^" and so on, like git
+/// revisions. `None` if it is not such a reference.
+pub fn recent_index(reference: &str) -> Option<usize> {
+    if reference == "." {
+        return Some(0);
     }
-    let problem_print = Problem {
-        description: get_printable_html_text(&problem.description, graphics_protocol).await,
-        input: map_optional_printable!(&problem.input),
-        output: map_optional_printable!(&problem.output),
-        sample_input: map_optional_printable!(&problem.sample_input),
-        sample_output: map_optional_printable!(&problem.sample_output),
-        hint: map_optional_printable!(&problem.hint),
-        source: map_optional_printable!(&problem.source),
-        ..problem
-    };
-    print!("{}", &problem_print);
-    AppConfig {
-        last_problem: Some(url.to_string()),
-        ..config.unwrap_or_default()
+    if let Some(count) = reference.strip_prefix('~') {
+        return if count.is_empty() {
+            Some(1)
+        } else {
+            count.parse().ok()
+        };
     }
-    .write_config(get_config_dir())?;
-    Ok(())
+    (!reference.is_empty() && reference.chars().all(|c| c == '^')).then_some(reference.len())
 }
 
-async fn submit_solution_internal(
-    urls: Vec<&str>,
-    file: &str,
-    lang: Language,
-    email: &str,
-    password: &str,
-) -> Result<()> {
-    let client = libopenjudge::create_client().await?;
-    libopenjudge::login(&client, email, password).await?;
-    let code = fs::read_to_string(file)?;
-    for url in urls {
-        println!("Submitting solution of {}", url.blue().underline());
-        let submission_url = libopenjudge::submit_solution(&client, url, &code, lang).await?;
-        println!(
-            "Submission created at {}\nWaiting for judgement...",
-            submission_url.blue().underline()
-        );
-        let submission = libopenjudge::query_submission_result(&client, &submission_url).await?;
-        print!("{}", &submission);
-    }
-    Ok(())
+/// Moves `url` to the front of `recent`, keeping at most
+/// [`RECENT_PROBLEMS`].
+pub fn push_recent(recent: &mut Vec<String>, url: String) {
+    recent.retain(|known| *known != url);
+    recent.insert(0, url);
+    recent.truncate(RECENT_PROBLEMS);
 }
 
-pub async fn submit_solution(urls: Vec<&str>, file: &str, lang: Option<String>) -> Result<()> {
-    let lang = determine_language(file, lang)?;
-    let config = AppConfig::read_config(get_config_dir())?;
-    let (email, password) = ensure_account(&config)?;
-    if urls.len() == 1 {
-        let url = urls[0];
-        let url = ensure_last_problem(url, &config)?;
-        submit_solution_internal(vec![url], file, lang, email, &password).await?;
-        AppConfig {
-            last_problem: Some(url.to_string()),
-            ..config.unwrap_or_default()
-        }
-        .write_config(get_config_dir())?;
-        Ok(())
-    } else {
-        let urls = urls
-            .iter()
-            .map(|url| -> Result<_> {
-                let url = ensure_last_problem(url, &config)?;
-                Ok(url)
-            })
-            .collect::<Result<Vec<_>>>()?;
-        submit_solution_internal(urls, file, lang, email, &password).await?;
-        Ok(())
+fn ensure_last_problem(specified: &str, config: &AppConfig) -> Result<ProblemUrl> {
+    if let Some(index) = recent_index(specified) {
+        let recent = config.recent_problems();
+        if recent.is_empty() {
+            return Err(anyhow::anyhow!(no_last_problem_found()));
+        }
+        return recent
+            .get(index)
+            .ok_or_else(|| anyhow::anyhow!(tr!("recent-out-of-range", count = recent.len())))?
+            .parse();
+    }
+    if let Some(name) = specified.strip_prefix('@') {
+        return resolve_alias(name, config)?.parse();
     }
+    specified.parse()
 }
 
-pub async fn test_solution(
-    url: &str,
-    file: &str,
-    lang: Option<String>,
-    submit: bool,
-) -> Result<()> {
-    let config = AppConfig::read_config(get_config_dir())?;
-    let url = ensure_last_problem(url, &config)?;
-    let lang = determine_language(file, lang)?;
-    let client = libopenjudge::create_client().await?;
-    let problem = libopenjudge::get_problem(&client, url).await?;
-    if problem.sample_input.is_none() || problem.sample_output.is_none() {
-        return Err(anyhow::anyhow!("No sample input/output found for problem."));
+/// The URL `@name` stands for, from `oj.yaml` if it defines it, or else from
+/// the config.
+fn resolve_alias(name: &str, config: &AppConfig) -> Result<String> {
+    let manifest = Manifest::read_current()?;
+    manifest
+        .as_ref()
+        .and_then(|manifest| manifest.aliases.get(name))
+        .or_else(|| config.aliases.as_ref()?.get(name))
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!(tr!("no-such-alias", name = name)))
+}
+
+/// Checks an alias name, given with or without its `@`.
+pub fn parse_alias_name(name: &str) -> Result<&str> {
+    let name = name.strip_prefix('@').unwrap_or(name);
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(anyhow::anyhow!(tr!("invalid-alias-name", name = name)));
     }
-    println!(
-        "Testing solution {} of problem {}",
-        file.blue().underline(),
-        problem.title.blue().underline()
-    );
+    Ok(name)
+}
 
-    let mut input = if let Some(s) = &problem.sample_input {
-        get_printable_html_text(s, GraphicsProtocol::Disabled).await
-    } else {
-        String::new()
-    };
-    let output = if let Some(s) = &problem.sample_output {
-        get_printable_html_text(s, GraphicsProtocol::Disabled).await
-    } else {
-        String::new()
-    };
+/// State shared by all operations of one invocation, so that nested flows
+/// reuse a single cookie jar and connection pool, and log in at most once.
+pub struct Session {
+    config: Mutex<AppConfig>,
+    client: OnceCell<libopenjudge::Client>,
+    logged_in: OnceCell<()>,
+    /// Show placeholders instead of downloading images, see `--no-images`.
+    skip_images: bool,
+    /// Protocol drawing images in place of the configured one, see
+    /// `--graphics`.
+    graphics_protocol: Option<GraphicsProtocol>,
+    /// Problem the invocation operated on last, for the activity log.
+    target: Mutex<Option<String>>,
+    /// Problems loaded so far by URL, so chained operations such as
+    /// `oj test --submit` fetch each page once.
+    problems: Mutex<HashMap<String, Problem>>,
+}
 
-    if input.as_str() == "(无)" || input.as_str() == "（无）" {
-        input = "".to_string();
+impl Session {
+    pub fn new() -> Result<Self> {
+        let config = AppConfig::read_config(get_config_dir())?.unwrap_or_default();
+        i18n::set_lang(config.lang.unwrap_or_else(i18n::detect_lang));
+        set_display_options(DisplayOptions {
+            time_format: config
+                .time_format
+                .clone()
+                .unwrap_or_else(|| DEFAULT_TIME_FORMAT.to_string()),
+            relative_time: config.relative_time.unwrap_or(false),
+            verdict_style: config.verdict_style.unwrap_or_default(),
+        });
+        interactions::set_inline(config.inline_select.unwrap_or(false));
+        if config.screen_reader.unwrap_or(false) {
+            set_screen_reader(true);
+            colored::control::set_override(false);
+        }
+        Ok(Self {
+            config: Mutex::new(config),
+            client: OnceCell::new(),
+            logged_in: OnceCell::new(),
+            skip_images: false,
+            graphics_protocol: None,
+            target: Mutex::new(None),
+            problems: Mutex::new(HashMap::new()),
+        })
     }
 
-    println!("{}", "Case Input:".yellow().bold());
-    println!("{}", input);
-    let code_output = match lang {
-        Language::Gcc | Language::Gpp => {
-            // .exe used for Windows compatibility
-            let excutable_path = format!("./sol-{}.exe", nanoid!());
-            process::Command::new(if lang == Language::Gcc { "gcc" } else { "g++" })
-                .arg("--std=gnu++14")
-                .arg("-o")
-                .arg(&excutable_path)
-                .arg(file)
-                .spawn()?
-                .wait()?;
-            let mut child_process = process::Command::new(&excutable_path)
-                .stdin(process::Stdio::piped())
-                .stdout(process::Stdio::piped())
-                .stderr(process::Stdio::piped())
-                .spawn()?;
-            child_process
-                .stdin
-                .take()
-                .expect("Handle to stdin not available.")
-                .write_all(input.as_bytes())?;
-            let output = child_process.wait_with_output()?;
-            let _ = fs::remove_file(&excutable_path);
-            output
-        }
-        Language::PyPy3 | Language::Python3 => {
-            let mut child_process = process::Command::new(if lang == Language::PyPy3 {
-                "pypy3"
-            } else {
-                "python3"
-            })
-            .arg(file)
-            .env("PYTHON_COLORS", "1")
-            .stdin(process::Stdio::piped())
-            .stdout(process::Stdio::piped())
-            .stderr(process::Stdio::piped())
-            .spawn()?;
-            child_process
-                .stdin
-                .take()
-                .expect("Handle to stdin not available.")
-                .write_all(input.as_bytes())?;
-            child_process.wait_with_output()?
+    /// How to draw images, with the protocol given by `--graphics` if any.
+    fn image_graphics(&self) -> GraphicsOptions {
+        let graphics = self.config().graphics();
+        match self.graphics_protocol {
+            Some(protocol) if !screen_reader() => GraphicsOptions {
+                protocol,
+                ..graphics
+            },
+            _ => graphics,
         }
-    };
-    if code_output.status.success() {
-        let code_output = String::from_utf8(code_output.stdout)?;
-        if code_output.trim() == output.trim() {
-            println!("{}", "Accepted!".blue().bold());
-            if submit {
-                let (email, password) = ensure_account(&config)?;
-                submit_solution_internal(vec![url], file, lang, email, &password).await?;
+    }
+
+    /// How to draw images of problems, none with `--no-images`.
+    fn graphics(&self) -> GraphicsOptions {
+        let graphics = self.image_graphics();
+        if self.skip_images {
+            GraphicsOptions {
+                protocol: GraphicsProtocol::Disabled,
+                ..graphics
             }
         } else {
-            let diff = TextDiff::from_lines(output.trim(), code_output.trim());
-            println!("{}", "Wrong Answer.".red().bold());
-            println!("{}", "Expected Output:".yellow().bold());
-            println!("{}", output.trim());
-            println!("{}", "Your Output:".yellow().bold());
-            println!("{}", code_output.trim());
-            println!("{}", "Diff:".yellow().bold());
-            for change in diff.iter_all_changes() {
-                let old_index = change
-                    .old_index()
-                    .map(|v| (v + 1).to_string())
-                    .unwrap_or(" ".to_string());
-                let new_index = change
-                    .new_index()
-                    .map(|v| (v + 1).to_string())
-                    .unwrap_or(" ".to_string());
-                match change.tag() {
-                    ChangeTag::Delete => {
-                        println!(
-                            "{:>3} {:>3} | {} {}",
-                            old_index,
-                            new_index,
-                            "-".red(),
-                            change.value().trim().red()
-                        );
-                    }
-                    ChangeTag::Insert => {
-                        println!(
-                            "{:>3} {:>3} | {} {}",
-                            old_index,
-                            new_index,
-                            "+".green(),
-                            change.value().trim().green()
-                        );
-                    }
-                    ChangeTag::Equal => {
-                        println!(
-                            "{:>3} {:>3} |   {}",
-                            old_index,
-                            new_index,
-                            change.value().trim()
-                        );
-                    }
-                }
-            }
-        }
-    } else {
-        println!("{}", "Runtime Error.".red().bold());
-        println!(
-            "Exit Code: {}",
-            code_output.status.code().unwrap_or_default()
-        );
-        #[cfg(unix)]
-        {
-            println!(
-                "Signal: {}",
-                code_output.status.signal().unwrap_or_default()
-            );
+            graphics
         }
-        println!("STDOUT:\n{}", String::from_utf8(code_output.stdout)?);
-        println!("STDERR:\n{}", String::from_utf8(code_output.stderr)?);
     }
-    AppConfig {
-        last_problem: Some(url.to_string()),
-        ..config.unwrap_or_default()
+
+    /// Leaves images of problems undownloaded if `skip`, they can then be
+    /// fetched one by one.
+    pub fn skip_images(self, skip: bool) -> Self {
+        Self {
+            skip_images: skip,
+            ..self
+        }
     }
-    .write_config(get_config_dir())?;
-    Ok(())
-}
 
-pub async fn search(group: &str, query: &str, interactive: bool) -> Result<()> {
-    println!(
-        "Searching for {} in group {}...",
-        query.bold(),
-        group.bold()
-    );
-    let client = libopenjudge::create_client().await?;
-    let result = libopenjudge::search(&client, group, query).await?;
-    println!();
-    if !interactive {
-        println!("Found {} results:", result.len().to_string().bold());
-        for item in &result {
-            println!("{}", item);
+    /// Draws images with `protocol` instead of the configured one, if given.
+    pub fn graphics_protocol(self, protocol: Option<GraphicsProtocol>) -> Self {
+        Self {
+            graphics_protocol: protocol,
+            ..self
         }
-        return Ok(());
     }
-    let selected_index =
-        interactions::select_within(&format!("Found {} results:", result.len()), &result, 4, 1);
-    if let Some(index) = selected_index {
-        let selected_problem = &result[index];
-        view_problem(&selected_problem.url).await
-    } else {
-        println!("No problem selected.");
-        Ok(())
+
+    fn config(&self) -> AppConfig {
+        self.config.lock().unwrap().clone()
     }
-}
 
-pub async fn view_user() -> Result<()> {
-    println!("Fetching user details...");
-    let config = AppConfig::read_config(get_config_dir())?;
-    let (email, password) = ensure_account(&config)?;
-    let client = libopenjudge::create_client().await?;
-    libopenjudge::login(&client, email, &password).await?;
-    let user = libopenjudge::get_user_info(&client).await?;
-    print!("{}", user);
-    Ok(())
-}
+    /// Applies `update` to the configuration and persists it.
+    fn update_config(&self, update: impl FnOnce(&mut AppConfig)) -> Result<()> {
+        let mut config = self.config.lock().unwrap();
+        update(&mut config);
+        config.write_config(get_config_dir())
+    }
 
-pub async fn view_submission(url: &str) -> Result<()> {
-    println!("Fetching submission details...");
-    let config = AppConfig::read_config(get_config_dir())?;
-    let (email, password) = ensure_account(&config)?;
-    let client = libopenjudge::create_client().await?;
-    libopenjudge::login(&client, email, &password).await?;
-    let submission = libopenjudge::query_submission_result(&client, url).await?;
-    println!("{}", submission);
-    println!("{}", "Code".bold().on_white());
-    let syntax_set = SyntaxSet::load_defaults_nonewlines();
-    let syntax = syntax_set
-        .find_syntax_by_extension(match submission.lang.as_str() {
-            "Python3" => "py",
-            "PyPy3" => "py",
-            "G++" => "cpp",
-            "GCC" => "c",
-            _ => "text",
+    /// Saves the page behind a [`ParseError`] to a timestamped file under the
+    /// cache directory and points `error` at it, so reports about layout
+    /// changes come with the HTML. Other errors are returned as they are.
+    pub fn snapshot_parse_error(&self, error: anyhow::Error) -> anyhow::Error {
+        let Some(parse_error) = error.downcast_ref::<ParseError>() else {
+            return error;
+        };
+        let Some(html) = self
+            .client
+            .get()
+            .and_then(|client| client.recent_page(&parse_error.url))
+        else {
+            return error;
+        };
+        let now = chrono::Local::now();
+        let dir = get_cache_dir().join("snapshots");
+        let path = dir.join(format!("{}.html", now.format("%Y%m%d-%H%M%S")));
+        let written = fs::create_dir_all(&dir)
+            .and_then(|_| fs::write(&path, parse_snapshot(parse_error, &html, now)));
+        match written {
+            Ok(()) => anyhow::anyhow!(
+                "{:#}\n{}",
+                error,
+                tr!("page-saved", path = path.display().to_string())
+            ),
+            Err(_) => error,
+        }
+    }
+
+    /// Status bar of interactive screens, with the account filled in.
+    pub(crate) fn status_bar(&self) -> StatusBar {
+        StatusBar {
+            user: self.config().user_email,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn remember_problem(&self, url: &ProblemUrl) -> Result<()> {
+        *self.target.lock().unwrap() = Some(url.to_string());
+        self.update_config(|config| {
+            let mut recent = config.recent_problems();
+            push_recent(&mut recent, url.to_string());
+            config.recent_problems = Some(recent);
+            config.last_problem = None;
         })
-        .unwrap();
-    let mut highlighter = HighlightLines::new(syntax, &code_theme::ENKI_TOKYO_NIGHT_THEME);
-    for line in submission.code.lines() {
-        let ranges: Vec<(Style, &str)> = highlighter.highlight_line(line, &syntax_set)?;
-        let escaped = as_24_bit_terminal_escaped(&ranges[..], false);
-        println!("{}", escaped);
     }
-    Ok(())
-}
 
-pub async fn list_submissions(problem_url: &str, interactive: bool) -> Result<()> {
-    let config = AppConfig::read_config(get_config_dir())?;
-    let problem_url = ensure_last_problem(problem_url, &config)?;
-    let (email, password) = ensure_account(&config)?;
-    let client = libopenjudge::create_client().await?;
-    libopenjudge::login(&client, email, &password).await?;
-    let submissions = libopenjudge::list_submissions(&client, problem_url).await?;
+    /// Adds this invocation, the problem it operated on and how it ended to
+    /// the activity log listed by `oj history`.
+    pub fn record_activity(&self, result: &Result<()>) -> Result<()> {
+        let path = get_config_root().join("activity.json");
+        let mut log = load_json::<ActivityLog>(&path)?;
+        log.push(Activity {
+            time: chrono::Local::now().fixed_offset(),
+            args: std::env::args().skip(1).collect(),
+            dir: std::env::current_dir()
+                .ok()
+                .map(|dir| dir.display().to_string()),
+            target: self.target.lock().unwrap().clone(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        });
+        save_json(&path, &log)
+    }
 
-    if submissions.is_empty() {
-        println!("{}", "No submissions found.".bold());
-        return Ok(());
+    /// Applies `update` to the log of solve times and persists it.
+    pub(crate) fn update_solves(&self, update: impl FnOnce(&mut SolveLog)) -> Result<()> {
+        let path = get_config_root().join("solves.json");
+        let mut log = load_json::<SolveLog>(&path)?;
+        update(&mut log);
+        save_json(&path, &log)
     }
 
-    if !interactive {
-        println!(
-            "Found {} submissions:",
-            submissions.len().to_string().bold()
-        );
-        for submission in &submissions {
-            println!("{}", submission);
-        }
-        return Ok(());
+    /// Applies `update` to the log of submissions waiting for a verdict and
+    /// persists it.
+    pub(crate) fn update_pending(&self, update: impl FnOnce(&mut PendingLog)) -> Result<()> {
+        let path = get_config_root().join("pending.json");
+        let mut log = load_json::<PendingLog>(&path)?;
+        update(&mut log);
+        save_json(&path, &log)
     }
-    let selected_index = select_within(
-        &format!(
-            "Found {} submissions:",
-            submissions.len().to_string().bold(),
-        ),
-        &submissions,
-        2,
-        1,
-    );
-    match selected_index {
-        None => Ok(()),
-        Some(i) => {
-            let selected_submission = &submissions[i];
-            view_submission(&selected_submission.url).await
+
+    /// Records the first time a problem is viewed, the start of its solve time.
+    pub(crate) fn record_view(&self, url: &ProblemUrl, problem: &Problem) -> Result<()> {
+        self.update_solves(|log| {
+            log.record_view(
+                &url.to_string(),
+                &problem.title,
+                chrono::Local::now().fixed_offset(),
+            )
+        })
+    }
+
+    /// The problem at `url`, fetched unless this invocation already did.
+    pub(crate) async fn problem(&self, url: &ProblemUrl) -> Result<Problem> {
+        if let Some(problem) = self.problems.lock().unwrap().get(&url.to_string()) {
+            return Ok(problem.clone());
         }
+        let problem = libopenjudge::get_problem(self.client().await?, url).await?;
+        self.problems
+            .lock()
+            .unwrap()
+            .insert(url.to_string(), problem.clone());
+        Ok(problem)
+    }
+
+    pub(crate) async fn client(&self) -> Result<&libopenjudge::Client> {
+        self.client
+            .get_or_try_init(|| async { create_client(&self.config()).await })
+            .await
+    }
+
+    /// The client, logged in with the saved credentials on first use.
+    pub(crate) async fn authenticated_client(&self) -> Result<&libopenjudge::Client> {
+        let client = self.client().await?;
+        self.logged_in
+            .get_or_try_init(|| async {
+                let (email, password) = ensure_account(&self.config())?;
+                spin(
+                    tr!("logging-in"),
+                    libopenjudge::login(client, &email, &password),
+                )
+                .await
+            })
+            .await?;
+        Ok(client)
     }
 }
 
-pub fn strip_slashes(text: &str) -> &str {
-    let pattern = Regex::new(r#"^\/?(.*?)\/?$"#).unwrap();
-    let captures = pattern.captures(text).unwrap();
-    captures.at(1).unwrap_or("")
+/// A problem to operate on, resolved from a manifest key, "." or a plain URL.
+struct Target {
+    url: ProblemUrl,
+    file: Option<String>,
+    lang: Option<String>,
 }
 
-pub async fn list_probsets(group: &str, interactive: bool) -> Result<()> {
-    println!("Fetching probsets...");
-    let group_id = group;
-    let client = libopenjudge::create_client().await?;
-    let group = libopenjudge::get_group_info(&client, group).await?;
-    if !interactive || group.probsets.is_empty() {
-        println!("{}", group);
-        return Ok(());
+fn resolve_target(
+    specified: &str,
+    config: &AppConfig,
+    manifest: &Option<Manifest>,
+) -> Result<Target> {
+    if let Some(problem) = manifest.as_ref().and_then(|m| m.get(specified)) {
+        return Ok(Target {
+            url: problem.url.parse()?,
+            file: problem.file.clone(),
+            lang: problem.lang.clone(),
+        });
     }
-    let mut prompt = String::new();
-    writeln!(prompt, "{}", &group.name.bold())?;
-    writeln!(prompt, "{}", &group.url.blue().underline())?;
-    writeln!(prompt, "{}", &group.description)?;
-    let selected_index = select_within(&prompt, &group.probsets, 2, 3);
-    match selected_index {
-        None => Ok(()),
-        Some(i) => {
-            let selected_probset = &group.probsets[i];
-            list_problems(
-                group_id,
-                strip_slashes(&selected_probset.url),
-                None,
+    Ok(Target {
+        url: ensure_last_problem(specified, config)?,
+        file: None,
+        lang: None,
+    })
+}
+
+pub async fn process_credentials(session: &Session, email: String) -> Result<()> {
+    let password = rpassword::prompt_password(format!("{} ", tr!("enter-password")))?;
+    let client = session.client().await?;
+    spin(
+        tr!("validating-credentials"),
+        libopenjudge::login(client, &email, &password),
+    )
+    .await?;
+    if let Some(ref user_email) = session.config().user_email {
+        let entry = Entry::new("openjudge-cli", user_email)?;
+        let _ = entry.delete_credential();
+    }
+    let entry = Entry::new("openjudge-cli", &email)?;
+    entry.set_password(&password)?;
+    session.update_config(|config| config.user_email = Some(email.clone()))?;
+    println!("{}", tr!("credentials-saved"));
+    Ok(())
+}
+
+pub async fn view_problem(session: &Session, url: &str) -> Result<()> {
+    view_problems(session, &[url], None).await
+}
+
+/// Fetches all of `urls` concurrently, then prints them in the given order.
+/// Files linked from the statements are saved into `attachments_dir`, if
+/// given.
+pub async fn view_problems(
+    session: &Session,
+    urls: &[&str],
+    attachments_dir: Option<&Path>,
+) -> Result<()> {
+    let config = session.config();
+    let urls = urls
+        .iter()
+        .map(|url| ensure_last_problem(url, &config))
+        .collect::<Result<Vec<_>>>()?;
+    let imported = urls
+        .iter()
+        .map(|url| bundle::imported_problem(&bundle_dir(url), url))
+        .collect::<Result<Vec<_>>>()?;
+    let missing = urls
+        .iter()
+        .zip(&imported)
+        .filter(|(_, problem)| problem.is_none())
+        .map(|(url, _)| url)
+        .collect::<Vec<_>>();
+    let mut fetched = if missing.is_empty() {
+        vec![]
+    } else {
+        spin(
+            tr!("fetching-problem"),
+            libopenjudge::join_bounded(
+                missing.iter().map(|url| session.problem(url)),
+                session.config().concurrency(),
+            ),
+        )
+        .await
+    }
+    .into_iter();
+    for (url, problem) in urls.iter().zip(imported) {
+        let problem = match problem {
+            Some(problem) => problem,
+            None => fetched.next().unwrap()?,
+        };
+        session.record_view(url, &problem)?;
+        print_problem(session, url, problem, attachments_dir).await?;
+    }
+    if let Some(url) = urls.last() {
+        session.remember_problem(url)?;
+    }
+    Ok(())
+}
+
+/// Directory a bundle of the problem at `url` is imported into.
+fn bundle_dir(url: &ProblemUrl) -> PathBuf {
+    get_cache_dir()
+        .join("bundles")
+        .join(url.group())
+        .join(url.probset())
+        .join(url.number())
+}
+
+/// The problem at `url`, from its imported bundle if there is one, without
+/// going online.
+async fn fetch_problem(session: &Session, url: &ProblemUrl) -> Result<Problem> {
+    if let Some(problem) = bundle::imported_problem(&bundle_dir(url), url)? {
+        return Ok(problem);
+    }
+    spin(tr!("fetching-problem"), session.problem(url)).await
+}
+
+/// HTML parts of the statement of `problem`.
+fn statement_parts(problem: &Problem) -> impl Iterator<Item = &String> {
+    [
+        Some(&problem.description),
+        problem.input.as_ref(),
+        problem.output.as_ref(),
+        problem.sample_input.as_ref(),
+        problem.sample_output.as_ref(),
+        problem.hint.as_ref(),
+        problem.source.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Packs the statement of the problem at `url` as Markdown, its images and
+/// its samples as test files into a zip archive at `file`, for reading and
+/// practicing without an account.
+pub async fn bundle_export(session: &Session, url: &str, file: &str) -> Result<()> {
+    let url = ensure_last_problem(url, &session.config())?;
+    let client = session.client().await?;
+    let problem = spin(
+        tr!("fetching-problem"),
+        libopenjudge::get_problem(client, &url),
+    )
+    .await?;
+    session.record_view(&url, &problem)?;
+    let base = url::Url::parse(&url.to_string())?;
+    // the same source may appear in several parts
+    let mut seen = HashSet::new();
+    let sources = statement_parts(&problem)
+        .flat_map(|html| image_sources(html))
+        .filter(|src| !src.starts_with("data:") && seen.insert(src.clone()))
+        .collect::<Vec<_>>();
+    let absolute = |src: &str| {
+        base.join(src)
+            .map(|url| url.to_string())
+            .unwrap_or_else(|_| src.to_string())
+    };
+    let images = spin(
+        tr!("fetching-image"),
+        fetch_images(sources.iter().map(|src| absolute(src)).collect()),
+    )
+    .await;
+    let mut files = vec![];
+    let mut paths = HashMap::new();
+    for src in &sources {
+        match images.get(&absolute(src)) {
+            Some(Ok(bytes)) => {
+                let path = format!(
+                    "{}/{}-{}",
+                    bundle::IMAGES_DIR,
+                    files.len() + 1,
+                    image_name(src)
+                );
+                paths.insert(src.clone(), path.clone());
+                files.push((path, bytes.clone()));
+            }
+            _ => println!(
+                "{} {}",
+                tr!("warning").yellow().bold(),
+                tr!(
+                    "image-linked-instead",
+                    url = src.blue().underline().to_string()
+                )
+            ),
+        }
+    }
+    let (input, output) = plain_samples(&problem).await;
+    let statement =
+        bundle::problem_markdown(&problem, &url.to_string(), (&input, &output), &|src| {
+            paths
+                .get(src)
+                .cloned()
+                .or_else(|| (!src.starts_with("data:")).then(|| absolute(src)))
+        });
+    files.push((bundle::STATEMENT_FILE.to_string(), statement.into_bytes()));
+    if problem.sample_input.is_some() || problem.sample_output.is_some() {
+        files.push((format!("{}/1.in", bundle::TESTS_DIR), input.into_bytes()));
+        files.push((format!("{}/1.out", bundle::TESTS_DIR), output.into_bytes()));
+    }
+    bundle::write_bundle(Path::new(file), &files)?;
+    println!(
+        "{}",
+        tr!(
+            "bundle-saved",
+            title = problem.title.bold().to_string(),
+            count = paths.len(),
+            file = file.bold().to_string()
+        )
+    );
+    session.remember_problem(&url)?;
+    Ok(())
+}
+
+/// Unpacks the bundle at `file` into the cache, so `oj view problem` and
+/// `oj test` read the problem from it offline. The problem is the one the
+/// bundle was exported from, unless `url` is given.
+pub fn bundle_import(session: &Session, file: &str, url: Option<&str>) -> Result<()> {
+    let files = bundle::read_bundle(Path::new(file))?;
+    let statement = files
+        .iter()
+        .find(|(name, _)| name == bundle::STATEMENT_FILE)
+        .map(|(_, contents)| String::from_utf8_lossy(contents))
+        .ok_or_else(|| {
+            anyhow::anyhow!(tr!(
+                "bundle-missing-file",
+                bundle = file,
+                file = bundle::STATEMENT_FILE
+            ))
+        })?;
+    let url = match url.or_else(|| bundle::exported_from(&statement)) {
+        Some(url) => ensure_last_problem(url, &session.config())?,
+        None => {
+            return Err(anyhow::anyhow!(tr!("bundle-without-url", bundle = file)));
+        }
+    };
+    let title = ProblemSpec::from_markdown(&statement)
+        .with_context(|| tr!("parsing-file", path = bundle::STATEMENT_FILE))?
+        .title
+        .unwrap_or_else(|| url.to_string());
+    let dir = bundle_dir(&url);
+    // files of an earlier import of the problem would linger
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    for (name, contents) in &files {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)?;
+    }
+    println!(
+        "{}",
+        tr!(
+            "bundle-imported",
+            title = title.bold().to_string(),
+            url = url.to_string().blue().underline().to_string()
+        )
+    );
+    session.remember_problem(&url)?;
+    Ok(())
+}
+
+/// Absolute URLs of the documents and data files linked from the statement
+/// of the problem at `url`.
+fn problem_attachments(url: &ProblemUrl, problem: &Problem) -> Vec<String> {
+    let Ok(base) = url::Url::parse(&url.to_string()) else {
+        return vec![];
+    };
+    let mut seen = HashSet::new();
+    statement_parts(problem)
+        .flat_map(|html| attachment_links(html))
+        .filter_map(|href| base.join(&href).ok())
+        .map(|url| url.to_string())
+        .filter(|url| seen.insert(url.clone()))
+        .collect()
+}
+
+/// Name to save the attachment at `url` under: the last segment of its path,
+/// decoded, or a numbered one if it has none.
+pub fn attachment_file_name(url: &str, index: usize) -> String {
+    let segment = url::Url::parse(url)
+        .ok()
+        .and_then(|url| {
+            url.path_segments()?
+                .rfind(|segment| !segment.is_empty())
+                .map(str::to_string)
+        })
+        .unwrap_or_default();
+    let mut bytes = vec![];
+    let mut rest = segment.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = tail
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(decoded) if byte == b'%' => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    let name = String::from_utf8_lossy(&bytes).replace(['/', '\\'], "_");
+    if name.is_empty() || name == "." || name == ".." {
+        format!("attachment-{}", index + 1)
+    } else {
+        name
+    }
+}
+
+/// Saves `attachments` into `dir`, logged in if credentials are saved since
+/// files of private groups are only served to members.
+async fn download_attachments(session: &Session, attachments: &[String], dir: &Path) -> Result<()> {
+    if attachments.is_empty() {
+        println!("{}", tr!("no-attachments"));
+        return Ok(());
+    }
+    fs::create_dir_all(dir)?;
+    let client = match session.authenticated_client().await {
+        Ok(client) => client,
+        Err(_) => session.client().await?,
+    };
+    for (i, url) in attachments.iter().enumerate() {
+        let bytes = spin(tr!("downloading", file = url.as_str()), async {
+            let response = client.get(url).await?.error_for_status()?;
+            Ok::<_, anyhow::Error>(response.bytes().await?)
+        })
+        .await?;
+        let path = dir.join(attachment_file_name(url, i));
+        fs::write(&path, &bytes)
+            .with_context(|| tr!("writing-file", path = path.display().to_string()))?;
+        println!(
+            "{} {}",
+            tr!("saved-attachment", bytes = bytes.len()),
+            path.display().to_string().bold()
+        );
+    }
+    Ok(())
+}
+
+/// Lists the attachments of a problem and saves them into the directory the
+/// user types, if any.
+async fn prompt_attachments(session: &Session, attachments: &[String]) -> Result<()> {
+    println!("\n{}", tr!("attachments-found", count = attachments.len()));
+    for (i, url) in attachments.iter().enumerate() {
+        println!("{:>3}. {}", i + 1, url.blue().underline());
+    }
+    print!("{} ", tr!("attachments-prompt"));
+    std::io::stdout().flush()?;
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    match line.trim() {
+        "" => Ok(()),
+        dir => download_attachments(session, attachments, Path::new(dir)).await,
+    }
+}
+
+async fn print_problem(
+    session: &Session,
+    url: &ProblemUrl,
+    problem: Problem,
+    attachments_dir: Option<&Path>,
+) -> Result<()> {
+    let graphics = session.graphics();
+    let images = statement_parts(&problem)
+        .flat_map(|html| image_sources(html))
+        .collect::<Vec<_>>();
+    let attachments = problem_attachments(url, &problem);
+    macro_rules! map_optional_printable {
+        ($field: expr) => {
+            if let Some(s) = $field {
+                Some(render_html(s, RenderMode::Styled(graphics)).await)
+            } else {
+                None
+            }
+        };
+    }
+    // images are fetched while rendering
+    let spinner = Spinner::start(tr!("rendering", title = problem.title.as_str()));
+    let problem_print = Problem {
+        description: render_html(&problem.description, RenderMode::Styled(graphics)).await,
+        input: map_optional_printable!(&problem.input),
+        output: map_optional_printable!(&problem.output),
+        sample_input: map_optional_printable!(&problem.sample_input),
+        sample_output: map_optional_printable!(&problem.sample_output),
+        hint: map_optional_printable!(&problem.hint),
+        source: map_optional_printable!(&problem.source),
+        ..problem
+    };
+    drop(spinner);
+    print!("{}", Styled(&problem_print));
+    if session.skip_images && !screen_reader() && is_interactive() {
+        prompt_images(session.image_graphics(), &images).await?;
+    }
+    match attachments_dir {
+        Some(dir) => download_attachments(session, &attachments, dir).await?,
+        None if !attachments.is_empty() && is_interactive() => {
+            prompt_attachments(session, &attachments).await?
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+fn is_interactive() -> bool {
+    use std::io::IsTerminal;
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// Lists the images left out of a problem and draws those picked by number,
+/// until the user presses Enter on an empty line.
+async fn prompt_images(graphics: GraphicsOptions, images: &[String]) -> Result<()> {
+    if images.is_empty() {
+        return Ok(());
+    }
+    println!("\n{}", tr!("images-skipped", count = images.len()));
+    for (i, src) in images.iter().enumerate() {
+        println!("{:>3}. {}", i + 1, src.blue().underline());
+    }
+    loop {
+        print!("{} ", tr!("fetch-image-prompt"));
+        std::io::stdout().flush()?;
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line)? == 0 || line.trim().is_empty() {
+            return Ok(());
+        }
+        match line.trim().parse::<usize>() {
+            Ok(number) if (1..=images.len()).contains(&number) => {
+                let src = &images[number - 1];
+                let image = spin(tr!("fetching-image"), render_image(src, graphics)).await;
+                println!("{}", image);
+            }
+            _ => println!("{}", tr!("no-such-image", max = images.len())),
+        }
+    }
+}
+
+/// Shows the topics on a page of the discussion board of a problem, each with
+/// its posts.
+pub async fn view_board(session: &Session, url: &str, page: Option<u32>) -> Result<()> {
+    let config = session.config();
+    let url = ensure_last_problem(url, &config)?;
+    let client = session.client().await?;
+    let board = spin(
+        tr!("fetching-board"),
+        libopenjudge::get_board(client, &url, page),
+    )
+    .await?;
+    if board.topics.is_empty() {
+        println!("{}", tr!("no-topics"));
+        return Ok(());
+    }
+    let all_posts = spin(
+        tr!("fetching-posts"),
+        libopenjudge::join_bounded(
+            board
+                .topics
+                .iter()
+                .map(|topic| libopenjudge::get_board_posts(client, &topic.url)),
+            session.config().concurrency(),
+        ),
+    )
+    .await;
+    let graphics = session.graphics();
+    println!("{}\n", board.url.blue().underline());
+    for (topic, posts) in board.topics.iter().zip(all_posts) {
+        println!(
+            "{} {}",
+            topic.title.yellow().bold(),
+            tr!("topic-replies", count = topic.replies).dimmed()
+        );
+        for post in posts? {
+            let time = post.time.as_ref().map(format_time).unwrap_or_default();
+            println!("{} {}", post.author.bold(), time.dimmed());
+            let content = render_html(&post.content, RenderMode::Styled(graphics)).await;
+            for line in content.trim().lines() {
+                println!("{} {}", vertical_bar().dimmed(), line);
+            }
+        }
+        println!();
+    }
+    if board.max_page > 1 {
+        println!("{}", page_of(board.page, board.max_page));
+    }
+    Ok(())
+}
+
+/// Fetches and draws the image at `url`, regardless of `--no-images`.
+pub async fn view_image(session: &Session, url: &str) -> Result<()> {
+    let image = spin(
+        tr!("fetching-image"),
+        render_image(url, session.image_graphics()),
+    )
+    .await;
+    println!("{}", image);
+    Ok(())
+}
+
+/// Runs the pre-submit `hooks` on `file`, failing on the first hook that
+/// exits with an error. The path is quoted, see [`expand_command_template`].
+pub fn run_pre_submit_hooks(hooks: &[String], file: &str) -> Result<()> {
+    for hook in hooks {
+        let command = expand_command_template(hook, file, "");
+        println!("{} {}", tr!("running-hook").dimmed(), command.dimmed());
+        let status = shell_command(&command).status()?;
+        if !status.success() {
+            return Err(anyhow::anyhow!(tr!(
+                "hook-failed",
+                command = command,
+                code = status.code().unwrap_or_default()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Asks on the terminal whether to go on, `false` unless the answer is yes.
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} ", prompt);
+    std::io::stdout().flush()?;
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    Ok(matches!(line.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Submits `file` to each of `urls`. Unless `force`, code already submitted
+/// to a problem is submitted again only if the user confirms it on a
+/// terminal. Returns whether anything was submitted.
+async fn submit_solution_internal(
+    session: &Session,
+    urls: Vec<&ProblemUrl>,
+    file: &str,
+    lang: &Language,
+    verify: bool,
+    force: bool,
+    wait: bool,
+) -> Result<bool> {
+    if verify {
+        let hooks = session.config().pre_submit_hooks.unwrap_or_default();
+        run_pre_submit_hooks(&hooks, file)?;
+    }
+    let code = fs::read_to_string(file)?;
+    if session.config().sanity_checks.unwrap_or(false) {
+        print!(
+            "{}",
+            render_diagnostic_list(&sanity::sanity_check(&code, lang), &code)
+        );
+    }
+    let hash = source_hash(&code);
+    let mut urls = urls;
+    if !force {
+        let log = load_json::<SolveLog>(get_config_root().join("solves.json"))?;
+        let mut kept = vec![];
+        for url in urls {
+            if !log.was_submitted(&url.to_string(), &hash) {
+                kept.push(url);
+                continue;
+            }
+            println!(
+                "{} {}",
+                tr!("warning").yellow().bold(),
+                tr!(
+                    "duplicate-submission",
+                    file = file.bold().to_string(),
+                    url = url.to_string().blue().underline().to_string()
+                )
+            );
+            if is_interactive() && confirm(&tr!("resubmit-prompt"))? {
+                kept.push(url);
+            } else {
+                println!("{}", tr!("duplicate-skipped"));
+            }
+        }
+        urls = kept;
+        if urls.is_empty() {
+            return Ok(false);
+        }
+    }
+    let client = session.authenticated_client().await?;
+    if urls.len() > 1 {
+        // make sure every problem can be loaded before submitting to any
+        let checks = spin(
+            tr!("checking-problems"),
+            libopenjudge::join_bounded(
+                urls.iter().map(|url| session.problem(url)),
+                session.config().concurrency(),
+            ),
+        )
+        .await;
+        for (url, check) in urls.iter().zip(checks) {
+            check.with_context(|| tr!("cannot-load-problem", url = url.to_string()))?;
+        }
+    }
+    for url in urls {
+        let spinner = Spinner::start(tr!("submitting", url = url.to_string()));
+        let submission_url = libopenjudge::submit_solution(client, url, &code, lang).await?;
+        session.update_solves(|log| log.record_source(&url.to_string(), &hash))?;
+        session.update_pending(|log| {
+            log.push(PendingSubmission {
+                url: submission_url.clone(),
+                problem: url.to_string(),
+                file: file.to_string(),
+                time: chrono::Local::now().fixed_offset(),
+            })
+        })?;
+        spinner.println(tr!(
+            "submission-created",
+            url = submission_url.blue().underline().to_string()
+        ));
+        if !wait {
+            continue;
+        }
+        spinner.set_message(tr!("waiting-for-verdict"));
+        let submission = libopenjudge::query_submission_result(client, &submission_url).await?;
+        drop(spinner);
+        print!("{}", Styled(&submission));
+        record_verdict(session, &submission_url, url, file, lang, &submission).await?;
+    }
+    if !wait {
+        println!("{}", tr!("monitor-hint"));
+    }
+    Ok(true)
+}
+
+/// Local tests taking at least this long alert the user when they finish,
+/// quicker ones are watched anyway.
+const LONG_LOCAL_TEST: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Alerts the user that something they waited for is done, by playing the
+/// sound command of the config, or else ringing the terminal bell if turned
+/// on. The sound plays in the background.
+fn notify_finished(config: &AppConfig) {
+    use std::io::IsTerminal;
+    if let Some(command) = &config.sound_command {
+        let _ = shell_command(command)
+            .stdin(process::Stdio::null())
+            .stdout(process::Stdio::null())
+            .stderr(process::Stdio::null())
+            .spawn();
+    } else if config.bell.unwrap_or(false) && std::io::stdout().is_terminal() {
+        print!("\x07");
+        let _ = std::io::stdout().flush();
+    }
+}
+
+/// Notes the verdict of a submission of `file` made through the CLI: it is no
+/// longer pending, it counts towards solve times, and it is archived if
+/// accepted and archiving is on. The user is alerted as configured.
+async fn record_verdict(
+    session: &Session,
+    submission_url: &str,
+    url: &ProblemUrl,
+    file: &str,
+    lang: &Language,
+    submission: &libopenjudge::Submission,
+) -> Result<()> {
+    notify_finished(&session.config());
+    let accepted = submission.result == SubmissionResult::Accepted;
+    if accepted && let Some(dir) = &session.config().archive_dir {
+        match archive_solution(session, Path::new(dir), url, file, lang, &submission.code).await {
+            Ok(path) => println!(
+                "{}",
+                tr!(
+                    "archived-to",
+                    path = path.display().to_string().bold().to_string()
+                )
+            ),
+            Err(e) => println!(
+                "{} {}",
+                tr!("warning").yellow().bold(),
+                tr!("cannot-archive", error = format!("{:#}", e))
+            ),
+        }
+    }
+    session.update_pending(|log| log.remove(submission_url))?;
+    session.update_solves(|log| {
+        log.record_submission(
+            &url.to_string(),
+            accepted,
+            chrono::Local::now().fixed_offset(),
+        )
+    })
+}
+
+/// Time between checks of `oj monitor`.
+const MONITOR_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Checks of a submission in a row that may fail before `oj monitor` stops
+/// following it. It stays pending, for the next `oj monitor`.
+const MONITOR_RETRIES: u32 = 10;
+
+/// Shows the submissions waiting for a verdict in a table redrawn as they are
+/// judged. Without a terminal to redraw on, or for screen readers, verdicts
+/// are printed as they come and the table once at the end.
+pub async fn monitor(session: &Session) -> Result<()> {
+    use std::io::IsTerminal;
+    let pending = load_json::<PendingLog>(get_config_root().join("pending.json"))?.submissions;
+    if pending.is_empty() {
+        println!("{}", tr!("no-pending-submissions"));
+        return Ok(());
+    }
+    let config = session.config();
+    let client = session.authenticated_client().await?;
+    let live = std::io::stdout().is_terminal() && !screen_reader();
+    let mut statuses: Vec<Option<Result<SubmissionResult, String>>> = vec![None; pending.len()];
+    // failed checks in a row of each submission
+    let mut failures = vec![0; pending.len()];
+    let followed = |status: &Option<Result<SubmissionResult, String>>, failures: u32| match status {
+        None | Some(Ok(SubmissionResult::Waiting)) => true,
+        Some(Err(_)) => failures < MONITOR_RETRIES,
+        Some(Ok(_)) => false,
+    };
+    let mut drawn = 0;
+    loop {
+        let unfinished = (0..pending.len())
+            .filter(|&i| followed(&statuses[i], failures[i]))
+            .collect::<Vec<_>>();
+        let fetched = libopenjudge::join_bounded(
+            unfinished
+                .iter()
+                .map(|&i| libopenjudge::get_submission(client, &pending[i].url)),
+            session.config().concurrency(),
+        )
+        .await;
+        for (index, fetched) in unfinished.into_iter().zip(fetched) {
+            let submission = &pending[index];
+            let status = match fetched {
+                Ok(judged) if judged.result != SubmissionResult::Waiting => 'judged: {
+                    let problem = match submission.problem.parse::<ProblemUrl>() {
+                        Ok(problem) => problem,
+                        Err(e) => break 'judged Err(format!("{:#}", e)),
+                    };
+                    let lang =
+                        determine_language(&submission.file, None, config.custom_languages())
+                            .unwrap_or_else(|_| Language::Custom(judged.lang.clone()));
+                    if !live {
+                        println!(
+                            "{} {}",
+                            submission.file.bold(),
+                            verdict_label(&judged.result)
+                        );
+                    }
+                    match record_verdict(
+                        session,
+                        &submission.url,
+                        &problem,
+                        &submission.file,
+                        &lang,
+                        &judged,
+                    )
+                    .await
+                    {
+                        Ok(()) => Ok(judged.result),
+                        Err(e) => Err(format!("{:#}", e)),
+                    }
+                }
+                Ok(judged) => Ok(judged.result),
+                Err(e) => Err(format!("{:#}", e)),
+            };
+            failures[index] = if status.is_err() {
+                failures[index] + 1
+            } else {
+                0
+            };
+            statuses[index] = Some(status);
+        }
+        let done = statuses
+            .iter()
+            .zip(&failures)
+            .all(|(status, &failures)| !followed(status, failures));
+        if live || done {
+            let mut table = Table::new([
+                tr!("column-problem"),
+                tr!("column-file"),
+                tr!("column-submitted"),
+                tr!("column-result"),
+            ]);
+            for ((submission, status), &failures) in pending.iter().zip(&statuses).zip(&failures) {
+                table.push_row([
+                    submission.problem.blue().underline().to_string(),
+                    submission.file.bold().to_string(),
+                    format_time(&submission.time),
+                    match status {
+                        None | Some(Ok(SubmissionResult::Waiting)) => {
+                            tr!("verdict-waiting").yellow().to_string()
+                        }
+                        Some(Ok(result)) => verdict_label(result).to_string(),
+                        Some(Err(e)) => {
+                            let error = e.lines().next().unwrap_or_default();
+                            if failures < MONITOR_RETRIES {
+                                tr!("check-retrying", error = error).yellow().to_string()
+                            } else {
+                                error.red().to_string()
+                            }
+                        }
+                    },
+                ]);
+            }
+            let table = table.to_string();
+            if drawn > 0 {
+                crossterm::execute!(
+                    std::io::stdout(),
+                    crossterm::cursor::MoveUp(drawn),
+                    crossterm::terminal::Clear(crossterm::terminal::ClearType::FromCursorDown)
+                )?;
+            }
+            print!("{}", table);
+            std::io::stdout().flush()?;
+            drawn = table.lines().count() as u16;
+        }
+        if done {
+            return Ok(());
+        }
+        tokio::time::sleep(MONITOR_INTERVAL).await;
+    }
+}
+
+/// Lowercase words of `title` joined by dashes, for use in file names.
+/// Letters of any script are kept, other characters separate words.
+pub fn slugify(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Where an accepted solution of the problem at `url` is archived under
+/// `dir`: `<group>/<probset>/<number>-<slug>.<extension>`.
+pub fn archive_path(dir: &Path, url: &ProblemUrl, title: &str, extension: &str) -> PathBuf {
+    let slug = slugify(title);
+    let name = if slug.is_empty() {
+        url.number().to_string()
+    } else {
+        format!("{}-{}", url.number(), slug)
+    };
+    dir.join(url.group())
+        .join(url.probset())
+        .join(format!("{}.{}", name, extension))
+}
+
+/// Copies the accepted `code` of `file` into the archive under `dir`. The
+/// title of the problem is the one last viewed, or fetched if there is none.
+async fn archive_solution(
+    session: &Session,
+    dir: &Path,
+    url: &ProblemUrl,
+    file: &str,
+    lang: &Language,
+    code: &str,
+) -> Result<PathBuf> {
+    let log = load_json::<SolveLog>(get_config_root().join("solves.json"))?;
+    let title = match log
+        .problems
+        .get(&url.to_string())
+        .and_then(|record| record.title.clone())
+    {
+        Some(title) => title,
+        None => session.problem(url).await?.title,
+    };
+    let extension = Path::new(file)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_string())
+        .unwrap_or_else(|| {
+            match lang {
+                Language::Gcc => "c",
+                Language::Gpp => "cpp",
+                Language::Python3 | Language::PyPy3 => "py",
+                Language::Pascal => "pas",
+                Language::Custom(_) => "txt",
+            }
+            .to_string()
+        });
+    let path = archive_path(dir, url, &title, &extension);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, code)?;
+    Ok(path)
+}
+
+/// Submits to each of `args`. When every argument is a manifest key, each key is
+/// submitted with its mapped file and language; otherwise the last argument is
+/// the source file and the rest are problem URLs or keys. Pre-submit hooks and
+/// the check for code already submitted are skipped unless `verify`. Verdicts
+/// are waited for if `wait`, and left for `oj monitor` otherwise.
+pub async fn submit_solution(
+    session: &Session,
+    args: Vec<&str>,
+    lang: Option<String>,
+    verify: bool,
+    force: bool,
+    wait: bool,
+) -> Result<()> {
+    let config = session.config();
+    let manifest = Manifest::read_current()?;
+    let all_keys = manifest
+        .as_ref()
+        .is_some_and(|manifest| args.iter().all(|arg| manifest.get(arg).is_some()));
+    if all_keys {
+        let mut last_url = None;
+        let mut submitted = false;
+        for arg in &args {
+            let target = resolve_target(arg, &config, &manifest)?;
+            let file = target
+                .file
+                .ok_or_else(|| anyhow::anyhow!(tr!("no-manifest-file", key = arg.to_string())))?;
+            let lang = determine_language(
+                &file,
+                lang.clone().or(target.lang),
+                config.custom_languages(),
+            )?;
+            submitted |= submit_solution_internal(
+                session,
+                vec![&target.url],
+                &file,
+                &lang,
+                verify,
+                force,
+                wait,
+            )
+            .await?;
+            last_url = Some(target.url);
+        }
+        if let Some(url) = last_url {
+            session.remember_problem(&url)?;
+        }
+        if !submitted {
+            return Err(anyhow::anyhow!(tr!("nothing-submitted")));
+        }
+        return Ok(());
+    }
+    let (file, urls) = args
+        .split_last()
+        .ok_or_else(|| anyhow::anyhow!(tr!("no-problem-specified")))?;
+    if urls.is_empty() {
+        return Err(anyhow::anyhow!(tr!(
+            "not-a-manifest-key",
+            key = file.to_string()
+        )));
+    }
+    let targets = urls
+        .iter()
+        .map(|url| resolve_target(url, &config, &manifest))
+        .collect::<Result<Vec<_>>>()?;
+    let lang = determine_language(
+        file,
+        lang.or_else(|| targets[0].lang.clone()),
+        config.custom_languages(),
+    )?;
+    let urls = targets.iter().map(|t| &t.url).collect::<Vec<_>>();
+    let submitted =
+        submit_solution_internal(session, urls.clone(), file, &lang, verify, force, wait).await?;
+    if urls.len() == 1 {
+        session.remember_problem(urls[0])?;
+    }
+    if !submitted {
+        return Err(anyhow::anyhow!(tr!("nothing-submitted")));
+    }
+    Ok(())
+}
+
+/// How `oj test` checks a solution, and what it does after.
+#[derive(Default)]
+pub struct TestOptions {
+    /// Whether to submit the solution if every case passes.
+    pub submit: bool,
+    pub diff_style: DiffStyle,
+    /// Whether to run the pre-submit hooks before submitting.
+    pub verify: bool,
+    /// Whether to submit code already submitted to the problem without
+    /// asking.
+    pub force: bool,
+    /// Cases of the user, as `<input file>:<expected output file>`.
+    pub cases: Vec<String>,
+}
+
+/// Tests `file` on the samples of the problem at `url`, its cases under
+/// `tests/` and the cases of `options`, then submits it if asked to and all
+/// of them pass.
+pub async fn test_solution(
+    session: &Session,
+    url: &str,
+    file: Option<String>,
+    lang: Option<String>,
+    options: TestOptions,
+) -> Result<()> {
+    let TestOptions {
+        submit,
+        diff_style,
+        verify,
+        force,
+        cases,
+    } = options;
+    let cases = cases
+        .iter()
+        .map(|case| read_case_arg(case))
+        .collect::<Result<Vec<_>>>()?;
+    let config = session.config();
+    let manifest = Manifest::read_current()?;
+    let target = resolve_target(url, &config, &manifest)?;
+    let url = &target.url;
+    let file = file
+        .or(target.file)
+        .ok_or_else(|| anyhow::anyhow!(tr!("no-source-file", url = url.to_string())))?;
+    let file = file.as_str();
+    let lang = determine_language(file, lang.or(target.lang), config.custom_languages())?;
+    let start = std::time::Instant::now();
+    let verdict = run_samples(session, url, file, &lang, diff_style, &cases).await?;
+    if start.elapsed() >= LONG_LOCAL_TEST {
+        notify_finished(&config);
+    }
+    session.remember_problem(url)?;
+    if submit
+        && matches!(verdict, SubmissionResult::Accepted)
+        && !submit_solution_internal(session, vec![url], file, &lang, verify, force, true).await?
+    {
+        return Err(anyhow::anyhow!(tr!("nothing-submitted")));
+    }
+    Ok(())
+}
+
+/// Lines at the top of a source file searched for its problem URL.
+const HEADER_LINES: usize = 10;
+
+/// Editor of the config, or else of the environment.
+fn editor_command(config: &AppConfig) -> String {
+    config
+        .editor
+        .clone()
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        })
+}
+
+/// First lines of a new source file for the problem at `url`: a comment
+/// naming it, so `oj test --dir` and `oj edit` find it later, followed by the
+/// template for files with `extension`, if any.
+pub fn new_source(url: &str, extension: &str, template: Option<&str>) -> String {
+    let header = match extension.to_lowercase().as_str() {
+        "py" | "rb" | "sh" => format!("# {}", url),
+        "pas" => format!("{{ {} }}", url),
+        _ => format!("// {}", url),
+    };
+    format!("{}\n{}", header, template.unwrap_or_default())
+}
+
+/// Template for new source files with `extension`, from the `templates`
+/// directory of the config root.
+fn read_template(extension: &str) -> Option<String> {
+    fs::read_to_string(
+        get_config_root()
+            .join("templates")
+            .join(format!("template.{}", extension)),
+    )
+    .ok()
+}
+
+/// Opens the source file of the problem at `url` in the editor. It is `file`,
+/// or the one mapped in the manifest, or else a file in the current directory
+/// naming the problem in its header. A missing file is created if `create`.
+pub fn edit(session: &Session, url: &str, file: Option<String>, create: bool) -> Result<()> {
+    let config = session.config();
+    let manifest = Manifest::read_current()?;
+    let target = resolve_target(url, &config, &manifest)?;
+    let url = target.url;
+    let file = file.or(target.file).or_else(|| {
+        let mut paths = fs::read_dir(".")
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file())
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths.into_iter().find_map(|path| {
+            let header = header_problem_url(&fs::read_to_string(&path).ok()?)?;
+            let named = ensure_last_problem(&header, &config).ok()?;
+            (named.to_string() == url.to_string())
+                .then(|| path.to_string_lossy().trim_start_matches("./").to_string())
+        })
+    });
+    let file = file.ok_or_else(|| anyhow::anyhow!(tr!("no-source-file", url = url.to_string())))?;
+    let path = Path::new(&file);
+    if !path.exists() {
+        if !create {
+            return Err(anyhow::anyhow!(tr!(
+                "source-not-found",
+                file = file.as_str()
+            )));
+        }
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let template = read_template(&extension);
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            path,
+            new_source(&url.to_string(), &extension, template.as_deref()),
+        )?;
+        println!("{}", tr!("file-created", file = file.bold().to_string()));
+    }
+    let editor = editor_command(&config);
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or_default();
+    let status = process::Command::new(program)
+        .args(words)
+        .arg(&file)
+        .status()
+        .with_context(|| tr!("running-editor", editor = editor.as_str()))?;
+    session.remember_problem(&url)?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(tr!(
+            "editor-failed",
+            editor = editor.as_str(),
+            code = status.code().unwrap_or_default()
+        )));
+    }
+    Ok(())
+}
+
+/// Problem URL named in a comment at the top of a source file, such as
+/// `// http://cs101.openjudge.cn/practice/01005/`.
+pub fn header_problem_url(source: &str) -> Option<String> {
+    let pattern = Regex::new(r#"https?://[\w.-]*openjudge\.cn/[^\s"'<>)]+"#).unwrap();
+    source
+        .lines()
+        .take(HEADER_LINES)
+        .find_map(|line| pattern.find(line).map(|(start, end)| &line[start..end]))
+        .map(|url| url.trim_end_matches(['.', ',', ';']).to_string())
+}
+
+/// Tests every source file in `dir` against the samples of its problem, found
+/// in the manifest or in a comment at the top of the file, then prints a
+/// summary. Fails if any of them did not pass.
+pub async fn test_directory(session: &Session, dir: &str, diff_style: DiffStyle) -> Result<()> {
+    let config = session.config();
+    let manifest = Manifest::read_current()?;
+    let mut paths = fs::read_dir(dir)
+        .with_context(|| tr!("reading-file", path = dir))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+    paths.sort();
+    let mut table = Table::new([
+        tr!("column-file"),
+        tr!("column-problem"),
+        tr!("column-result"),
+    ]);
+    let (mut tested, mut passed) = (0, 0);
+    let start = std::time::Instant::now();
+    for path in paths {
+        let file = path.to_string_lossy().to_string();
+        let canonical = fs::canonicalize(&path).ok();
+        let mapped = manifest.as_ref().and_then(|manifest| {
+            manifest.problems.values().find(|problem| {
+                problem
+                    .file
+                    .as_ref()
+                    .and_then(|file| fs::canonicalize(file).ok())
+                    .is_some_and(|file| Some(file) == canonical)
+            })
+        });
+        let Ok(lang) = determine_language(
+            &file,
+            mapped.and_then(|problem| problem.lang.clone()),
+            config.custom_languages(),
+        ) else {
+            continue;
+        };
+        let url = match mapped {
+            Some(problem) => Some(problem.url.clone()),
+            None => fs::read_to_string(&path)
+                .ok()
+                .and_then(|source| header_problem_url(&source)),
+        };
+        let Some(url) = url else {
+            table.push_row([
+                file.bold().to_string(),
+                "-".to_string(),
+                tr!("no-problem-url").dimmed().to_string(),
+            ]);
+            continue;
+        };
+        tested += 1;
+        let result = match ensure_last_problem(&url, &config) {
+            Ok(url) => run_samples(session, &url, &file, &lang, diff_style, &[]).await,
+            Err(e) => Err(e),
+        };
+        println!();
+        let result = match result {
+            Ok(verdict) => {
+                if matches!(verdict, SubmissionResult::Accepted) {
+                    passed += 1;
+                }
+                verdict_label(&verdict).to_string()
+            }
+            Err(e) => format!("{:#}", e)
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .red()
+                .to_string(),
+        };
+        table.push_row([
+            file.bold().to_string(),
+            url.blue().underline().to_string(),
+            result,
+        ]);
+    }
+    if tested == 0 {
+        return Err(anyhow::anyhow!(tr!("no-solutions-in", dir = dir)));
+    }
+    print!("{}", table);
+    if start.elapsed() >= LONG_LOCAL_TEST {
+        notify_finished(&config);
+    }
+    if passed < tested {
+        return Err(anyhow::anyhow!(tr!(
+            "solutions-failed",
+            failed = tested - passed,
+            total = tested
+        )));
+    }
+    println!("{}", tr!("solutions-passed", count = tested));
+    Ok(())
+}
+
+/// Sample input and output of `problem` as plain text. An input stated as
+/// "(无)", none, is empty.
+async fn plain_samples(problem: &Problem) -> (String, String) {
+    let mut input = if let Some(s) = &problem.sample_input {
+        render_html(s, RenderMode::Plain).await
+    } else {
+        String::new()
+    };
+    let output = if let Some(s) = &problem.sample_output {
+        render_html(s, RenderMode::Plain).await
+    } else {
+        String::new()
+    };
+    if input.as_str() == "(无)" || input.as_str() == "（无）" {
+        input = "".to_string();
+    }
+    (input, output)
+}
+
+/// An input and the output expected for it, from the problem statement or
+/// from files of the user.
+pub struct TestCase {
+    /// Where the case comes from, shown above its input.
+    pub name: String,
+    pub input: String,
+    pub output: String,
+}
+
+/// Reads the case given as `<input file>:<expected output file>`. The last
+/// colon separates them, so the input may have a Windows drive letter.
+pub fn read_case_arg(arg: &str) -> Result<TestCase> {
+    let (input, output) = arg
+        .rsplit_once(':')
+        .filter(|(input, output)| !input.is_empty() && !output.is_empty())
+        .ok_or_else(|| anyhow::anyhow!(tr!("invalid-case", case = arg)))?;
+    Ok(TestCase {
+        name: input.to_string(),
+        input: fs::read_to_string(input).with_context(|| tr!("reading-file", path = input))?,
+        output: fs::read_to_string(output).with_context(|| tr!("reading-file", path = output))?,
+    })
+}
+
+/// The cases in `dir`, each `<name>.in` with the `<name>.out` next to it,
+/// numbered names first in numeric order. A missing `dir` has none.
+pub fn read_case_dir(dir: &Path) -> Result<Vec<TestCase>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => {
+            return Err(e).with_context(|| tr!("reading-file", path = dir.display().to_string()));
+        }
+    };
+    let mut inputs = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == "in"))
+        .collect::<Vec<_>>();
+    inputs.sort_by_key(|path| {
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        (stem.parse::<u64>().unwrap_or(u64::MAX), stem)
+    });
+    inputs
+        .into_iter()
+        .map(|input| {
+            let output = input.with_extension("out");
+            if !output.is_file() {
+                return Err(anyhow::anyhow!(tr!(
+                    "no-expected-output",
+                    input = input.display().to_string(),
+                    output = output.display().to_string()
+                )));
+            }
+            Ok(TestCase {
+                name: input.display().to_string(),
+                input: fs::read_to_string(&input)?,
+                output: fs::read_to_string(&output)?,
+            })
+        })
+        .collect()
+}
+
+/// Directory of the user's own cases of the problem at `url`: `tests/<key>`
+/// for the key mapping it in the manifest, as written by `oj contest init`,
+/// or else `tests/<number>`.
+pub fn case_dir(url: &ProblemUrl, manifest: Option<&Manifest>) -> PathBuf {
+    let name = manifest
+        .and_then(|manifest| manifest.key_of(url))
+        .unwrap_or(url.number());
+    Path::new("tests").join(name)
+}
+
+/// A solution ready to run, compiled if its language needs it. The
+/// executable is removed when it is dropped.
+struct PreparedSolution {
+    command: Box<dyn Fn() -> process::Command>,
+    executable: Option<String>,
+}
+
+impl PreparedSolution {
+    fn run(&self, input: &str) -> Result<process::Output> {
+        let mut child_process = (self.command)()
+            .stdin(process::Stdio::piped())
+            .stdout(process::Stdio::piped())
+            .stderr(process::Stdio::piped())
+            .spawn()?;
+        let mut stdin = child_process
+            .stdin
+            .take()
+            .expect("Handle to stdin not available.");
+        // written from another thread, so a large input cannot block on a
+        // full output pipe
+        let input = input.to_string();
+        let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+        let output = child_process.wait_with_output()?;
+        // a solution may exit without reading all of its input
+        let _ = writer.join();
+        Ok(output)
+    }
+}
+
+impl Drop for PreparedSolution {
+    fn drop(&mut self) {
+        if let Some(executable) = &self.executable {
+            let _ = fs::remove_file(executable);
+        }
+    }
+}
+
+/// Prints the verdict heading of a compile error and the diagnostics of
+/// `file` in the compiler's `output`.
+fn print_compile_error(output: &process::Output, file: &str) -> Result<()> {
+    println!(
+        "{}",
+        verdict_heading(&SubmissionResult::CompileError { message: None })
+    );
+    // fpc reports on stdout
+    let message = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    print!(
+        "{}",
+        render_diagnostics(&message, &fs::read_to_string(file)?)
+    );
+    Ok(())
+}
+
+/// Compiles `file` if its language needs it. Returns `None` after printing
+/// why if it does not compile, or if it has a warning set to block in the
+/// config.
+fn prepare_solution(
+    config: &AppConfig,
+    file: &str,
+    lang: &Language,
+) -> Result<Option<PreparedSolution>> {
+    // .exe used for Windows compatibility
+    let excutable_path = format!("./sol-{}.exe", nanoid!());
+    match lang {
+        Language::Gcc | Language::Gpp | Language::Pascal => {
+            let compilation = if *lang == Language::Pascal {
+                // keep fpc's object files out of the working directory
+                let units_dir = std::env::temp_dir().join(format!("oj-fpc-{}", nanoid!()));
+                fs::create_dir_all(&units_dir)?;
+                let compilation = process::Command::new("fpc")
+                    .arg("-l-")
+                    .arg("-vew")
+                    .arg(format!("-FU{}", units_dir.display()))
+                    .arg(format!("-o{}", excutable_path))
+                    .arg(file)
+                    .output();
+                let _ = fs::remove_dir_all(&units_dir);
+                compilation?
+            } else if *lang == Language::Gpp {
+                let cpp_std = config.cpp_std.as_deref().unwrap_or(JUDGE_CPP_STD);
+                if cpp_std != JUDGE_CPP_STD {
+                    println!(
+                        "{} {}",
+                        tr!("warning").yellow().bold(),
+                        tr!(
+                            "cpp-std-differs",
+                            cpp_std = cpp_std.bold().to_string(),
+                            judge_std = JUDGE_CPP_STD.bold().to_string()
+                        )
+                    );
+                }
+                process::Command::new("g++")
+                    .arg(format!("--std={}", cpp_std))
+                    .arg("-Wall")
+                    .arg("-o")
+                    .arg(&excutable_path)
+                    .arg(file)
+                    .output()?
+            } else {
+                process::Command::new("gcc")
+                    .arg(format!("--std={}", JUDGE_C_STD))
+                    .arg("-Wall")
+                    .arg("-o")
+                    .arg(&excutable_path)
+                    .arg(file)
+                    .output()?
+            };
+            if !compilation.status.success() {
+                print_compile_error(&compilation, file)?;
+                return Ok(None);
+            }
+            let prepared = PreparedSolution {
+                command: Box::new({
+                    let executable = excutable_path.clone();
+                    move || process::Command::new(&executable)
+                }),
+                executable: Some(excutable_path),
+            };
+            let message = format!(
+                "{}{}",
+                String::from_utf8_lossy(&compilation.stdout),
+                String::from_utf8_lossy(&compilation.stderr)
+            );
+            let warnings = compiler_warnings(&message);
+            if !warnings.is_empty() {
+                print!(
+                    "{}",
+                    render_diagnostic_list(&warnings, &fs::read_to_string(file)?)
+                );
+                let blocking = config.blocking_warnings.as_deref().unwrap_or_default();
+                let blockers = warnings
+                    .iter()
+                    .filter(|warning| warning.is_blocking(blocking))
+                    .filter_map(|warning| warning.warning_flag())
+                    .map(|flag| format!("-W{}", flag))
+                    .collect::<BTreeSet<_>>();
+                if !blockers.is_empty() {
+                    println!(
+                        "{}",
+                        verdict_heading(&SubmissionResult::CompileError { message: None })
+                    );
+                    println!(
+                        "{}",
+                        tr!(
+                            "warnings-blocking",
+                            flags = blockers
+                                .into_iter()
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                                .bold()
+                                .to_string()
+                        )
+                    );
+                    return Ok(None);
+                }
+            }
+            Ok(Some(prepared))
+        }
+        Language::PyPy3 | Language::Python3 => {
+            let interpreter = if *lang == Language::PyPy3 {
+                "pypy3".into()
+            } else {
+                python_interpreter(config.python.as_deref())
+            };
+            let file = file.to_string();
+            Ok(Some(PreparedSolution {
+                command: Box::new(move || {
+                    let mut command = process::Command::new(&interpreter);
+                    command.arg(&file).env("PYTHON_COLORS", "1");
+                    command
+                }),
+                executable: None,
+            }))
+        }
+        Language::Custom(submit_as) => {
+            let language = config.custom_language(lang).ok_or_else(|| {
+                anyhow::anyhow!(tr!("undeclared-language", name = submit_as.as_str()))
+            })?;
+            if let Some(compile) = &language.compile {
+                let compilation =
+                    shell_command(&expand_command_template(compile, file, &excutable_path))
+                        .output()?;
+                if !compilation.status.success() {
+                    print_compile_error(&compilation, file)?;
+                    let _ = fs::remove_file(&excutable_path);
+                    return Ok(None);
+                }
+            }
+            let run = expand_command_template(&language.run, file, &excutable_path);
+            Ok(Some(PreparedSolution {
+                command: Box::new(move || shell_command(&run)),
+                executable: Some(excutable_path),
+            }))
+        }
+    }
+}
+
+/// Prints how `code_output` of `case` went, and returns its verdict.
+fn judge_case(
+    case: &TestCase,
+    code_output: process::Output,
+    diff_style: DiffStyle,
+) -> Result<SubmissionResult> {
+    let output = &case.output;
+    if code_output.status.success() {
+        let code_output = String::from_utf8(code_output.stdout)?;
+        if code_output.trim() == output.trim() {
+            println!("{}", verdict_heading(&SubmissionResult::Accepted));
+            Ok(SubmissionResult::Accepted)
+        } else {
+            println!("{}", verdict_heading(&SubmissionResult::WrongAnswer));
+            println!("{}", tr!("expected-output").yellow().bold());
+            println!("{}", output.trim());
+            println!("{}", tr!("your-output").yellow().bold());
+            println!("{}", code_output.trim());
+            println!("{}", tr!("diff").yellow().bold());
+            let width = crossterm::terminal::size().map_or(80, |(columns, _)| columns as usize);
+            print!(
+                "{}",
+                render_diff(output.trim(), code_output.trim(), diff_style, width)
+            );
+            Ok(SubmissionResult::WrongAnswer)
+        }
+    } else {
+        println!("{}", verdict_heading(&SubmissionResult::RuntimeError));
+        println!(
+            "{} {}",
+            tr!("exit-code"),
+            code_output.status.code().unwrap_or_default()
+        );
+        #[cfg(unix)]
+        {
+            println!(
+                "{} {}",
+                tr!("signal"),
+                code_output.status.signal().unwrap_or_default()
+            );
+        }
+        println!("STDOUT:\n{}", String::from_utf8(code_output.stdout)?);
+        println!("STDERR:\n{}", String::from_utf8(code_output.stderr)?);
+        Ok(SubmissionResult::RuntimeError)
+    }
+}
+
+/// Runs `file` on the samples of the problem at `url`, then on its cases in
+/// [`case_dir`] and on `extra_cases`, and prints how each went. The verdict
+/// is that of the first failing case, if any.
+async fn run_samples(
+    session: &Session,
+    url: &ProblemUrl,
+    file: &str,
+    lang: &Language,
+    diff_style: DiffStyle,
+    extra_cases: &[TestCase],
+) -> Result<SubmissionResult> {
+    let config = session.config();
+    let problem = fetch_problem(session, url).await?;
+    session.record_view(url, &problem)?;
+    let mut cases = vec![];
+    if problem.sample_input.is_some() && problem.sample_output.is_some() {
+        let (input, output) = plain_samples(&problem).await;
+        cases.push(TestCase {
+            name: tr!("sample-case"),
+            input,
+            output,
+        });
+    }
+    let manifest = Manifest::read_current()?;
+    cases.extend(read_case_dir(&case_dir(url, manifest.as_ref()))?);
+    let cases = cases.iter().chain(extra_cases).collect::<Vec<_>>();
+    if cases.is_empty() {
+        return Err(anyhow::anyhow!(tr!("no-samples")));
+    }
+    println!(
+        "{}",
+        tr!(
+            "testing-solution",
+            file = file.blue().underline().to_string(),
+            problem = problem.title.blue().underline().to_string()
+        )
+    );
+    let Some(prepared) = prepare_solution(&config, file, lang)? else {
+        return Ok(SubmissionResult::CompileError { message: None });
+    };
+    let mut verdict = SubmissionResult::Accepted;
+    let mut passed = 0;
+    let total = cases.len();
+    for case in cases {
+        if total > 1 {
+            println!(
+                "{}",
+                tr!(
+                    "case-heading",
+                    name = case.name.blue().underline().to_string()
+                )
+                .bold()
+            );
+        }
+        println!("{}", tr!("case-input").yellow().bold());
+        println!("{}", case.input.trim_end());
+        let result = judge_case(case, prepared.run(&case.input)?, diff_style)?;
+        if result == SubmissionResult::Accepted {
+            passed += 1;
+        } else if verdict == SubmissionResult::Accepted {
+            verdict = result;
+        }
+    }
+    if total > 1 {
+        println!(
+            "{}",
+            tr!(
+                "cases-passed",
+                passed = passed.to_string().bold().to_string(),
+                total = total
+            )
+        );
+    }
+    Ok(verdict)
+}
+
+/// Replaces the placeholders of a custom language command with the paths,
+/// quoted for [`shell_command`] so that spaces and quotes in them survive.
+pub fn expand_command_template(template: &str, source: &str, executable: &str) -> String {
+    let path = std::path::Path::new(source);
+    let dir = path
+        .parent()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .filter(|dir| !dir.is_empty())
+        .unwrap_or_else(|| ".".to_string());
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    template
+        .replace("{source}", &shell_quote(source))
+        .replace("{executable}", &shell_quote(executable))
+        .replace("{dir}", &shell_quote(&dir))
+        .replace("{stem}", &shell_quote(&stem))
+}
+
+/// Quotes `arg` as a single word for the shell run by [`shell_command`],
+/// leaving plain paths as they are.
+pub fn shell_quote(arg: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "_-+./:@,=".contains(c);
+    if !arg.is_empty() && arg.chars().all(plain) {
+        return arg.to_string();
+    }
+    if cfg!(windows) {
+        // cmd has no escape inside quotes, and paths cannot contain them anyway
+        format!("\"{}\"", arg.replace('"', ""))
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+fn shell_command(command: &str) -> process::Command {
+    #[cfg(windows)]
+    {
+        let mut shell = process::Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    }
+    #[cfg(not(windows))]
+    {
+        let mut shell = process::Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    }
+}
+
+pub async fn search(session: &Session, group: &str, query: &str, interactive: bool) -> Result<()> {
+    let client = session.client().await?;
+    let result = spin(
+        tr!("searching", query = query, group = group),
+        libopenjudge::search(client, group, query),
+    )
+    .await?;
+    if !interactive {
+        println!(
+            "{}",
+            tr!(
+                "found-results",
+                count = result.len().to_string().bold().to_string()
+            )
+        );
+        for item in &result {
+            println!("{}", Styled(item));
+        }
+        return Ok(());
+    }
+    let options = result.iter().map(Styled).collect::<Vec<_>>();
+    let mut previews = ProblemPreviews::new(session).await?;
+    let selection = interactions::select_many_with_preview(
+        &tr!("found-results", count = result.len()),
+        &options,
+        &StatusBar {
+            group: Some(group.to_string()),
+            ..session.status_bar()
+        },
+        &mut |i| previews.get(&result[i].url),
+    );
+    let Selection::Selected(indices) = selection else {
+        println!("{}", tr!("no-problem-selected"));
+        return Ok(());
+    };
+    let urls = indices
+        .iter()
+        .map(|&i| result[i].url.as_str())
+        .collect::<Vec<_>>();
+    view_one_by_one(session, &urls).await
+}
+
+/// Where `answer` to the prompt between problems moves from `current` among
+/// `len` of them, `None` to stop. An empty answer goes to the next problem.
+pub fn step_through(answer: &str, current: usize, len: usize) -> Option<usize> {
+    match answer.trim().to_lowercase().as_str() {
+        "" | "n" | "next" => (current + 1 < len).then_some(current + 1),
+        "p" | "prev" | "previous" => Some(current.saturating_sub(1)),
+        "q" | "quit" => None,
+        _ => Some(current),
+    }
+}
+
+/// Shows the problems at `urls` one at a time, asking between them whether
+/// to go to the next or previous one or to quit. They are fetched together
+/// up front, so moving between them does not wait.
+async fn view_one_by_one(session: &Session, urls: &[&str]) -> Result<()> {
+    if let [url] = urls {
+        return view_problem(session, url).await;
+    }
+    let parsed = urls
+        .iter()
+        .map(|url| url.parse::<ProblemUrl>())
+        .collect::<Result<Vec<_>, _>>()?;
+    for fetched in spin(
+        tr!("fetching-problems"),
+        libopenjudge::join_bounded(
+            parsed.iter().map(|url| session.problem(url)),
+            session.config().concurrency(),
+        ),
+    )
+    .await
+    {
+        fetched?;
+    }
+    let mut current = 0;
+    loop {
+        view_problem(session, urls[current]).await?;
+        let choices = if current + 1 < urls.len() {
+            tr!("step-choices")
+        } else {
+            tr!("step-choices-last")
+        };
+        print!(
+            "\n{} {}: ",
+            tr!("step-position", current = current + 1, total = urls.len()).bold(),
+            choices
+        );
+        std::io::stdout().flush()?;
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        match step_through(&line, current, urls.len()) {
+            Some(next) => current = next,
+            None => return Ok(()),
+        }
+        println!();
+    }
+}
+
+/// Previews of problems for the interactive selector, each fetched in the
+/// background the first time it is asked for.
+struct ProblemPreviews {
+    client: Arc<libopenjudge::Client>,
+    previews: Arc<Mutex<HashMap<String, String>>>,
+    requested: HashSet<String>,
+}
+
+impl ProblemPreviews {
+    async fn new(session: &Session) -> Result<Self> {
+        // a client of its own, since fetches outlive the borrow of the session
+        let client = create_client(&session.config()).await?;
+        Ok(Self {
+            client: Arc::new(client),
+            previews: Arc::new(Mutex::new(HashMap::new())),
+            requested: HashSet::new(),
+        })
+    }
+
+    /// The preview of the problem at `url`, or `None` while it is loading.
+    fn get(&mut self, url: &str) -> Option<String> {
+        if let Some(preview) = self.previews.lock().unwrap().get(url) {
+            return Some(preview.clone());
+        }
+        if self.requested.insert(url.to_string()) {
+            let client = self.client.clone();
+            let previews = self.previews.clone();
+            let url = url.to_string();
+            tokio::spawn(async move {
+                let preview = match url.parse::<ProblemUrl>() {
+                    Ok(problem_url) => {
+                        match libopenjudge::get_problem(&*client, &problem_url).await {
+                            Ok(problem) => problem_preview(&problem),
+                            Err(e) => tr!("preview-failed", error = e.to_string()),
+                        }
+                    }
+                    Err(e) => tr!("preview-failed", error = e.to_string()),
+                };
+                previews.lock().unwrap().insert(url, preview);
+            });
+        }
+        None
+    }
+}
+
+pub async fn view_user(session: &Session) -> Result<()> {
+    let client = session.authenticated_client().await?;
+    let user = spin(tr!("fetching-user"), libopenjudge::get_user_info(client)).await?;
+    print!("{}", Styled(&user));
+    Ok(())
+}
+
+/// Shows the submission at `url` with its highlighted code, also putting the
+/// code on the clipboard if `copy`.
+pub async fn view_submission(session: &Session, url: &str, copy: bool) -> Result<()> {
+    let config = session.config();
+    let theme = code_theme::load_theme(config.code_theme.as_deref())?;
+    let client = session.authenticated_client().await?;
+    let submission = spin(
+        tr!("fetching-submission"),
+        libopenjudge::query_submission_result(client, url),
+    )
+    .await?;
+    println!("{}", Styled(&submission));
+    let extension = match submission.lang.as_str() {
+        "Python3" => "py",
+        "PyPy3" => "py",
+        "G++" => "cpp",
+        "GCC" => "c",
+        "Pascal" => "pas",
+        lang => config
+            .custom_languages()
+            .iter()
+            .find(|language| language.submit_as == lang)
+            .and_then(|language| language.extensions.first())
+            .map_or("txt", |extension| extension.trim_start_matches('.')),
+    };
+    let line_count = submission.code.lines().count();
+    println!(
+        "{} {}.{} · {} · {}",
+        format!(" {} ", tr!("code-heading")).bold().on_white(),
+        submission.id,
+        extension,
+        submission.lang,
+        tr!("line-count", count = line_count)
+    );
+    let syntax_set = SyntaxSet::load_defaults_nonewlines();
+    let syntax = syntax_set
+        .find_syntax_by_extension(extension)
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, &theme);
+    let gutter_width = line_count.to_string().len();
+    for (number, line) in submission.code.lines().enumerate() {
+        let ranges: Vec<(Style, &str)> = highlighter.highlight_line(line, &syntax_set)?;
+        let escaped = as_24_bit_terminal_escaped(&ranges[..], false);
+        // reset after the line so colors do not leak into the next gutter
+        println!(
+            "{} {}\x1b[0m",
+            format!("{:>gutter_width$} {}", number + 1, vertical_bar()).dimmed(),
+            escaped
+        );
+    }
+    if copy {
+        match clipboard::copy(&submission.code)? {
+            Copied::Command(command) => println!(
+                "{}",
+                tr!("copied-with", count = line_count, command = command)
+            ),
+            Copied::Terminal => println!("{}", tr!("copied-by-terminal", count = line_count)),
+        }
+    }
+    Ok(())
+}
+
+pub async fn list_submissions(
+    session: &Session,
+    problem_url: &str,
+    interactive: bool,
+) -> Result<()> {
+    let problem_url = ensure_last_problem(problem_url, &session.config())?;
+    let client = session.authenticated_client().await?;
+    let submissions = spin(
+        tr!("fetching-submissions"),
+        libopenjudge::list_submissions(client, &problem_url),
+    )
+    .await?;
+
+    if submissions.is_empty() {
+        println!("{}", tr!("no-submissions").bold());
+        return Ok(());
+    }
+
+    if !interactive {
+        println!(
+            "{}",
+            tr!(
+                "found-submissions",
+                count = submissions.len().to_string().bold().to_string()
+            )
+        );
+        print!("{}", Styled(&submissions));
+        return Ok(());
+    }
+    let selection = select_within(
+        &tr!(
+            "found-submissions",
+            count = submissions.len().to_string().bold().to_string()
+        ),
+        &submissions.iter().map(Styled).collect::<Vec<_>>(),
+        &StatusBar {
+            group: Some(problem_url.group().to_string()),
+            probset: Some(problem_url.probset().to_string()),
+            pending: submissions
+                .iter()
+                .filter(|submission| matches!(submission.result, SubmissionResult::Waiting))
+                .count(),
+            ..session.status_bar()
+        },
+    );
+    match selection {
+        Selection::Selected(i) => {
+            let selected_submission = &submissions[i];
+            view_submission(session, &selected_submission.url, false).await
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Prints the latest verdict of every problem in the manifest.
+pub async fn status(session: &Session) -> Result<()> {
+    let manifest = Manifest::read_current()?.ok_or_else(|| anyhow::anyhow!(no_manifest_found()))?;
+    let client = session.authenticated_client().await?;
+    let urls = manifest
+        .problems
+        .values()
+        .map(|problem| problem.url.parse())
+        .collect::<Result<Vec<ProblemUrl>>>()?;
+    let all_submissions = spin(
+        tr!("fetching-submissions"),
+        libopenjudge::join_bounded(
+            urls.iter()
+                .map(|url| libopenjudge::list_submissions(client, url)),
+            session.config().concurrency(),
+        ),
+    )
+    .await;
+    let mut table = Table::new([
+        tr!("column-key"),
+        tr!("column-result"),
+        tr!("column-time"),
+        "URL".to_string(),
+    ]);
+    for ((key, problem), submissions) in manifest.problems.iter().zip(all_submissions) {
+        let submissions = submissions?;
+        match submissions.first() {
+            Some(submission) => table.push_row([
+                key.bold().to_string(),
+                verdict_label(&submission.result).to_string(),
+                format_time(&submission.time),
+                submission.url.blue().underline().to_string(),
+            ]),
+            None => table.push_row([
+                key.bold().to_string(),
+                tr!("not-attempted").dimmed().to_string(),
+                String::new(),
+                problem.url.blue().underline().to_string(),
+            ]),
+        }
+    }
+    print!("{}", table);
+    Ok(())
+}
+
+pub fn strip_slashes(text: &str) -> &str {
+    let pattern = Regex::new(r#"^\/?(.*?)\/?$"#).unwrap();
+    let captures = pattern.captures(text).unwrap();
+    captures.at(1).unwrap_or("")
+}
+
+pub async fn join_group(session: &Session, group: &str, message: Option<String>) -> Result<()> {
+    let client = session.authenticated_client().await?;
+    let response = spin(
+        tr!("joining-group", group = group),
+        libopenjudge::join_group(client, group, message.as_deref()),
+    )
+    .await?;
+    match response {
+        Some(response) => println!("{}", response),
+        None => println!("{}", tr!("join-request-sent")),
+    }
+    Ok(())
+}
+
+/// Creates a problem from a problem description file, then uploads the files
+/// of its test data directory.
+pub async fn create_problem(
+    session: &Session,
+    group: &str,
+    probset: &str,
+    file: &str,
+    data: Option<String>,
+) -> Result<()> {
+    warn_experimental();
+    let spec = ProblemSpec::read(file)?;
+    let problem = spec.to_new_problem()?;
+    // test data named in the description is relative to it
+    let data_dir = data.map(std::path::PathBuf::from).or_else(|| {
+        spec.data.as_ref().map(|data| {
+            std::path::Path::new(file)
+                .parent()
+                .unwrap_or(std::path::Path::new(""))
+                .join(data)
+        })
+    });
+    let files = match &data_dir {
+        Some(dir) => read_test_data(dir)?,
+        None => vec![],
+    };
+    let client = session.authenticated_client().await?;
+    let url = spin(
+        tr!("creating-problem", title = problem.title.as_str()),
+        libopenjudge::create_problem(client, group, probset, &problem),
+    )
+    .await?;
+    println!(
+        "{}",
+        tr!(
+            "problem-created",
+            title = problem.title.bold().to_string(),
+            url = url.to_string().blue().underline().to_string()
+        )
+    );
+    if !files.is_empty() {
+        let response = spin(
+            tr!("uploading-test-data", count = files.len()),
+            libopenjudge::upload_test_data(client, &url, &files),
+        )
+        .await?;
+        match response {
+            Some(response) => println!("{}", response),
+            None => println!("{}", tr!("test-data-uploaded", count = files.len())),
+        }
+    }
+    session.remember_problem(&url)?;
+    Ok(())
+}
+
+/// Reads every file directly in `dir`, in name order.
+fn read_test_data(dir: &std::path::Path) -> Result<Vec<libopenjudge::FormFile>> {
+    let mut paths = fs::read_dir(dir)
+        .with_context(|| tr!("reading-file", path = dir.display().to_string()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    paths.retain(|path| path.is_file());
+    paths.sort();
+    if paths.is_empty() {
+        return Err(anyhow::anyhow!(tr!(
+            "no-test-data",
+            dir = dir.display().to_string()
+        )));
+    }
+    paths
+        .into_iter()
+        .map(|path| {
+            Ok(libopenjudge::FormFile {
+                field: "files".to_string(),
+                file_name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                content: fs::read(&path)
+                    .with_context(|| tr!("reading-file", path = path.display().to_string()))?,
+            })
+        })
+        .collect()
+}
+
+/// Contests of `group` open at `now`, the one closing first first.
+fn open_contests(
+    group: &libopenjudge::Group,
+    now: chrono::DateTime<chrono::FixedOffset>,
+) -> Vec<&libopenjudge::ProblemSetEntry> {
+    let mut contests = group
+        .probsets
+        .iter()
+        .filter(|probset| probset.end.is_some_and(|end| end > now))
+        .filter(|probset| probset.start.is_none_or(|start| start <= now))
+        .collect::<Vec<_>>();
+    contests.sort_by_key(|probset| probset.end);
+    contests
+}
+
+/// Shows the time remaining in the open contests of a group, or when the next
+/// one starts.
+pub async fn contest_status(session: &Session, group: &str) -> Result<()> {
+    let client = session.client().await?;
+    let group_info = spin(
+        tr!("fetching-group"),
+        libopenjudge::get_group_info(client, group),
+    )
+    .await?;
+    let now = chrono::Local::now().fixed_offset();
+    let contests = open_contests(&group_info, now);
+    if contests.is_empty() {
+        let next = group_info
+            .probsets
+            .iter()
+            .filter(|probset| probset.start.is_some_and(|start| start > now))
+            .min_by_key(|probset| probset.start);
+        match next.and_then(|probset| probset.start.map(|start| (probset, start))) {
+            Some((probset, start)) => println!(
+                "{}",
+                tr!(
+                    "contest-upcoming",
+                    name = probset.name.bold().to_string(),
+                    duration = format_duration(start - now).bold().to_string(),
+                    time = format_time(&start)
+                )
+            ),
+            None => println!("{}", tr!("no-contest", group = group.bold().to_string())),
+        }
+        return Ok(());
+    }
+    for contest in contests {
+        let Some(end) = contest.end else {
+            continue;
+        };
+        println!(
+            "{} {}",
+            Styled(contest),
+            tr!(
+                "contest-remaining",
+                duration = format_duration(end - now).yellow().bold().to_string(),
+                time = format_time(&end)
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Waits until `before` the end of the contest of `group` closing first, then
+/// rings the bell and sends a desktop notification. With `detach`, the
+/// notification is left to a background process instead.
+pub async fn contest_remind(
+    session: &Session,
+    group: &str,
+    before: &str,
+    detach: bool,
+) -> Result<()> {
+    let before = parse_duration(before)?;
+    let client = session.client().await?;
+    let group_info = spin(
+        tr!("fetching-group"),
+        libopenjudge::get_group_info(client, group),
+    )
+    .await?;
+    let now = chrono::Local::now().fixed_offset();
+    let contest = open_contests(&group_info, now)
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!(tr!("no-contest", group = group)))?;
+    let end = contest.end.unwrap_or(now);
+    let at = end - before;
+    let message = tr!(
+        "contest-ends-in",
+        name = contest.name.as_str(),
+        duration = format_duration(before)
+    );
+    if detach {
+        let delay = (at - now).num_seconds().max(0);
+        let notification = desktop_notification("OpenJudge", &message)
+            .ok_or_else(|| anyhow::anyhow!(tr!("no-desktop-notifications")))?;
+        schedule_command(delay, notification)?;
+        println!(
+            "{}",
+            tr!(
+                "notify-at",
+                time = format_time(&at.max(now)).bold().to_string()
+            )
+        );
+        return Ok(());
+    }
+    let spinner = Spinner::start("");
+    loop {
+        let remaining = at - chrono::Local::now().fixed_offset();
+        if remaining <= chrono::TimeDelta::zero() {
+            break;
+        }
+        spinner.set_message(tr!(
+            "reminding-in",
+            remaining = format_duration(remaining),
+            before = format_duration(before),
+            name = contest.name.as_str()
+        ));
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+    drop(spinner);
+    println!("\x07{}", message.yellow().bold());
+    if let Some(mut notification) = desktop_notification("OpenJudge", &message) {
+        let _ = notification.status();
+    }
+    Ok(())
+}
+
+/// Key of the `index`th problem of a contest in the manifest: A to Z, then
+/// AA, AB and so on.
+pub fn contest_key(index: usize) -> String {
+    let mut key = String::new();
+    let mut n = index + 1;
+    while n > 0 {
+        n -= 1;
+        key.insert(0, (b'A' + (n % 26) as u8) as char);
+        n /= 26;
+    }
+    key
+}
+
+/// Sets up `dir` for solving the problems of `probset`: a source file per
+/// problem named by its key, started from the template for `extension`, its
+/// samples as `tests/<key>/1.in` and `1.out`, and the problems mapped in
+/// `oj.yaml`. Existing source files are kept, and problems already in the
+/// manifest under other keys stay there.
+pub async fn contest_init(
+    session: &Session,
+    group: &str,
+    probset: &str,
+    extension: &str,
+    dir: &str,
+) -> Result<()> {
+    let dir = Path::new(dir);
+    let config = session.config();
+    let client = session.authenticated_client().await?;
+    let entries = spin(
+        tr!("fetching-problems"),
+        libopenjudge::problem_stream(client, group, probset, config.concurrency())
+            .collect::<Vec<_>>(),
+    )
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>>>()?;
+    if entries.is_empty() {
+        return Err(anyhow::anyhow!(tr!(
+            "probset-empty",
+            group = group,
+            probset = probset
+        )));
+    }
+    let urls = entries
+        .iter()
+        .map(|entry| entry.url.parse::<ProblemUrl>())
+        .collect::<Result<Vec<_>, _>>()?;
+    let problems = spin(
+        tr!("fetching-problem"),
+        libopenjudge::join_bounded(
+            urls.iter().map(|url| session.problem(url)),
+            config.concurrency(),
+        ),
+    )
+    .await;
+    fs::create_dir_all(dir)?;
+    let manifest_path = dir.join(MANIFEST_FILE_NAME);
+    let mut manifest = Manifest::read_manifest(&manifest_path)?.unwrap_or_default();
+    let template = read_template(extension);
+    let mut table = Table::new([
+        tr!("column-key"),
+        tr!("column-title"),
+        tr!("column-file"),
+        tr!("column-samples"),
+    ]);
+    for (index, ((entry, url), problem)) in entries.iter().zip(&urls).zip(problems).enumerate() {
+        let problem = problem?;
+        let key = contest_key(index);
+        let file = format!("{}.{}", key.to_lowercase(), extension);
+        let path = dir.join(&file);
+        let file_status = if path.exists() {
+            tr!("file-kept", file = file.as_str()).dimmed().to_string()
+        } else {
+            fs::write(
+                &path,
+                new_source(&url.to_string(), extension, template.as_deref()),
+            )?;
+            file.bold().to_string()
+        };
+        let samples = match (&problem.sample_input, &problem.sample_output) {
+            (Some(input), Some(output)) => {
+                let tests = dir.join("tests").join(&key);
+                fs::create_dir_all(&tests)?;
+                fs::write(
+                    tests.join("1.in"),
+                    render_html(input, RenderMode::Plain).await,
+                )?;
+                fs::write(
+                    tests.join("1.out"),
+                    render_html(output, RenderMode::Plain).await,
+                )?;
+                format!("tests/{}/", key)
+            }
+            _ => tr!("no-samples-short").dimmed().to_string(),
+        };
+        manifest.problems.insert(
+            key.clone(),
+            ManifestProblem {
+                url: url.to_string(),
+                file: Some(file),
+                lang: None,
+            },
+        );
+        table.push_row([
+            key.bold().to_string(),
+            entry.title.clone(),
+            file_status,
+            samples,
+        ]);
+    }
+    manifest.write_manifest(&manifest_path)?;
+    print!("{}", table);
+    println!(
+        "{}",
+        tr!(
+            "manifest-written",
+            count = entries.len(),
+            path = manifest_path.display().to_string().bold().to_string()
+        )
+    );
+    Ok(())
+}
+
+/// Command showing a desktop notification, if the platform has one.
+fn desktop_notification(title: &str, body: &str) -> Option<process::Command> {
+    if cfg!(target_os = "macos") {
+        let mut command = process::Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification {:?} with title {:?}",
+            body, title
+        ));
+        Some(command)
+    } else if cfg!(unix) {
+        let mut command = process::Command::new("notify-send");
+        command.arg(title).arg(body);
+        Some(command)
+    } else {
+        None
+    }
+}
+
+/// Runs `command` after `delay` seconds in a background process that outlives
+/// this one.
+fn schedule_command(delay: i64, command: process::Command) -> Result<()> {
+    let quoted = std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| format!("'{}'", arg.to_string_lossy().replace('\'', "'\\''")))
+        .collect::<Vec<_>>()
+        .join(" ");
+    process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("sleep {} && {}", delay, quoted))
+        .stdin(process::Stdio::null())
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+/// Warns before commands built on admin pages of OpenJudge that were never
+/// seen in a real session, and may not match what the server expects.
+fn warn_experimental() {
+    println!(
+        "{} {}",
+        tr!("warning").yellow().bold(),
+        tr!("experimental-command")
+    );
+}
+
+pub async fn list_join_requests(session: &Session, group: &str) -> Result<()> {
+    warn_experimental();
+    let client = session.authenticated_client().await?;
+    let requests = spin(
+        tr!("fetching-join-requests"),
+        libopenjudge::get_join_requests(client, group),
+    )
+    .await?;
+    if requests.is_empty() {
+        println!("{}", tr!("no-join-requests"));
+        return Ok(());
+    }
+    let mut table = Table::new([
+        "ID".to_string(),
+        tr!("column-user"),
+        tr!("column-message"),
+        tr!("column-time"),
+    ]);
+    for request in &requests {
+        table.push_row([
+            request.id.bold().to_string(),
+            request.user.clone(),
+            request.message.clone().unwrap_or_default(),
+            format_time(&request.time),
+        ]);
+    }
+    print!("{}", table);
+    Ok(())
+}
+
+/// Approves or rejects the given join requests, or all pending ones. Every
+/// request is tried even if some fail.
+pub async fn review_join_requests(
+    session: &Session,
+    group: &str,
+    ids: Vec<String>,
+    all: bool,
+    approve: bool,
+) -> Result<()> {
+    warn_experimental();
+    let client = session.authenticated_client().await?;
+    let ids = if all {
+        spin(
+            tr!("fetching-join-requests"),
+            libopenjudge::get_join_requests(client, group),
+        )
+        .await?
+        .into_iter()
+        .map(|request| request.id)
+        .collect()
+    } else {
+        ids
+    };
+    let done = if approve {
+        tr!("join-request-approved")
+    } else {
+        tr!("join-request-rejected")
+    };
+    let mut failed = 0;
+    for id in &ids {
+        let result = spin(
+            if approve {
+                tr!("approving-join-request", id = id.as_str())
+            } else {
+                tr!("rejecting-join-request", id = id.as_str())
+            },
+            libopenjudge::review_join_request(client, group, id, approve),
+        )
+        .await;
+        match result {
+            Ok(message) => println!(
+                "{} {}{}",
+                done.green().bold(),
+                id,
+                message.map_or(String::new(), |message| format!(": {}", message))
+            ),
+            Err(error) => {
+                failed += 1;
+                println!("{} {}: {}", tr!("failed").red().bold(), id, error);
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(anyhow::anyhow!(tr!(
+            "join-requests-failed",
+            failed = failed,
+            total = ids.len()
+        )));
+    }
+    Ok(())
+}
+
+pub async fn view_standings(
+    session: &Session,
+    group: &str,
+    probset: &str,
+    page: Option<u32>,
+) -> Result<()> {
+    let client = session.client().await?;
+    let standings = spin(
+        tr!("fetching-standings"),
+        libopenjudge::get_standings(client, group, probset, page),
+    )
+    .await?;
+    print!("{}", Styled(&standings));
+    Ok(())
+}
+
+/// Writes every page of the standings as CSV, to `output` or stdout.
+pub async fn export_standings(
+    session: &Session,
+    group: &str,
+    probset: &str,
+    output: Option<&str>,
+) -> Result<()> {
+    let client = session.client().await?;
+    let mut pages = pin!(libopenjudge::stream_pages(
+        |page| libopenjudge::get_standings(client, group, probset, (page > 1).then_some(page)),
+        |standings| standings.max_page,
+        session.config().concurrency(),
+    ));
+    let spinner = Spinner::start(tr!("fetching-standings"));
+    let (mut problems, mut rows) = (vec![], vec![]);
+    while let Some(page) = pages.next().await {
+        let page = page?;
+        spinner.set_message(format!(
+            "{}, {}",
+            tr!("fetching-standings"),
+            page_of(page.page, page.max_page)
+        ));
+        problems = page.problems;
+        rows.extend(page.rows);
+    }
+    drop(spinner);
+    let csv = standings_csv(&problems, &rows);
+    match output {
+        Some(path) => {
+            fs::write(path, csv).with_context(|| tr!("writing-file", path = path))?;
+            println!(
+                "{}",
+                tr!(
+                    "rows-exported",
+                    count = rows.len().to_string().bold().to_string(),
+                    path = path.blue().underline().to_string()
+                )
+            );
+        }
+        None => print!("{}", csv),
+    }
+    Ok(())
+}
+
+/// One line per user: rank, user, profile, one column per problem (1 if
+/// accepted, 0 otherwise), solved count, failed attempts and penalty.
+pub fn standings_csv(problems: &[String], rows: &[libopenjudge::StandingsRow]) -> String {
+    let mut csv = String::new();
+    let header = ["Rank", "User", "Profile"]
+        .into_iter()
+        .map(str::to_string)
+        .chain(problems.iter().cloned())
+        .chain(["Solved", "Failed Attempts", "Penalty"].map(str::to_string));
+    push_csv_record(&mut csv, header);
+    for row in rows {
+        let results = (0..problems.len()).map(|i| {
+            let accepted = row
+                .cells
+                .get(i)
+                .and_then(Option::as_ref)
+                .is_some_and(|cell| cell.accepted);
+            u8::from(accepted).to_string()
+        });
+        let failed_attempts = row
+            .cells
+            .iter()
+            .flatten()
+            .map(|cell| cell.failed_attempts)
+            .sum::<u32>();
+        let record = [
+            row.rank.to_string(),
+            row.user.clone(),
+            row.user_url.clone().unwrap_or_default(),
+        ]
+        .into_iter()
+        .chain(results)
+        .chain([
+            row.solved.to_string(),
+            failed_attempts.to_string(),
+            row.penalty.clone().unwrap_or_default(),
+        ]);
+        push_csv_record(&mut csv, record);
+    }
+    csv
+}
+
+/// Appends a CSV line, quoting fields with separators, quotes or line breaks.
+fn push_csv_record(csv: &mut String, fields: impl Iterator<Item = String>) {
+    let fields = fields
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<_>>();
+    csv.push_str(&fields.join(","));
+    csv.push_str("\r\n");
+}
+
+pub async fn list_probsets(session: &Session, group: &str, interactive: bool) -> Result<()> {
+    let group_id = group;
+    let client = session.client().await?;
+    let group = spin(
+        tr!("fetching-probsets"),
+        libopenjudge::get_group_info(client, group),
+    )
+    .await?;
+    if !interactive || group.probsets.is_empty() {
+        println!("{}", Styled(&group));
+        return Ok(());
+    }
+    let mut prompt = String::new();
+    writeln!(prompt, "{}", &group.name.bold())?;
+    writeln!(prompt, "{}", &group.url.blue().underline())?;
+    writeln!(prompt, "{}", &group.description)?;
+    let selection = select_within(
+        &prompt,
+        &group.probsets.iter().map(Styled).collect::<Vec<_>>(),
+        &StatusBar {
+            group: Some(group_id.to_string()),
+            ..session.status_bar()
+        },
+    );
+    match selection {
+        Selection::Selected(i) => {
+            let selected_probset = &group.probsets[i];
+            list_problems(
+                session,
+                group_id,
+                strip_slashes(url::Url::parse(&selected_probset.url)?.path()),
+                None,
                 true,
                 interactive,
             )
             .await
         }
+        _ => Ok(()),
+    }
+}
+
+pub async fn list_problems(
+    session: &Session,
+    group: &str,
+    probset: &str,
+    page: Option<u32>,
+    show_status: bool,
+    interactive: bool,
+) -> Result<()> {
+    let client = if show_status {
+        session.authenticated_client().await?
+    } else {
+        session.client().await?
+    };
+    let problems = spin(
+        tr!("fetching-problems"),
+        libopenjudge::get_partial_probset_info(client, group, probset, page),
+    )
+    .await?;
+    if !interactive {
+        println!("{}", Styled(&problems));
+        return Ok(());
+    }
+    let mut prompt = String::new();
+    writeln!(prompt, "{}/{}", problems.group_name, problems.name.bold())?;
+    writeln!(prompt, "{}", problems.url.blue().underline())?;
+    if problems.max_page != 1 {
+        writeln!(prompt, "{}\n", page_of(problems.page, problems.max_page))?;
+    };
+    let options = problems.problems.iter().map(Styled).collect::<Vec<_>>();
+    let mut previews = ProblemPreviews::new(session).await?;
+    let selection = interactions::select_with_preview(
+        &prompt,
+        &options,
+        &StatusBar {
+            group: Some(group.to_string()),
+            probset: Some(probset.to_string()),
+            page: Some((problems.page, problems.max_page)),
+            ..session.status_bar()
+        },
+        &mut |i| previews.get(&problems.problems[i].url),
+    );
+    let page = match selection {
+        Selection::Selected(i) => {
+            return Box::pin(view_problem(session, &problems.problems[i].url)).await;
+        }
+        Selection::NextPage => problems.page + 1,
+        Selection::PrevPage => problems.page - 1,
+        Selection::Cancelled => return Ok(()),
+    };
+    Box::pin(list_problems(
+        session,
+        group,
+        probset,
+        Some(page),
+        show_status,
+        interactive,
+    ))
+    .await
+}
+
+/// Writes the samples of problems on a probset page to `dir`, all of them or,
+/// in interactive mode, those picked by the user.
+pub async fn save_problem_samples(
+    session: &Session,
+    group: &str,
+    probset: &str,
+    page: Option<u32>,
+    dir: &str,
+    interactive: bool,
+) -> Result<()> {
+    let client = session.client().await?;
+    let problems = spin(
+        tr!("fetching-problems"),
+        libopenjudge::get_partial_probset_info(client, group, probset, page),
+    )
+    .await?;
+    let chosen = if interactive {
+        let options = problems.problems.iter().map(Styled).collect::<Vec<_>>();
+        let prompt = format!(
+            "{}/{}\n{}",
+            problems.group_name,
+            problems.name.bold(),
+            tr!("select-samples")
+        );
+        let status = StatusBar {
+            group: Some(group.to_string()),
+            probset: Some(probset.to_string()),
+            page: Some((problems.page, problems.max_page)),
+            ..session.status_bar()
+        };
+        let selection = interactions::select_many_within(&prompt, &options, &status);
+        match selection {
+            Selection::Selected(indices) => {
+                indices.into_iter().map(|i| &problems.problems[i]).collect()
+            }
+            Selection::NextPage | Selection::PrevPage => {
+                let page = if matches!(selection, Selection::NextPage) {
+                    problems.page + 1
+                } else {
+                    problems.page - 1
+                };
+                return Box::pin(save_problem_samples(
+                    session,
+                    group,
+                    probset,
+                    Some(page),
+                    dir,
+                    interactive,
+                ))
+                .await;
+            }
+            Selection::Cancelled => {
+                println!("{}", tr!("no-problem-selected"));
+                return Ok(());
+            }
+        }
+    } else {
+        problems.problems.iter().collect::<Vec<_>>()
+    };
+    let urls = chosen
+        .iter()
+        .map(|entry| entry.url.parse::<ProblemUrl>())
+        .collect::<Result<Vec<_>, _>>()?;
+    let fetched = spin(
+        tr!("fetching-problem"),
+        libopenjudge::join_bounded(
+            urls.iter().map(|url| session.problem(url)),
+            session.config().concurrency(),
+        ),
+    )
+    .await;
+    fs::create_dir_all(dir)?;
+    for (entry, problem) in chosen.iter().zip(fetched) {
+        let problem = problem?;
+        let (Some(input), Some(output)) = (&problem.sample_input, &problem.sample_output) else {
+            println!(
+                "{}",
+                tr!(
+                    "samples-skipped",
+                    number = entry.problem_number.as_str(),
+                    title = entry.title.as_str()
+                )
+            );
+            continue;
+        };
+        let base = std::path::Path::new(dir).join(&entry.problem_number);
+        fs::write(
+            base.with_extension("in"),
+            render_html(input, RenderMode::Plain).await,
+        )?;
+        fs::write(
+            base.with_extension("out"),
+            render_html(output, RenderMode::Plain).await,
+        )?;
+        println!(
+            "{}",
+            tr!(
+                "samples-saved",
+                number = entry.problem_number.as_str(),
+                title = entry.title.as_str(),
+                path = base.with_extension("{in,out}").display().to_string()
+            )
+        );
     }
+    Ok(())
 }
 
-pub async fn list_problems(
+pub async fn list_all_problems(
+    session: &Session,
     group: &str,
     probset: &str,
-    page: Option<u32>,
     show_status: bool,
-    interactive: bool,
 ) -> Result<()> {
-    println!("Fetching problems...");
-    let client = libopenjudge::create_client().await?;
-    if show_status {
-        let config = AppConfig::read_config(get_config_dir())?;
-        let (email, password) = ensure_account(&config)?;
-        libopenjudge::login(&client, email, &password).await?;
-    }
-    let problems = libopenjudge::get_partial_probset_info(&client, group, probset, page).await?;
-    if !interactive {
-        println!("{}", problems);
+    let client = if show_status {
+        session.authenticated_client().await?
+    } else {
+        session.client().await?
+    };
+    let mut problems = pin!(libopenjudge::problem_stream(
+        client,
+        group,
+        probset,
+        session.config().concurrency()
+    ));
+    let mut count = 0;
+    let spinner = Spinner::start(tr!("fetching-problems"));
+    while let Some(problem) = problems.next().await {
+        spinner.println(Styled(&problem?).to_string());
+        count += 1;
+        spinner.set_message(tr!("fetching-problems-so-far", count = count));
+    }
+    drop(spinner);
+    println!(
+        "{}",
+        tr!(
+            "found-problems",
+            count = count.to_string().bold().to_string()
+        )
+    );
+    Ok(())
+}
+
+/// Shows today's practice problem of `group`, an unsolved problem of
+/// `probset` picked once a day, and updates whether past ones got solved.
+pub async fn daily_problem(session: &Session, group: Option<&str>, probset: &str) -> Result<()> {
+    let config = session.config();
+    let group = match group {
+        Some(group) => group.to_string(),
+        None => ensure_last_problem(".", &config)?.group().to_string(),
+    };
+    let log_path = get_config_root().join("daily.json");
+    let mut log = load_json::<DailyLog>(&log_path)?;
+    let client = session.authenticated_client().await?;
+    let problems = spin(
+        tr!("fetching-problems"),
+        libopenjudge::problem_stream(client, &group, probset, config.concurrency())
+            .collect::<Vec<_>>(),
+    )
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>>>()?;
+    let parsed_url = |url: &str| url.parse::<ProblemUrl>().ok();
+    for entry in &mut log.entries {
+        let url = parsed_url(&entry.url);
+        if let Some(problem) = problems
+            .iter()
+            .find(|problem| parsed_url(&problem.url) == url)
+        {
+            entry.solved = problem.solved == Some(true);
+        }
+    }
+
+    let today = chrono::Local::now().date_naive();
+    let date = today.to_string();
+    if log.get(&date).is_none() {
+        let picked = log
+            .entries
+            .iter()
+            .filter_map(|entry| parsed_url(&entry.url))
+            .collect::<Vec<_>>();
+        let candidates = problems
+            .iter()
+            .filter(|problem| problem.solved != Some(true))
+            .filter(|problem| parsed_url(&problem.url).is_none_or(|url| !picked.contains(&url)))
+            .collect::<Vec<_>>();
+        let weights = candidates
+            .iter()
+            .map(|problem| daily::Candidate {
+                accepted: problem.accepted_population,
+                submitters: problem.submitters,
+            })
+            .collect::<Vec<_>>();
+        let seed = chrono::Datelike::num_days_from_ce(&today) as u64;
+        let index = daily::pick(&weights, log.target_rate(), seed).ok_or_else(|| {
+            anyhow::anyhow!(tr!("no-unsolved-problem", group = group, probset = probset))
+        })?;
+        let problem = candidates[index];
+        log.entries.push(DailyEntry {
+            date: date.clone(),
+            url: problem.url.clone(),
+            title: problem.title.clone(),
+            solved: false,
+        });
+    }
+    save_json(&log_path, &log)?;
+
+    let entry = log.get(&date).expect("Today's problem was just picked.");
+    let recent = log.recent();
+    println!(
+        "{} {}{}",
+        tr!("daily-problem", date = date.as_str()).bold(),
+        entry.title.blue().underline(),
+        if entry.solved {
+            format!(" ({})", tr!("problem-solved")).green().to_string()
+        } else {
+            String::new()
+        }
+    );
+    let solved = recent.iter().filter(|entry| entry.solved).count();
+    println!(
+        "{}",
+        tr!(
+            "daily-solved",
+            solved = solved.to_string().bold().to_string(),
+            total = recent.len()
+        )
+    );
+    let url = entry.url.parse::<ProblemUrl>()?;
+    let problem = spin(
+        tr!("fetching-problem"),
+        libopenjudge::get_problem(client, &url),
+    )
+    .await?;
+    session.record_view(&url, &problem)?;
+    print_problem(session, &url, problem, None).await?;
+    session.remember_problem(&url)?;
+    Ok(())
+}
+
+/// Ranks the configured friends, and the logged in user, by the problems of
+/// `probset` solved according to their home pages.
+pub async fn leaderboard(session: &Session, group: &str, probset: &str) -> Result<()> {
+    let config = session.config();
+    let mut players = config.friends.clone().unwrap_or_default();
+    if players.is_empty() {
+        return Err(anyhow::anyhow!(tr!("no-friends")));
+    }
+    let me = if config.user_email.is_some() {
+        let client = session.authenticated_client().await?;
+        let user = spin(tr!("fetching-user"), libopenjudge::get_user_info(client)).await?;
+        players.retain(|friend| friend.id != user.id);
+        players.push(Friend {
+            id: user.id.clone(),
+            name: Some(user.username),
+        });
+        Some(user.id)
+    } else {
+        None
+    };
+    let client = session.client().await?;
+    let pages = spin(
+        tr!("fetching-users", count = players.len()),
+        libopenjudge::join_bounded(
+            players
+                .iter()
+                .map(|friend| libopenjudge::get_user_problems(client, &friend.id)),
+            session.config().concurrency(),
+        ),
+    )
+    .await;
+    let mut rows = vec![];
+    for (friend, problems) in players.iter().zip(pages) {
+        let problems =
+            problems.with_context(|| tr!("fetching-user-id", id = friend.id.as_str()))?;
+        let (solved, attempted) = problems
+            .iter()
+            .filter(|entry| {
+                entry
+                    .problem
+                    .as_ref()
+                    .is_some_and(|problem| problem.group() == group && problem.probset() == probset)
+            })
+            .fold((0, 0), |(solved, attempted), entry| {
+                if entry.solved {
+                    (solved + 1, attempted)
+                } else {
+                    (solved, attempted + 1)
+                }
+            });
+        rows.push((friend, solved, attempted));
+    }
+    // most solved first, fewer unsolved attempts breaking ties
+    rows.sort_by_key(|(_, solved, attempted)| (std::cmp::Reverse(*solved), *attempted));
+    let mut table = Table::new([
+        tr!("column-rank"),
+        tr!("column-user"),
+        tr!("column-solved"),
+        tr!("column-attempted"),
+    ]);
+    let mut rank = 0;
+    let mut previous = None;
+    for (i, (friend, solved, attempted)) in rows.iter().enumerate() {
+        if previous != Some((solved, attempted)) {
+            rank = i + 1;
+            previous = Some((solved, attempted));
+        }
+        let name = friend.name.clone().unwrap_or_else(|| friend.id.clone());
+        let name = if me.as_ref() == Some(&friend.id) {
+            tr!("you", name = name).yellow().bold().to_string()
+        } else {
+            name.bold().to_string()
+        };
+        table.push_row([
+            rank.to_string(),
+            name,
+            solved.to_string(),
+            attempted.to_string(),
+        ]);
+    }
+    print!("{}", table);
+    Ok(())
+}
+
+/// Measures each stage of reaching openjudge.cn, or the configured hosts, and
+/// a group subdomain, the group of the last problem unless given, and tells
+/// which one is slow.
+pub async fn doctor_net(session: &Session, group: Option<&str>) -> Result<()> {
+    let config = session.config();
+    let group = match group {
+        Some(group) => Some(group.to_string()),
+        None => config
+            .recent_problems()
+            .first()
+            .and_then(|url| url.parse::<ProblemUrl>().ok())
+            .map(|url| url.group().to_string()),
+    };
+    let bases = config
+        .hosts
+        .clone()
+        .unwrap_or_else(|| vec![libopenjudge::ROOT_HOST.to_string()]);
+    let hosts = bases
+        .iter()
+        .flat_map(|base| {
+            std::iter::once(base.clone())
+                .chain(group.as_ref().map(|group| format!("{}.{}", group, base)))
+        })
+        .collect::<Vec<_>>();
+    let (scheme, user_agent) = (config.scheme(), config.user_agent());
+    let reports = spin(
+        tr!("measuring-network"),
+        futures::future::join_all(
+            hosts
+                .iter()
+                .map(|host| doctor::check_host(host, scheme, &user_agent)),
+        ),
+    )
+    .await;
+    let timing = |timing: &Option<Result<std::time::Duration>>| match timing {
+        None => "-".dimmed().to_string(),
+        Some(Ok(duration)) => format!("{} ms", duration.as_millis()),
+        Some(Err(_)) => tr!("failed").red().bold().to_string(),
+    };
+    let mut table = Table::new([
+        tr!("column-host"),
+        tr!("column-address"),
+        "DNS".to_string(),
+        tr!("column-connect"),
+        tr!("column-fetch"),
+    ])
+    .align_right(2)
+    .align_right(3)
+    .align_right(4);
+    for report in &reports {
+        table.push_row([
+            report.host.clone(),
+            report
+                .address
+                .map(|address| address.ip().to_string())
+                .unwrap_or_default(),
+            timing(&report.dns),
+            timing(&report.connect),
+            timing(&report.fetch),
+        ]);
+    }
+    print!("{}", table);
+    for report in &reports {
+        let errors = [&report.dns, &report.connect, &report.fetch]
+            .into_iter()
+            .filter_map(|timing| timing.as_ref()?.as_ref().err());
+        for error in errors {
+            println!("{} {}: {:#}", tr!("error").red().bold(), report.host, error);
+        }
+        match report.problem() {
+            Some(stage) => println!(
+                "{} {}",
+                tr!("warning").yellow().bold(),
+                tr!(
+                    "stage-slow",
+                    stage = stage.name(),
+                    host = report.host.bold().to_string()
+                )
+            ),
+            None => println!(
+                "{}",
+                tr!("host-fine", host = report.host.bold().to_string())
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Saves the config, logs and caches into a zip archive.
+pub fn backup_state(file: &str) -> Result<()> {
+    let count = backup::backup(&get_config_root(), std::path::Path::new(file))?;
+    println!(
+        "{}",
+        tr!(
+            "backed-up",
+            count = count.to_string().bold().to_string(),
+            file = file.blue().underline().to_string()
+        )
+    );
+    println!("{}", tr!("backup-keyring-note"));
+    Ok(())
+}
+
+/// Restores the config, logs and caches from an archive made by `oj backup`.
+pub fn restore_state(file: &str, force: bool) -> Result<()> {
+    let count = backup::restore(std::path::Path::new(file), &get_config_root(), force)?;
+    println!(
+        "{}",
+        tr!(
+            "restored",
+            count = count.to_string().bold().to_string(),
+            file = file.blue().underline().to_string()
+        )
+    );
+    Ok(())
+}
+
+/// Saves `url`, which may itself be "." or an alias, as `@name`.
+pub fn alias_set(session: &Session, name: &str, url: &str) -> Result<()> {
+    let name = parse_alias_name(name)?;
+    let url = ensure_last_problem(url, &session.config())?.to_string();
+    println!("{} {}", format!("@{}", name).bold(), url.blue().underline());
+    session.update_config(|config| {
+        config
+            .aliases
+            .get_or_insert_default()
+            .insert(name.to_string(), url);
+    })
+}
+
+pub fn alias_remove(session: &Session, name: &str) -> Result<()> {
+    let name = parse_alias_name(name)?;
+    if !session
+        .config()
+        .aliases
+        .is_some_and(|aliases| aliases.contains_key(name))
+    {
+        return Err(anyhow::anyhow!(tr!("alias-not-in-config", name = name)));
+    }
+    session.update_config(|config| {
+        if let Some(aliases) = &mut config.aliases {
+            aliases.remove(name);
+        }
+    })?;
+    println!(
+        "{}",
+        tr!(
+            "alias-removed",
+            name = format!("@{}", name).bold().to_string()
+        )
+    );
+    Ok(())
+}
+
+/// Lists the aliases of the config and of `oj.yaml`, the latter shadowing the
+/// former.
+pub fn alias_list(session: &Session) -> Result<()> {
+    let config = session.config();
+    let manifest = Manifest::read_current()?.unwrap_or_default();
+    let mut aliases = BTreeMap::new();
+    for (name, url) in config.aliases.iter().flatten() {
+        aliases.insert(name, (url, tr!("alias-source-config")));
+    }
+    for (name, url) in &manifest.aliases {
+        aliases.insert(name, (url, MANIFEST_FILE_NAME.to_string()));
+    }
+    if aliases.is_empty() {
+        println!("{}", tr!("no-aliases"));
         return Ok(());
     }
-    let mut prompt = String::new();
-    writeln!(prompt, "{}/{}", problems.group_name, problems.name.bold())?;
-    writeln!(prompt, "{}", problems.url.blue().underline())?;
-    if problems.max_page != 1 {
-        writeln!(
-            prompt,
-            "Displaying page {} of {}\n",
-            problems.page.to_string().bold(),
-            problems.max_page.to_string().bold()
-        )?;
+    let mut table = Table::new([
+        tr!("column-alias"),
+        "URL".to_string(),
+        tr!("column-defined-in"),
+    ]);
+    for (name, (url, source)) in aliases {
+        table.push_row([
+            format!("@{}", name).bold().to_string(),
+            url.blue().underline().to_string(),
+            source,
+        ]);
+    }
+    print!("{}", table);
+    Ok(())
+}
+
+/// Lists the recently operated problems with the references addressing them.
+pub fn recent(session: &Session) -> Result<()> {
+    let recent = session.config().recent_problems();
+    if recent.is_empty() {
+        println!("{}", tr!("no-recent-problems"));
+        return Ok(());
+    }
+    let log = load_json::<SolveLog>(get_config_root().join("solves.json"))?;
+    let mut table = Table::new([
+        tr!("column-reference"),
+        tr!("column-title"),
+        "URL".to_string(),
+    ]);
+    for (i, url) in recent.iter().enumerate() {
+        let title = log
+            .problems
+            .get(url)
+            .and_then(|record| record.title.clone())
+            .unwrap_or_default();
+        table.push_row([
+            if i == 0 {
+                ".".to_string()
+            } else {
+                format!("~{}", i)
+            }
+            .bold()
+            .to_string(),
+            title,
+            url.blue().underline().to_string(),
+        ]);
+    }
+    print!("{}", table);
+    Ok(())
+}
+
+/// Lists the latest commands run through the CLI, newest first and numbered
+/// for `--rerun`.
+pub fn history(limit: usize) -> Result<()> {
+    let log = load_json::<ActivityLog>(get_config_root().join("activity.json"))?;
+    if log.entries.is_empty() {
+        println!("{}", tr!("no-history"));
+        return Ok(());
+    }
+    let mut table = Table::new([
+        "#".to_string(),
+        tr!("column-time"),
+        tr!("column-command"),
+        tr!("column-problem"),
+        tr!("column-result"),
+    ]);
+    for (i, activity) in log.entries.iter().rev().take(limit).enumerate() {
+        table.push_row([
+            (i + 1).to_string(),
+            format_time(&activity.time),
+            format!("oj {}", activity.args.join(" ")).bold().to_string(),
+            activity
+                .target
+                .as_deref()
+                .unwrap_or_default()
+                .blue()
+                .underline()
+                .to_string(),
+            match &activity.error {
+                None => tr!("command-ok").green().to_string(),
+                Some(error) => error.lines().next().unwrap_or_default().red().to_string(),
+            },
+        ]);
+    }
+    print!("{}", table);
+    Ok(())
+}
+
+/// Runs the `n`th latest command of the history again, from the directory it
+/// was first run in.
+pub fn rerun(n: usize) -> Result<()> {
+    let log = load_json::<ActivityLog>(get_config_root().join("activity.json"))?;
+    let activity = log
+        .nth_latest(n)
+        .ok_or_else(|| anyhow::anyhow!(tr!("no-such-command", n = n)))?;
+    let command_line = format!("oj {}", activity.args.join(" "));
+    println!("{} {}", tr!("running").dimmed(), command_line.dimmed());
+    let mut command = process::Command::new(std::env::current_exe()?);
+    command.args(&activity.args);
+    if let Some(dir) = &activity.dir
+        && std::path::Path::new(dir).is_dir()
+    {
+        command.current_dir(dir);
+    }
+    let status = command.status()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(tr!(
+            "command-failed",
+            command = command_line,
+            code = status.code().unwrap_or_default()
+        )));
+    }
+    Ok(())
+}
+
+/// Lists the problems viewed or solved through the CLI, with the time from
+/// the first view to the first accepted submission.
+pub fn problem_history(limit: usize) -> Result<()> {
+    let log = load_json::<SolveLog>(get_config_root().join("solves.json"))?;
+    if log.problems.is_empty() {
+        println!("{}", tr!("no-problems-viewed"));
+        return Ok(());
+    }
+    let mut records = log.problems.iter().collect::<Vec<_>>();
+    records.sort_by_key(|(_, record)| std::cmp::Reverse(record.last_activity()));
+    let mut table = Table::new([
+        tr!("column-problem"),
+        tr!("column-viewed"),
+        tr!("column-accepted"),
+        tr!("column-time-to-solve"),
+        tr!("label-submissions"),
+        "URL".to_string(),
+    ]);
+    let time = |time: Option<chrono::DateTime<chrono::FixedOffset>>| {
+        time.as_ref().map(format_time).unwrap_or_default()
     };
-    let mut options = problems
+    for (url, record) in records.into_iter().take(limit) {
+        table.push_row([
+            record.title.clone().unwrap_or_default().bold().to_string(),
+            time(record.first_viewed),
+            time(record.first_accepted),
+            record
+                .time_to_solve()
+                .map(format_duration)
+                .unwrap_or_default(),
+            record.submissions.to_string(),
+            url.blue().underline().to_string(),
+        ]);
+    }
+    print!("{}", table);
+    Ok(())
+}
+
+/// Most weeks in the heatmap of `oj stats`, a year like GitHub's. Fewer are
+/// shown in narrow terminals.
+const HEATMAP_WEEKS: u32 = 53;
+
+/// Summarizes the solve times recorded by the CLI, with the streaks of days
+/// with an Accepted and their heatmap.
+pub fn stats() -> Result<()> {
+    let log = load_json::<SolveLog>(get_config_root().join("solves.json"))?;
+    let solved = log
         .problems
+        .values()
+        .filter(|record| record.first_accepted.is_some())
+        .count();
+    println!(
+        "{}",
+        tr!(
+            "stats-viewed",
+            viewed = log.problems.len().to_string().bold().to_string(),
+            solved = solved.to_string().bold().to_string()
+        )
+    );
+    let times = log.solve_times();
+    if let (Some(fastest), Some(slowest)) = (times.first(), times.last()) {
+        let total = times.iter().sum::<chrono::TimeDelta>();
+        println!(
+            "{}",
+            tr!(
+                "stats-times",
+                median = format_duration(times[times.len() / 2]).bold().to_string(),
+                average = format_duration(total / times.len() as i32)
+                    .bold()
+                    .to_string(),
+                fastest = format_duration(*fastest).bold().to_string(),
+                slowest = format_duration(*slowest).bold().to_string()
+            )
+        );
+    }
+    let days = log.accepted_per_day();
+    if days.is_empty() {
+        return Ok(());
+    }
+    let today = chrono::Local::now().date_naive();
+    let (current, longest) = streaks(&days, today);
+    println!(
+        "{}",
+        tr!(
+            "stats-streaks",
+            current = current.to_string().bold().to_string(),
+            longest = longest.to_string().bold().to_string()
+        )
+    );
+    if !screen_reader() {
+        let width = crossterm::terminal::size().map_or(80, |(columns, _)| columns as u32);
+        let weeks = (width.saturating_sub(4) / 2).clamp(1, HEATMAP_WEEKS);
+        println!("\n{}", heatmap(&days, today, weeks));
+    }
+    Ok(())
+}
+
+/// Verdict columns of `oj stats breakdown`, rarer verdicts are counted under
+/// the last one.
+pub const BREAKDOWN_VERDICTS: [&str; 7] = ["AC", "WA", "TLE", "MLE", "RE", "CE", "Other"];
+
+fn breakdown_column(result: &SubmissionResult) -> usize {
+    match result {
+        SubmissionResult::Accepted => 0,
+        SubmissionResult::WrongAnswer => 1,
+        SubmissionResult::TimeLimitExceeded => 2,
+        SubmissionResult::MemoryLimitExceeded => 3,
+        SubmissionResult::RuntimeError => 4,
+        SubmissionResult::CompileError { .. } => 5,
+        _ => 6,
+    }
+}
+
+/// Counts the verdicts of `submissions` by key, such as a language, in the
+/// order of [`BREAKDOWN_VERDICTS`].
+pub fn verdict_breakdown<'a, I>(submissions: I) -> BTreeMap<String, [usize; 7]>
+where
+    I: IntoIterator<Item = (&'a str, &'a SubmissionResult)>,
+{
+    let mut breakdown = BTreeMap::<String, [usize; 7]>::new();
+    for (key, result) in submissions {
+        breakdown.entry(key.to_string()).or_default()[breakdown_column(result)] += 1;
+    }
+    breakdown
+}
+
+fn print_breakdown(heading: &str, breakdown: &BTreeMap<String, [usize; 7]>) {
+    // verdicts are the same abbreviations in every language, "Other" is not
+    let verdicts = BREAKDOWN_VERDICTS.map(|verdict| match verdict {
+        "Other" => tr!("column-other"),
+        verdict => verdict.to_string(),
+    });
+    let mut table = Table::new(
+        [heading.to_string(), tr!("column-total")]
+            .into_iter()
+            .chain(verdicts),
+    );
+    for column in 1..=BREAKDOWN_VERDICTS.len() + 1 {
+        table = table.align_right(column);
+    }
+    for (key, counts) in breakdown {
+        let total = counts.iter().sum::<usize>();
+        let cells = counts.iter().map(|&count| match count {
+            0 => "-".dimmed().to_string(),
+            count => format!("{} ({}%)", count, count * 100 / total),
+        });
+        table.push_row(
+            [key.bold().to_string(), total.to_string()]
+                .into_iter()
+                .chain(cells),
+        );
+    }
+    print!("{}", table);
+}
+
+/// Tables of verdict counts per language and per problem set, over the
+/// problems on the user's home page and those submitted to through the CLI.
+pub async fn stats_breakdown(session: &Session) -> Result<()> {
+    let client = session.authenticated_client().await?;
+    let user = spin(tr!("fetching-user"), libopenjudge::get_user_info(client)).await?;
+    let mut problems = spin(
+        tr!("fetching-problems"),
+        libopenjudge::get_user_problems(client, &user.id),
+    )
+    .await?
+    .into_iter()
+    .filter_map(|entry| entry.problem)
+    .map(|problem| (problem.to_string(), problem))
+    .collect::<BTreeMap<_, _>>();
+    let log = load_json::<SolveLog>(get_config_root().join("solves.json"))?;
+    for (url, record) in &log.problems {
+        if record.submissions > 0
+            && let Ok(problem) = url.parse::<ProblemUrl>()
+        {
+            problems.entry(url.clone()).or_insert(problem);
+        }
+    }
+    let histories = spin(
+        tr!("fetching-submissions"),
+        libopenjudge::join_bounded(
+            problems
+                .values()
+                .map(|problem| libopenjudge::list_submissions(client, problem)),
+            session.config().concurrency(),
+        ),
+    )
+    .await;
+    let mut entries = vec![];
+    for (problem, history) in problems.values().zip(histories) {
+        match history {
+            Ok(history) => entries.extend(
+                history
+                    .into_iter()
+                    .filter(|entry| entry.result != SubmissionResult::Waiting)
+                    .map(|entry| (problem, entry)),
+            ),
+            Err(e) => tracing::warn!("Skipping {}: {:#}", problem, e),
+        }
+    }
+    if entries.is_empty() {
+        println!("{}", tr!("no-submissions"));
+        return Ok(());
+    }
+    // the language is only shown on the page of each submission
+    let details = spin(
+        tr!("fetching-submission-count", count = entries.len()),
+        libopenjudge::join_bounded(
+            entries
+                .iter()
+                .map(|(_, entry)| libopenjudge::query_submission_result(client, &entry.url)),
+            session.config().concurrency(),
+        ),
+    )
+    .await;
+    let mut by_lang = vec![];
+    for ((_, entry), details) in entries.iter().zip(details) {
+        match details {
+            Ok(submission) => by_lang.push((submission.lang, &entry.result)),
+            Err(e) => tracing::warn!("Skipping {}: {:#}", entry.url, e),
+        }
+    }
+    let by_probset = entries
         .iter()
-        .map(|x| x.to_string())
+        .map(|(problem, entry)| {
+            (
+                format!("{}/{}", problem.group(), problem.probset()),
+                &entry.result,
+            )
+        })
         .collect::<Vec<_>>();
-    if problems.page > 1 {
-        options.push("Prev Page".to_owned());
-    }
-    if problems.page < problems.max_page {
-        options.push("Next Page".to_owned());
-    }
-    let selected_index = select_within(&prompt, &options, 2, 3);
-    match selected_index {
-        None => Ok(()),
-        Some(i) => match options[i].as_str() {
-            "Next Page" => {
-                Box::pin(list_problems(
-                    group,
-                    probset,
-                    Some(problems.page + 1),
-                    show_status,
-                    interactive,
-                ))
-                .await
-            }
-            "Prev Page" => {
-                Box::pin(list_problems(
-                    group,
-                    probset,
-                    Some(problems.page - 1),
-                    show_status,
-                    interactive,
-                ))
-                .await
+    println!(
+        "{}",
+        tr!(
+            "breakdown-summary",
+            submissions = entries.len().to_string().bold().to_string(),
+            problems = problems.len().to_string().bold().to_string()
+        )
+    );
+    print_breakdown(
+        &tr!("column-language"),
+        &verdict_breakdown(
+            by_lang
+                .iter()
+                .map(|(lang, result)| (lang.as_str(), *result)),
+        ),
+    );
+    print_breakdown(
+        &tr!("column-probset"),
+        &verdict_breakdown(
+            by_probset
+                .iter()
+                .map(|(probset, result)| (probset.as_str(), *result)),
+        ),
+    );
+    Ok(())
+}
+
+/// Opens the full-screen browser on `group`, or on the group of the last
+/// operated problem.
+pub async fn browse(session: &Session, group: Option<&str>) -> Result<()> {
+    let group = match group {
+        Some(group) => group.to_string(),
+        None => ensure_last_problem(".", &session.config())?
+            .group()
+            .to_string(),
+    };
+    tui::run(session, &group).await
+}
+
+/// Settings changed by `oj config`, `None` leaves a setting untouched.
+#[derive(Default)]
+pub struct ConfigChanges {
+    pub graphics: Option<String>,
+    pub https: Option<bool>,
+    pub user_agent: Option<String>,
+    pub theme: Option<String>,
+    /// Largest sixel image size, as `WIDTHxHEIGHT` in pixels.
+    pub sixel_max_size: Option<String>,
+    pub sixel_diffusion: Option<String>,
+    pub image_max_rows: Option<u32>,
+    pub time_format: Option<String>,
+    pub relative_time: Option<bool>,
+    pub verdicts: Option<String>,
+    pub inline_select: Option<bool>,
+    /// C++ standard for local compiles, e.g. `c++17`.
+    pub cpp_std: Option<String>,
+    /// Comma separated warning flags failing local tests.
+    pub blocking_warnings: Option<String>,
+    /// Python interpreter, or a virtualenv directory, for local tests.
+    pub python: Option<String>,
+    /// Command opening files for `oj edit`.
+    pub editor: Option<String>,
+    pub archive_dir: Option<String>,
+    /// Friends to add, as `ID` or `ID=NAME`.
+    pub add_friends: Vec<String>,
+    /// IDs of friends to remove.
+    pub remove_friends: Vec<String>,
+    /// Pre-submit hooks to add.
+    pub add_pre_submit_hooks: Vec<String>,
+    pub clear_pre_submit_hooks: bool,
+    pub sanity_checks: Option<bool>,
+    pub bell: Option<bool>,
+    pub sound_command: Option<String>,
+    pub lang: Option<String>,
+    pub screen_reader: Option<bool>,
+    /// Comma separated base hosts.
+    pub hosts: Option<String>,
+    pub concurrency: Option<usize>,
+    pub pool_size: Option<usize>,
+    /// Seconds idle connections are kept.
+    pub keep_alive: Option<u64>,
+}
+
+pub fn configure(session: &Session, changes: ConfigChanges) -> Result<()> {
+    let ConfigChanges {
+        graphics,
+        https,
+        user_agent,
+        theme,
+        sixel_max_size,
+        sixel_diffusion,
+        image_max_rows,
+        time_format,
+        relative_time,
+        verdicts,
+        inline_select,
+        cpp_std,
+        blocking_warnings,
+        python,
+        editor,
+        archive_dir,
+        add_friends,
+        remove_friends,
+        add_pre_submit_hooks,
+        clear_pre_submit_hooks,
+        sanity_checks,
+        bell,
+        sound_command,
+        lang,
+        screen_reader,
+        hosts,
+        concurrency,
+        pool_size,
+        keep_alive,
+    } = changes;
+    let add_friends = add_friends
+        .iter()
+        .map(|friend| friend.parse::<Friend>())
+        .collect::<Result<Vec<_>>>()?;
+    let verdict_style = verdicts
+        .map(|verdicts| verdicts.parse::<VerdictStyle>())
+        .transpose()?;
+    // an empty value restores detection from the system locale
+    let lang = lang
+        .map(|lang| (!lang.is_empty()).then(|| lang.parse::<Lang>()).transpose())
+        .transpose()?;
+    if let Some(time_format) = &time_format
+        && StrftimeItems::new(time_format).any(|item| item == Item::Error)
+    {
+        return Err(anyhow::anyhow!(tr!(
+            "invalid-time-format",
+            format = time_format.as_str()
+        )));
+    }
+    if let Some(cpp_std) = &cpp_std
+        && !cpp_std.is_empty()
+        && !is_cpp_std(cpp_std)
+    {
+        return Err(anyhow::anyhow!(tr!(
+            "invalid-cpp-std",
+            cpp_std = cpp_std.as_str()
+        )));
+    }
+    if image_max_rows == Some(0) {
+        return Err(anyhow::anyhow!(tr!("invalid-image-rows")));
+    }
+    if concurrency == Some(0) {
+        return Err(anyhow::anyhow!(tr!("invalid-concurrency")));
+    }
+    let graphics_protocol = graphics.map(|graphics| graphics.parse()).transpose()?;
+    let sixel_max_size = sixel_max_size
+        .map(|size| parse_image_size(&size))
+        .transpose()?;
+    let sixel_diffusion = sixel_diffusion
+        .map(|diffusion| diffusion.parse::<SixelDiffusion>())
+        .transpose()?;
+    if let Some(theme) = &theme {
+        // fail early rather than on the next `view submission`
+        code_theme::load_theme(Some(theme))?;
+    }
+    session.update_config(|config| {
+        if graphics_protocol.is_some() {
+            config.graphics_protocol = graphics_protocol;
+        }
+        if https.is_some() {
+            config.prefer_https = https;
+        }
+        if let Some(user_agent) = user_agent {
+            // an empty value restores the default
+            config.user_agent = (!user_agent.is_empty()).then_some(user_agent);
+        }
+        if theme.is_some() {
+            config.code_theme = theme;
+        }
+        if let Some(time_format) = time_format {
+            // an empty value restores the default
+            config.time_format = (!time_format.is_empty()).then_some(time_format);
+        }
+        if relative_time.is_some() {
+            config.relative_time = relative_time;
+        }
+        if verdict_style.is_some() {
+            config.verdict_style = verdict_style;
+        }
+        if let Some(lang) = lang {
+            config.lang = lang;
+        }
+        if image_max_rows.is_some() {
+            config.image_max_rows = image_max_rows;
+        }
+        if inline_select.is_some() {
+            config.inline_select = inline_select;
+        }
+        if screen_reader.is_some() {
+            config.screen_reader = screen_reader;
+        }
+        if sanity_checks.is_some() {
+            config.sanity_checks = sanity_checks;
+        }
+        if bell.is_some() {
+            config.bell = bell;
+        }
+        if let Some(sound_command) = sound_command {
+            // an empty value restores the bell
+            config.sound_command = (!sound_command.is_empty()).then_some(sound_command);
+        }
+        if concurrency.is_some() {
+            config.concurrency = concurrency;
+        }
+        if pool_size.is_some() {
+            config.pool_size = pool_size;
+        }
+        if keep_alive.is_some() {
+            config.keep_alive = keep_alive;
+        }
+        if let Some(hosts) = hosts {
+            let hosts = hosts
+                .split(',')
+                .map(|host| host.trim().to_string())
+                .filter(|host| !host.is_empty())
+                .collect::<Vec<_>>();
+            // an empty value restores the default
+            config.hosts = (!hosts.is_empty()).then_some(hosts);
+        }
+        if let Some(cpp_std) = cpp_std {
+            // an empty value restores the default
+            config.cpp_std = (!cpp_std.is_empty()).then_some(cpp_std);
+        }
+        if let Some(warnings) = blocking_warnings {
+            let warnings = warnings
+                .split(',')
+                .map(|warning| warning.trim().to_string())
+                .filter(|warning| !warning.is_empty())
+                .collect::<Vec<_>>();
+            // an empty value restores the default
+            config.blocking_warnings = (!warnings.is_empty()).then_some(warnings);
+        }
+        if let Some(python) = python {
+            // an empty value restores the default
+            config.python = (!python.is_empty()).then_some(python);
+        }
+        if let Some(editor) = editor {
+            // an empty value restores the default
+            config.editor = (!editor.is_empty()).then_some(editor);
+        }
+        if let Some(archive_dir) = archive_dir {
+            // an empty value turns archiving off, relative paths are kept
+            // pointing where they were set from
+            config.archive_dir = (!archive_dir.is_empty()).then(|| {
+                std::path::absolute(&archive_dir)
+                    .map(|dir| dir.to_string_lossy().to_string())
+                    .unwrap_or(archive_dir)
+            });
+        }
+        if !add_friends.is_empty() || !remove_friends.is_empty() {
+            let friends = config.friends.get_or_insert_default();
+            friends.retain(|friend| {
+                !remove_friends.contains(&friend.id)
+                    && !add_friends.iter().any(|added| added.id == friend.id)
+            });
+            friends.extend(add_friends);
+        }
+        if clear_pre_submit_hooks {
+            config.pre_submit_hooks = None;
+        }
+        if !add_pre_submit_hooks.is_empty() {
+            config
+                .pre_submit_hooks
+                .get_or_insert_default()
+                .extend(add_pre_submit_hooks);
+        }
+        if sixel_max_size.is_some() || sixel_diffusion.is_some() {
+            let sixel = config.sixel.get_or_insert_default();
+            if let Some((max_width, max_height)) = sixel_max_size {
+                sixel.max_width = max_width;
+                sixel.max_height = max_height;
             }
-            _ => {
-                let rel = &problems.problems[i].url;
-                let root = url::Url::parse(&format!("http://{}.openjudge.cn", group))?;
-                let url = root.join(rel)?;
-                Box::pin(view_problem(url.as_str())).await
+            if let Some(diffusion) = sixel_diffusion {
+                sixel.diffusion = diffusion;
             }
-        },
+        }
+    })
+}
+
+/// Parses a duration like "10m", "1h30m" or "90s". A bare number is taken
+/// as minutes.
+pub fn parse_duration(duration: &str) -> Result<chrono::TimeDelta> {
+    let invalid = || anyhow::anyhow!(tr!("invalid-duration", duration = duration));
+    let duration = duration.trim();
+    if let Ok(minutes) = duration.parse::<i64>() {
+        return Ok(chrono::TimeDelta::minutes(minutes));
+    }
+    let mut total = chrono::TimeDelta::zero();
+    let mut number = String::new();
+    for c in duration.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value = number.parse::<i64>().map_err(|_| invalid())?;
+        number.clear();
+        total += match c.to_ascii_lowercase() {
+            'd' => chrono::TimeDelta::days(value),
+            'h' => chrono::TimeDelta::hours(value),
+            'm' => chrono::TimeDelta::minutes(value),
+            's' => chrono::TimeDelta::seconds(value),
+            _ => return Err(invalid()),
+        };
+    }
+    if !number.is_empty() || duration.is_empty() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+/// Whether `std` names a C++ standard accepted by `-std`, e.g. `c++17`,
+/// `gnu++2a` or `c++1z`.
+pub fn is_cpp_std(std: &str) -> bool {
+    let Some(version) = std
+        .strip_prefix("c++")
+        .or_else(|| std.strip_prefix("gnu++"))
+    else {
+        return false;
+    };
+    version.len() == 2 && version.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// The Python interpreter for local tests: the configured one, else that of
+/// the activated virtualenv, else `python3`. A directory is taken as a
+/// virtualenv.
+pub fn python_interpreter(configured: Option<&str>) -> std::path::PathBuf {
+    let venv_python = |venv: &std::path::Path| {
+        if cfg!(windows) {
+            venv.join("Scripts").join("python.exe")
+        } else {
+            venv.join("bin").join("python")
+        }
+    };
+    match configured {
+        Some(path) if std::path::Path::new(path).is_dir() => venv_python(path.as_ref()),
+        Some(path) => path.into(),
+        None => std::env::var_os("VIRTUAL_ENV")
+            .map(|venv| venv_python(venv.as_ref()))
+            .unwrap_or_else(|| "python3".into()),
     }
 }
 
-pub fn configure(graphics: &str) -> Result<()> {
-    let conf = AppConfig::read_config(get_config_dir())?;
-    AppConfig {
-        graphics_protocol: Some(graphics.parse()?),
-        ..conf.unwrap_or_default()
+/// Parses an image size given as `WIDTHxHEIGHT`, e.g. `800x600`.
+pub fn parse_image_size(size: &str) -> Result<(u32, u32)> {
+    let invalid = || anyhow::anyhow!(tr!("invalid-image-size", size = size));
+    let (width, height) = size.trim().split_once(['x', 'X']).ok_or_else(invalid)?;
+    let width: u32 = width.trim().parse().map_err(|_| invalid())?;
+    let height: u32 = height.trim().parse().map_err(|_| invalid())?;
+    if width == 0 || height == 0 {
+        return Err(invalid());
+    }
+    Ok((width, height))
+}
+
+pub fn list_themes() {
+    for name in code_theme::theme_names() {
+        println!("{}", name);
     }
-    .write_config(get_config_dir())?;
-    Ok(())
 }
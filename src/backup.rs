@@ -0,0 +1,89 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow};
+use zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+/// Writes every file under `root` into a zip archive at `archive`, with paths
+/// relative to `root`. Returns the number of files written.
+pub fn backup(root: &Path, archive: &Path) -> Result<usize> {
+    let file = fs::File::create(archive)
+        .with_context(|| tr!("writing-file", path = archive.display().to_string()))?;
+    // the archive may be written into the directory it backs up
+    let archive = archive.canonicalize()?;
+    let mut files = list_files(root)?;
+    files.retain(|path| path.canonicalize().is_ok_and(|path| path != archive));
+    let mut writer = ZipWriter::new(file);
+    for path in &files {
+        let name = path
+            .strip_prefix(root)?
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        writer.start_file(name, SimpleFileOptions::default())?;
+        writer.write_all(
+            &fs::read(path)
+                .with_context(|| tr!("reading-file", path = path.display().to_string()))?,
+        )?;
+    }
+    writer.finish()?;
+    Ok(files.len())
+}
+
+/// Extracts a backup made by [`backup`] into `root`. Existing files are only
+/// overwritten with `force`. Returns the number of files restored.
+pub fn restore(archive: &Path, root: &Path, force: bool) -> Result<usize> {
+    let file = fs::File::open(archive)
+        .with_context(|| tr!("reading-file", path = archive.display().to_string()))?;
+    let mut archive = ZipArchive::new(file)
+        .with_context(|| tr!("not-a-backup", path = archive.display().to_string()))?;
+    let mut targets = vec![];
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let name = entry
+            .enclosed_name()
+            .ok_or_else(|| anyhow!(tr!("unsafe-backup-path", path = entry.name().to_string())))?;
+        targets.push((i, root.join(name)));
+    }
+    if !force && let Some((_, existing)) = targets.iter().find(|(_, target)| target.exists()) {
+        return Err(anyhow!(tr!(
+            "backup-target-exists",
+            path = existing.display().to_string()
+        )));
+    }
+    for (i, target) in &targets {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut entry = archive.by_index(*i)?;
+        let mut output = fs::File::create(target)
+            .with_context(|| tr!("writing-file", path = target.display().to_string()))?;
+        io::copy(&mut entry, &mut output)?;
+    }
+    Ok(targets.len())
+}
+
+/// Files under `dir`, recursively and in name order.
+fn list_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| tr!("reading-file", path = dir.display().to_string()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+    let mut files = vec![];
+    for path in entries {
+        if path.is_dir() {
+            files.extend(list_files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
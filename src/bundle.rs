@@ -0,0 +1,146 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use libopenjudge::{Problem, ProblemLimits, ProblemUrl};
+use zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::{problem_spec::ProblemSpec, utils::html::html_to_markdown};
+
+/// Statement of a bundle, in the format `oj problem create` reads.
+pub const STATEMENT_FILE: &str = "problem.md";
+/// Directory of the images of a bundle.
+pub const IMAGES_DIR: &str = "images";
+/// Directory of the sample test files of a bundle, as `1.in` and `1.out`.
+pub const TESTS_DIR: &str = "tests";
+
+/// The statement of the problem at `url` as Markdown with a front matter,
+/// with `samples` taken as plain text and images pointing at
+/// `image_path(src)`.
+pub fn problem_markdown(
+    problem: &Problem,
+    url: &str,
+    samples: (&str, &str),
+    image_path: &dyn Fn(&str) -> Option<String>,
+) -> String {
+    let mut front_matter = vec![format!("# exported from {}", url)];
+    if let Some(time_limit) = problem.limits.case_time.or(problem.limits.total_time) {
+        front_matter.push(format!("time_limit: {}", time_limit));
+    }
+    if let Some(memory_limit) = problem.limits.memory {
+        front_matter.push(format!("memory_limit: {}", memory_limit));
+    }
+    front_matter.push(format!("data: {}/", TESTS_DIR));
+    let mut markdown = format!(
+        "---\n{}\n---\n# {}\n\n{}\n",
+        front_matter.join("\n"),
+        problem.title,
+        html_to_markdown(&problem.description, image_path)
+    );
+    for (heading, html) in [("Input", &problem.input), ("Output", &problem.output)] {
+        if let Some(html) = html {
+            markdown.push_str(&format!(
+                "\n## {}\n\n{}\n",
+                heading,
+                html_to_markdown(html, image_path)
+            ));
+        }
+    }
+    for (heading, sample) in [("Sample Input", samples.0), ("Sample Output", samples.1)] {
+        markdown.push_str(&format!(
+            "\n## {}\n\n```\n{}\n```\n",
+            heading,
+            sample.trim_end()
+        ));
+    }
+    for (heading, html) in [("Hint", &problem.hint), ("Source", &problem.source)] {
+        if let Some(html) = html {
+            let text = html_to_markdown(html, image_path);
+            if !text.is_empty() {
+                markdown.push_str(&format!("\n## {}\n\n{}\n", heading, text));
+            }
+        }
+    }
+    markdown
+}
+
+/// URL of the problem a statement was exported from, noted in its front
+/// matter.
+pub fn exported_from(markdown: &str) -> Option<&str> {
+    let (front_matter, _) = markdown.strip_prefix("---")?.split_once("\n---")?;
+    front_matter
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("# exported from "))
+        .map(str::trim)
+}
+
+/// Writes `files`, as paths within the archive and their contents, into a zip
+/// archive at `archive`.
+pub fn write_bundle(archive: &Path, files: &[(String, Vec<u8>)]) -> Result<()> {
+    let file =
+        fs::File::create(archive).with_context(|| format!("Writing {}", archive.display()))?;
+    let mut writer = ZipWriter::new(file);
+    for (name, contents) in files {
+        writer.start_file(name, SimpleFileOptions::default())?;
+        writer.write_all(contents)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Files of the zip archive at `archive`, as paths within the archive and
+/// their contents. Directories and paths leading out of the archive are
+/// left out.
+pub fn read_bundle(archive: &Path) -> Result<Vec<(String, Vec<u8>)>> {
+    let file = fs::File::open(archive).with_context(|| format!("Reading {}", archive.display()))?;
+    let mut reader = ZipArchive::new(file)?;
+    let mut files = vec![];
+    for i in 0..reader.len() {
+        let mut entry = reader.by_index(i)?;
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        if entry.is_dir() {
+            continue;
+        }
+        let mut contents = vec![];
+        entry.read_to_end(&mut contents)?;
+        files.push((name.to_string_lossy().replace('\\', "/"), contents));
+    }
+    Ok(files)
+}
+
+/// The problem at `url` from the bundle imported into `dir`, `None` if none
+/// was. Images point at the files of the bundle, so it reads without going
+/// online.
+pub fn imported_problem(dir: &Path, url: &ProblemUrl) -> Result<Option<Problem>> {
+    let statement = dir.join(STATEMENT_FILE);
+    if !statement.exists() {
+        return Ok(None);
+    }
+    let problem = ProblemSpec::read(&statement)?.to_new_problem()?;
+    let images = url::Url::from_directory_path(dir)
+        .map(|base| format!("src=\"{}{}/", base, IMAGES_DIR))
+        .unwrap_or_else(|_| format!("src=\"{}/", IMAGES_DIR));
+    let local = |html: String| html.replace(&format!("src=\"{}/", IMAGES_DIR), &images);
+    Ok(Some(Problem {
+        title: problem.title,
+        group: url.group().to_string(),
+        probset: url.probset().to_string(),
+        limits: ProblemLimits {
+            total_time: Some(problem.time_limit),
+            memory: Some(problem.memory_limit),
+            ..Default::default()
+        },
+        description: local(problem.description),
+        input: problem.input.map(local),
+        output: problem.output.map(local),
+        sample_input: problem.sample_input,
+        sample_output: problem.sample_output,
+        hint: problem.hint.map(local),
+        source: problem.source,
+    }))
+}
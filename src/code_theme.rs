@@ -1,7 +1,11 @@
-use std::sync::LazyLock;
+use std::{path::Path, sync::LazyLock};
 
+use anyhow::{Result, anyhow};
 use syntect::highlighting::{Theme, ThemeSet};
 
+/// Name of the theme used when none is configured.
+pub const DEFAULT_THEME: &str = "Enki-Tokyo-Night";
+
 /// The Tokyo Night theme from https://github.com/enkia/enki-theme/blob/master/scheme/Enki-Tokyo-Night.tmTheme
 const ENKI_TOKYO_NIGHT_CONFIG: &[u8; 44344] = br#"<?xml version="1.0" encoding="UTF-8"?>
 <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
@@ -1390,3 +1394,40 @@ pub static ENKI_TOKYO_NIGHT_THEME: LazyLock<Theme> = LazyLock::new(|| {
     };
     ThemeSet::load_from_reader(&mut reader).unwrap()
 });
+
+static BUNDLED_THEMES: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+
+/// Names of the themes that can be selected without a file.
+pub fn theme_names() -> Vec<&'static str> {
+    let mut names = vec![DEFAULT_THEME];
+    names.extend(BUNDLED_THEMES.themes.keys().map(|name| name.as_str()));
+    names
+}
+
+/// Loads a bundled theme by name (case insensitive), or a `.tmTheme` file if
+/// `name` is a path to one. `None` gives the default theme.
+pub fn load_theme(name: Option<&str>) -> Result<Theme> {
+    let Some(name) = name else {
+        return Ok(ENKI_TOKYO_NIGHT_THEME.clone());
+    };
+    if name.eq_ignore_ascii_case(DEFAULT_THEME) {
+        return Ok(ENKI_TOKYO_NIGHT_THEME.clone());
+    }
+    if let Some((_, theme)) = BUNDLED_THEMES
+        .themes
+        .iter()
+        .find(|(bundled, _)| bundled.eq_ignore_ascii_case(name))
+    {
+        return Ok(theme.clone());
+    }
+    let path = Path::new(name);
+    if path.extension().is_some_and(|ext| ext == "tmTheme") {
+        return ThemeSet::get_theme(path)
+            .map_err(|e| anyhow!("Cannot load theme {}: {}", path.display(), e));
+    }
+    Err(anyhow!(
+        "Unknown theme {}, expected a path to a .tmTheme file or one of: {}",
+        name,
+        theme_names().join(", ")
+    ))
+}
@@ -0,0 +1,148 @@
+use std::{
+    collections::VecDeque,
+    fs, io,
+    panic::{self, PanicHookInfo},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use tracing::Subscriber;
+use tracing_subscriber::{Layer, filter::LevelFilter, registry::LookupSpan};
+
+const ISSUES_URL: &str = "https://github.com/djdjz7/openjudge_cli/issues";
+
+/// Log lines kept for crash reports, whatever the verbosity.
+const RECENT_LOG_LINES: usize = 100;
+
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Keeps the last [`RECENT_LOG_LINES`] lines written to it.
+struct RecentLogs;
+
+impl io::Write for RecentLogs {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut logs = RECENT_LOGS.lock().unwrap_or_else(|e| e.into_inner());
+        for line in String::from_utf8_lossy(buf).lines() {
+            if logs.len() == RECENT_LOG_LINES {
+                logs.pop_front();
+            }
+            logs.push_back(line.to_string());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The lines kept by [`recent_logs_layer`], oldest first.
+pub fn recent_logs() -> Vec<String> {
+    RECENT_LOGS
+        .lock()
+        .map(|logs| logs.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Records debug logs in memory, for the report of a later panic.
+pub fn recent_logs_layer<S>() -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_writer(|| RecentLogs)
+        .with_filter(LevelFilter::DEBUG)
+}
+
+/// What went wrong and where the program was, for a crash report.
+pub struct CrashContext<'a> {
+    pub message: &'a str,
+    pub location: Option<String>,
+    pub args: &'a [String],
+    pub config: &'a str,
+    pub backtrace: &'a str,
+    pub logs: &'a [String],
+}
+
+/// The text of a crash report, in sections a maintainer can scan.
+pub fn crash_report(context: &CrashContext) -> String {
+    let location = context
+        .location
+        .as_deref()
+        .map(|location| format!("\nat {}", location))
+        .unwrap_or_default();
+    let logs = if context.logs.is_empty() {
+        "(none)".to_string()
+    } else {
+        context.logs.join("\n")
+    };
+    format!(
+        "oj {} crashed\nplatform: {} {}\ntime: {}\n\n\
+         ## Panic\n\n{}{}\n\n\
+         ## Command line\n\n{}\n\n\
+         ## Config\n\n{}\n\n\
+         ## Backtrace\n\n{}\n\n\
+         ## Recent logs\n\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        chrono::Local::now().to_rfc3339(),
+        context.message,
+        location,
+        context.args.join(" "),
+        context.config.trim_end(),
+        context.backtrace.trim_end(),
+        logs
+    )
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+fn write_report(dir: &Path, report: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!(
+        "crash-{}.txt",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::write(&path, report)?;
+    Ok(path)
+}
+
+/// Replaces bare panic messages with a report written to `dir`. The config is
+/// summarized by `config` when a panic happens, and should leave out secrets.
+/// If the report cannot be written, the default message is printed instead.
+pub fn install_panic_hook(dir: PathBuf, config: fn() -> String) {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        // a panic inside a selector leaves the terminal in raw mode
+        let _ = crossterm::terminal::disable_raw_mode();
+        let message = panic_message(info);
+        let args = std::env::args().collect::<Vec<_>>();
+        let logs = recent_logs();
+        let report = crash_report(&CrashContext {
+            message: &message,
+            location: info.location().map(|location| location.to_string()),
+            args: &args,
+            config: &config(),
+            backtrace: &std::backtrace::Backtrace::force_capture().to_string(),
+            logs: &logs,
+        });
+        match write_report(&dir, &report) {
+            Ok(path) => eprintln!(
+                "oj crashed: {}\nA report has been saved to {}, please attach it to an issue at {}.",
+                message,
+                path.display(),
+                ISSUES_URL
+            ),
+            Err(_) => default_hook(info),
+        }
+    }));
+}
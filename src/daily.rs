@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+/// Daily problems picked so far, kept in `daily.json` in the config root.
+#[derive(Serialize, Deserialize, Default)]
+pub struct DailyLog {
+    #[serde(default)]
+    pub entries: Vec<DailyEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DailyEntry {
+    /// Local date the problem was picked for, as `YYYY-MM-DD`.
+    pub date: String,
+    pub url: String,
+    pub title: String,
+    pub solved: bool,
+}
+
+/// A problem that may be picked, with its acceptance counts.
+pub struct Candidate {
+    pub accepted: u32,
+    pub submitters: u32,
+}
+
+/// Number of recent daily problems that decide how hard the next one is.
+const RECENT_DAYS: usize = 7;
+
+impl DailyLog {
+    pub fn get(&self, date: &str) -> Option<&DailyEntry> {
+        self.entries.iter().find(|entry| entry.date == date)
+    }
+
+    /// The last daily problems, oldest first.
+    pub fn recent(&self) -> &[DailyEntry] {
+        &self.entries[self.entries.len().saturating_sub(RECENT_DAYS)..]
+    }
+
+    /// Acceptance rate the next problem should have: easy ones after missed
+    /// days, harder ones while recent problems get solved.
+    pub fn target_rate(&self) -> f64 {
+        let recent = self.recent();
+        let solved = if recent.is_empty() {
+            0.5
+        } else {
+            recent.iter().filter(|entry| entry.solved).count() as f64 / recent.len() as f64
+        };
+        0.8 - 0.5 * solved
+    }
+}
+
+/// Picks a candidate at random, favoring acceptance rates close to `target`.
+/// The same `seed` picks the same problem.
+pub fn pick(candidates: &[Candidate], target: f64, seed: u64) -> Option<usize> {
+    let weights = candidates
+        .iter()
+        .map(|candidate| {
+            let rate = if candidate.submitters == 0 {
+                0.5
+            } else {
+                candidate.accepted as f64 / candidate.submitters as f64
+            };
+            1.0 / (0.05 + (rate - target).abs())
+        })
+        .collect::<Vec<_>>();
+    let total = weights.iter().sum::<f64>();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut point = random_unit(seed) * total;
+    for (i, weight) in weights.iter().enumerate() {
+        if point < *weight {
+            return Some(i);
+        }
+        point -= weight;
+    }
+    candidates.len().checked_sub(1)
+}
+
+/// A number in `[0, 1)` derived from `seed` with SplitMix64.
+fn random_unit(seed: u64) -> f64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
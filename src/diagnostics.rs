@@ -0,0 +1,233 @@
+use std::{fmt::Write, sync::LazyLock};
+
+use colored::{ColoredString, Colorize};
+use onig::Regex;
+
+use crate::utils::terminal::vertical_bar;
+
+/// `file:line:col: severity: message`, as printed by GCC and Clang.
+static GCC_DIAGNOSTIC_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[^\n]*?:(\d+):(?:(\d+):)?\s*(fatal error|error|warning|note):\s*(.*)$").unwrap()
+});
+
+/// `program: warning: message`, printed by GCC for problems not in the
+/// source, such as `cc1: warning: command-line option ...`. Errors are left
+/// out, `collect2: error: ld returned 1 exit status` says less than the
+/// linker output around it.
+static GCC_TOOL_WARNING_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[\w.+-]+: warning: (.*)$").unwrap());
+
+/// `file(line,col) Severity: message`, as printed by Free Pascal.
+static FPC_DIAGNOSTIC_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[^\n(]*\((\d+),(\d+)\) (Fatal|Error|Warning|Note|Hint): (.*)$").unwrap()
+});
+
+/// `[-Wformat=]` at the end of a GCC warning, naming the flag enabling it.
+static WARNING_FLAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[-W([\w+-]+)(?:=\d*)?\]$").unwrap());
+
+/// `  File "name", line N` of a Python traceback.
+static PYTHON_LOCATION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*File "[^"]*", line (\d+)"#).unwrap());
+
+/// A compiler message pointing at a location in the source code.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Diagnostic {
+    /// 1-based line number, 0 for messages of the compiler itself.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: Option<usize>,
+    pub severity: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Flag of a GCC warning without its `-W`, such as `format`.
+    pub fn warning_flag(&self) -> Option<&str> {
+        let captures = WARNING_FLAG_RE.captures(&self.message)?;
+        let (start, end) = captures.pos(1)?;
+        Some(&self.message[start..end])
+    }
+
+    /// Whether this is a warning enabled by one of `flags`, given with or
+    /// without their `-W`.
+    pub fn is_blocking(&self, flags: &[String]) -> bool {
+        self.severity == "warning"
+            && self.warning_flag().is_some_and(|flag| {
+                flags
+                    .iter()
+                    .any(|blocking| blocking.trim_start_matches("-W") == flag)
+            })
+    }
+}
+
+/// The warnings in compiler output, each once and in the order printed.
+/// Headers included more than once repeat theirs.
+pub fn compiler_warnings(output: &str) -> Vec<Diagnostic> {
+    let mut warnings = vec![];
+    for diagnostic in parse_diagnostics(output) {
+        if diagnostic.severity == "warning" && !warnings.contains(&diagnostic) {
+            warnings.push(diagnostic);
+        }
+    }
+    warnings
+}
+
+/// Finds the diagnostics in compiler output. Lines that do not point at a
+/// location, such as "In function 'int main()'", are skipped.
+pub fn parse_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    for line in output.lines() {
+        if let Some(captures) = GCC_DIAGNOSTIC_RE.captures(line) {
+            let (Some(line), Some(severity), Some(message)) =
+                (captures.at(1), captures.at(3), captures.at(4))
+            else {
+                continue;
+            };
+            diagnostics.push(Diagnostic {
+                line: line.parse().unwrap_or(0),
+                column: captures.at(2).and_then(|column| column.parse().ok()),
+                severity: severity.to_string(),
+                message: message.to_string(),
+            });
+        } else if let Some(message) = GCC_TOOL_WARNING_RE
+            .captures(line)
+            .and_then(|captures| captures.at(1))
+        {
+            diagnostics.push(Diagnostic {
+                line: 0,
+                column: None,
+                severity: "warning".to_string(),
+                message: message.to_string(),
+            });
+        } else if let Some(captures) = FPC_DIAGNOSTIC_RE.captures(line) {
+            let (Some(line), Some(column), Some(severity), Some(message)) = (
+                captures.at(1),
+                captures.at(2),
+                captures.at(3),
+                captures.at(4),
+            ) else {
+                continue;
+            };
+            diagnostics.push(Diagnostic {
+                line: line.parse().unwrap_or(0),
+                column: column.parse().ok(),
+                severity: match severity {
+                    "Fatal" => "fatal error".to_string(),
+                    severity => severity.to_lowercase(),
+                },
+                message: message.to_string(),
+            });
+        }
+    }
+    if !diagnostics.is_empty() {
+        return diagnostics;
+    }
+    // Python reports the error after the traceback, at its last location
+    let location = output
+        .lines()
+        .rev()
+        .filter_map(|line| PYTHON_LOCATION_RE.captures(line))
+        .filter_map(|captures| captures.at(1).and_then(|line| line.parse().ok()))
+        .next();
+    let message = output
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty() && !line.starts_with(' '));
+    if let (Some(line), Some(message)) = (location, message) {
+        diagnostics.push(Diagnostic {
+            line,
+            column: None,
+            severity: "error".to_string(),
+            message: message.trim().to_string(),
+        });
+    }
+    diagnostics
+}
+
+fn severity_label(severity: &str) -> ColoredString {
+    match severity {
+        "warning" => severity.yellow().bold(),
+        "note" | "hint" => severity.cyan().bold(),
+        _ => severity.red().bold(),
+    }
+}
+
+/// Prints each diagnostic in `output` with the source line it points at and
+/// a caret under the column. Output without recognizable diagnostics is
+/// returned as is.
+pub fn render_diagnostics(output: &str, source: &str) -> String {
+    let diagnostics = parse_diagnostics(output);
+    if diagnostics.is_empty() {
+        return output.to_string();
+    }
+    render_diagnostic_list(&diagnostics, source)
+}
+
+/// Prints each of `diagnostics` with the source line it points at.
+pub fn render_diagnostic_list(diagnostics: &[Diagnostic], source: &str) -> String {
+    let source_lines = source.lines().collect::<Vec<_>>();
+    let gutter_width = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.line.to_string().len())
+        .max()
+        .unwrap_or(1);
+    let mut rendered = String::new();
+    for diagnostic in diagnostics {
+        let _ = writeln!(
+            rendered,
+            "{}: {}",
+            severity_label(&diagnostic.severity),
+            diagnostic.message.bold()
+        );
+        if diagnostic.line == 0 {
+            continue;
+        }
+        let location = match diagnostic.column {
+            Some(column) => format!("{}:{}", diagnostic.line, column),
+            None => diagnostic.line.to_string(),
+        };
+        let _ = writeln!(
+            rendered,
+            "{:>gutter_width$}{} line {}",
+            "",
+            "-->".dimmed(),
+            location
+        );
+        let Some(source_line) = diagnostic
+            .line
+            .checked_sub(1)
+            .and_then(|index| source_lines.get(index))
+        else {
+            continue;
+        };
+        let _ = writeln!(
+            rendered,
+            "{} {}",
+            format!("{:>gutter_width$} {}", diagnostic.line, vertical_bar()).dimmed(),
+            source_line
+        );
+        if let Some(column) = diagnostic.column {
+            let _ = writeln!(
+                rendered,
+                "{} {}{}",
+                format!("{:>gutter_width$} {}", "", vertical_bar()).dimmed(),
+                caret_indent(source_line, column),
+                "^".red().bold()
+            );
+        }
+    }
+    rendered
+}
+
+/// Whitespace that puts a caret under the `column`-th character of `line`,
+/// keeping tabs so the caret lines up however the terminal expands them.
+fn caret_indent(line: &str, column: usize) -> String {
+    line.chars()
+        .take(column.saturating_sub(1))
+        .map(|c| match c {
+            '\t' => "\t".to_string(),
+            c => " ".repeat(unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)),
+        })
+        .collect()
+}
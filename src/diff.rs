@@ -0,0 +1,251 @@
+use std::{fmt::Write, str::FromStr};
+
+use colored::Colorize;
+use similar::{ChangeTag, DiffTag, TextDiff};
+use unicode_width::UnicodeWidthChar;
+
+use crate::utils::{
+    terminal::screen_reader,
+    width::{Align, display_width, pad, truncate},
+};
+
+/// Pieces of a line, `true` marking the emphasized ones.
+pub type Segments = Vec<(bool, String)>;
+
+/// How `oj test` shows the difference between expected and actual output.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DiffStyle {
+    #[default]
+    Unified,
+    /// Expected and actual output in two columns.
+    Side,
+}
+
+impl FromStr for DiffStyle {
+    type Err = anyhow::Error;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "u" | "unified" => Ok(DiffStyle::Unified),
+            "s" | "side" | "side-by-side" => Ok(DiffStyle::Side),
+            _ => Err(anyhow::format_err!(
+                "Invalid value for DiffStyle: {}",
+                value
+            )),
+        }
+    }
+}
+
+pub fn render_diff(expected: &str, actual: &str, style: DiffStyle, width: usize) -> String {
+    // changes are only marked by color in the columns
+    if screen_reader() {
+        return unified_diff(expected, actual);
+    }
+    match style {
+        DiffStyle::Unified => unified_diff(expected, actual),
+        DiffStyle::Side => side_by_side_diff(expected, actual, width),
+    }
+}
+
+/// Line diff with the line numbers of both sides, `-` for expected lines
+/// missing from the actual output and `+` for unexpected ones. Within changed
+/// lines the differing words are highlighted.
+pub fn unified_diff(expected: &str, actual: &str) -> String {
+    let diff = TextDiff::from_lines(expected, actual);
+    let mut output = String::new();
+    for op in diff.ops() {
+        for change in diff.iter_inline_changes(op) {
+            let old_index = change
+                .old_index()
+                .map(|v| (v + 1).to_string())
+                .unwrap_or(" ".to_string());
+            let new_index = change
+                .new_index()
+                .map(|v| (v + 1).to_string())
+                .unwrap_or(" ".to_string());
+            let (sign, color) = match change.tag() {
+                ChangeTag::Delete => ("-".red().to_string(), Some(Side::Expected)),
+                ChangeTag::Insert => ("+".green().to_string(), Some(Side::Actual)),
+                ChangeTag::Equal => (" ".to_string(), None),
+            };
+            let segments = change
+                .iter_strings_lossy()
+                .map(|(emphasized, text)| {
+                    (emphasized, text.trim_end_matches(['\r', '\n']).to_string())
+                })
+                .collect::<Vec<_>>();
+            let _ = writeln!(
+                output,
+                "{:>3} {:>3} | {} {}",
+                old_index,
+                new_index,
+                sign,
+                paint(&segments, color)
+            );
+        }
+    }
+    output
+}
+
+/// Expected output on the left and actual output on the right, changed lines
+/// paired up on the same row with the differing characters highlighted. Each
+/// column gets half of `width`, longer lines are cut off with `…`.
+pub fn side_by_side_diff(expected: &str, actual: &str, width: usize) -> String {
+    let diff = TextDiff::from_lines(expected, actual);
+    let old_lines = expected.lines().collect::<Vec<_>>();
+    let new_lines = actual.lines().collect::<Vec<_>>();
+    let number_width = old_lines
+        .len()
+        .max(new_lines.len())
+        .max(1)
+        .to_string()
+        .len();
+    // two gutters of "n │ " and a " │ " between the columns
+    let column_width = width.saturating_sub(2 * (number_width + 3) + 3).max(8) / 2;
+
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "{:>number_width$} │ {} │ {:>number_width$} │ {}",
+        "",
+        fit("Expected", column_width).bold(),
+        "",
+        "Actual".bold(),
+    );
+    for op in diff.ops() {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+        let rows = old_range.len().max(new_range.len());
+        for row in 0..rows {
+            let old = old_range
+                .clone()
+                .nth(row)
+                .and_then(|i| old_lines.get(i).map(|line| (i + 1, *line)));
+            let new = new_range
+                .clone()
+                .nth(row)
+                .and_then(|i| new_lines.get(i).map(|line| (i + 1, *line)));
+            let whole = |side: Option<(usize, &str)>| {
+                side.map_or(vec![], |(_, line)| vec![(false, line.to_string())])
+            };
+            let (left, right) = match (old, new) {
+                (Some((_, old_line)), Some((_, new_line))) if tag != DiffTag::Equal => {
+                    inline_segments(old_line, new_line)
+                }
+                _ => (whole(old), whole(new)),
+            };
+            let changed = tag != DiffTag::Equal;
+            let number = |side: Option<(usize, &str)>| {
+                side.map_or(String::new(), |(number, _)| number.to_string())
+            };
+            let _ = writeln!(
+                output,
+                "{:>number_width$} │ {} │ {:>number_width$} │ {}",
+                number(old).dimmed(),
+                paint(
+                    &fit_segments(&left, column_width),
+                    changed.then_some(Side::Expected)
+                ),
+                number(new).dimmed(),
+                paint(
+                    &trim_segments_end(fit_segments(&right, column_width)),
+                    changed.then_some(Side::Actual)
+                ),
+            );
+        }
+    }
+    output
+}
+
+/// Splits a changed pair of lines into segments, emphasizing the characters
+/// that differ from the other line.
+pub fn inline_segments(old: &str, new: &str) -> (Segments, Segments) {
+    let diff = TextDiff::from_chars(old, new);
+    let (mut left, mut right) = (vec![], vec![]);
+    for change in diff.iter_all_changes() {
+        let value = change.value().to_string();
+        match change.tag() {
+            ChangeTag::Equal => {
+                push_segment(&mut left, false, &value);
+                push_segment(&mut right, false, &value);
+            }
+            ChangeTag::Delete => push_segment(&mut left, true, &value),
+            ChangeTag::Insert => push_segment(&mut right, true, &value),
+        }
+    }
+    (left, right)
+}
+
+fn push_segment(segments: &mut Segments, emphasized: bool, text: &str) {
+    match segments.last_mut() {
+        Some((last_emphasized, last)) if *last_emphasized == emphasized => last.push_str(text),
+        _ => segments.push((emphasized, text.to_string())),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Expected,
+    Actual,
+}
+
+/// Colors segments of a changed line, red for expected and green for actual
+/// output, with the emphasized segments inverted. `None` leaves them plain.
+fn paint(segments: &[(bool, String)], side: Option<Side>) -> String {
+    segments
+        .iter()
+        .map(|(emphasized, text)| match (side, emphasized) {
+            (None, _) => text.normal(),
+            (Some(Side::Expected), false) => text.red(),
+            (Some(Side::Expected), true) => text.black().on_red(),
+            (Some(Side::Actual), false) => text.green(),
+            (Some(Side::Actual), true) => text.black().on_green(),
+        })
+        .map(|text| text.to_string())
+        .collect()
+}
+
+/// Pads or cuts `text` to exactly `width` terminal columns.
+fn fit(text: &str, width: usize) -> String {
+    pad(&truncate(text, width), width, Align::Left)
+}
+
+/// Pads or cuts segments to exactly `width` terminal columns in total.
+fn fit_segments(segments: &[(bool, String)], width: usize) -> Segments {
+    let total = segments
+        .iter()
+        .map(|(_, text)| display_width(text))
+        .sum::<usize>();
+    let mut output = vec![];
+    if total <= width {
+        output.extend_from_slice(segments);
+        output.push((false, " ".repeat(width - total)));
+        return output;
+    }
+    let mut used = 0;
+    'outer: for (emphasized, text) in segments {
+        let mut kept = String::new();
+        for c in text.chars() {
+            let char_width = c.width().unwrap_or(0);
+            // keep a column for the ellipsis
+            if used + char_width + 1 > width {
+                output.push((*emphasized, kept));
+                break 'outer;
+            }
+            kept.push(c);
+            used += char_width;
+        }
+        output.push((*emphasized, kept));
+    }
+    output.push((false, format!("…{}", " ".repeat(width - used - 1))));
+    output
+}
+
+fn trim_segments_end(mut segments: Segments) -> Segments {
+    while let Some((emphasized, text)) = segments.pop() {
+        let trimmed = text.trim_end();
+        if !trimmed.is_empty() {
+            segments.push((emphasized, trimmed.to_string()));
+            break;
+        }
+    }
+    segments
+}
@@ -1,230 +1,636 @@
-use crate::libopenjudge::{
-    Group, Problem, ProblemListEntry, ProblemSearchResult, ProblemSetEntry, ProblemSetPartial,
-    Submission, SubmissionHistoryEntry, SubmissionResult, User,
-};
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, TimeDelta};
+use colored::Color;
+use colored::ColoredString;
 use colored::Colorize;
-use std::fmt::Display;
+use libopenjudge::{
+    Group, Problem, ProblemLimits, ProblemListEntry, ProblemSearchResult, ProblemSetEntry,
+    ProblemSetPartial, Standings, Submission, SubmissionHistoryEntry, SubmissionResult, User,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr, sync::OnceLock};
+
+use crate::diagnostics::render_diagnostics;
+use crate::i18n::translate;
+use crate::utils::{
+    table::Table,
+    terminal::screen_reader,
+    width::{Align, display_width, pad},
+};
+
+/// Wraps a libopenjudge model so it can be printed with terminal styling.
+pub struct Styled<'a, T>(pub &'a T);
+
+pub const DEFAULT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// User preferences for [`Styled`] output, set once from the configuration
+/// before anything is printed.
+pub struct DisplayOptions {
+    /// strftime-style format of absolute times, in the local timezone.
+    pub time_format: String,
+    /// Show recent times as e.g. "3 minutes ago".
+    pub relative_time: bool,
+    pub verdict_style: VerdictStyle,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            time_format: DEFAULT_TIME_FORMAT.to_string(),
+            relative_time: false,
+            verdict_style: VerdictStyle::Words,
+        }
+    }
+}
+
+static DISPLAY_OPTIONS: OnceLock<DisplayOptions> = OnceLock::new();
+
+/// Sets the display options, only the first call has an effect.
+pub fn set_display_options(options: DisplayOptions) {
+    let _ = DISPLAY_OPTIONS.set(options);
+}
+
+fn display_options() -> &'static DisplayOptions {
+    DISPLAY_OPTIONS.get_or_init(DisplayOptions::default)
+}
+
+/// Formats a time scraped from OpenJudge in the local timezone, as configured.
+pub fn format_time(time: &DateTime<FixedOffset>) -> String {
+    let options = display_options();
+    if options.relative_time
+        && let Some(relative) = relative_time(time, Local::now().fixed_offset())
+    {
+        return relative;
+    }
+    time.with_timezone(&Local)
+        .format(&options.time_format)
+        .to_string()
+}
+
+/// `time` relative to `now`, e.g. "3 minutes ago". `None` for times in the
+/// future or more than a week ago, which read better as dates.
+pub fn relative_time(time: &DateTime<FixedOffset>, now: DateTime<FixedOffset>) -> Option<String> {
+    let elapsed = now.signed_duration_since(time);
+    if elapsed < TimeDelta::zero() || elapsed >= TimeDelta::weeks(1) {
+        return None;
+    }
+    Some(if elapsed < TimeDelta::minutes(1) {
+        tr!("just-now")
+    } else if elapsed < TimeDelta::hours(1) {
+        tr!("minutes-ago", count = elapsed.num_minutes())
+    } else if elapsed < TimeDelta::days(1) {
+        tr!("hours-ago", count = elapsed.num_hours())
+    } else {
+        tr!("days-ago", count = elapsed.num_days())
+    })
+}
+
+/// A duration like "2d 03h 15m", "1h 05m 09s" or "42s", rounded down to
+/// whole seconds. Negative durations count as zero.
+pub fn format_duration(remaining: TimeDelta) -> String {
+    let seconds = remaining.num_seconds().max(0);
+    let (days, hours, minutes, seconds) = (
+        seconds / 86400,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+    if days > 0 {
+        format!("{}d {:02}h {:02}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Shades of a heatmap cell by count, from GitHub's contribution graph.
+fn heatmap_cell(count: u32) -> ColoredString {
+    match count {
+        0 => "·".dimmed(),
+        1 => "■".truecolor(14, 68, 41),
+        2 => "■".truecolor(0, 109, 50),
+        3 | 4 => "■".truecolor(38, 166, 65),
+        _ => "■".truecolor(57, 211, 83),
+    }
+}
+
+/// A calendar of `counts` over the `weeks` weeks up to `today`, a column per
+/// week from Monday to Sunday with the months above, like GitHub's
+/// contribution graph.
+pub fn heatmap(counts: &BTreeMap<NaiveDate, u32>, today: NaiveDate, weeks: u32) -> String {
+    let start = today
+        - TimeDelta::days(today.weekday().num_days_from_monday() as i64)
+        - TimeDelta::weeks(weeks.saturating_sub(1) as i64);
+    let mondays = (0..weeks)
+        .map(|week| start + TimeDelta::weeks(week as i64))
+        .collect::<Vec<_>>();
+    // a month is named above the first week starting in it, if it fits
+    let mut months = String::from("    ");
+    for (i, monday) in mondays.iter().enumerate() {
+        let column = 4 + i * 2;
+        let new_month = i == 0 || monday.month() != mondays[i - 1].month();
+        if new_month && display_width(&months) <= column {
+            months = pad(&months, column, Align::Left);
+            months.push_str(&monday.format(&tr!("heatmap-month")).to_string());
+        }
+    }
+    let mut lines = vec![months.trim_end().to_string()];
+    let labels = [
+        tr!("weekday-monday"),
+        tr!("weekday-wednesday"),
+        tr!("weekday-friday"),
+    ];
+    for row in 0..7 {
+        let label = match row {
+            0 | 2 | 4 => labels[row / 2].as_str(),
+            _ => "",
+        };
+        let mut line = pad(label, 4, Align::Left);
+        for monday in &mondays {
+            let date = *monday + TimeDelta::days(row as i64);
+            if date > today {
+                break;
+            }
+            let count = counts.get(&date).copied().unwrap_or_default();
+            line.push_str(&format!("{} ", heatmap_cell(count)));
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines.push(format!(
+        "    {} {} {}",
+        tr!("heatmap-less"),
+        [0, 1, 2, 3, 5]
+            .map(|count| heatmap_cell(count).to_string())
+            .join(" "),
+        tr!("heatmap-more")
+    ));
+    lines.join("\n")
+}
+
+pub fn no_credentials_found() -> String {
+    tr!("no-credentials")
+}
+
+pub fn no_manifest_found() -> String {
+    tr!("no-manifest")
+}
+
+pub fn no_last_problem_found() -> String {
+    tr!("no-last-problem")
+}
+
+/// `label: value` lines with the values aligned, whatever the language of
+/// the labels.
+fn write_fields(f: &mut std::fmt::Formatter<'_>, fields: &[(String, String)]) -> std::fmt::Result {
+    let width = fields
+        .iter()
+        .map(|(label, _)| display_width(label))
+        .max()
+        .unwrap_or_default();
+    for (label, value) in fields {
+        writeln!(f, "{} {}", pad(label, width, Align::Left), value)?;
+    }
+    Ok(())
+}
 
-pub const NO_CREDENTIALS_FOUND: &str =
-    "No user credentials found. Please run `openjudge-cli credentials` first.";
-pub const NO_LAST_PROBLEM_FOUND: &str =
-    "Do not have a record of the last operated problem. Please specify a problem URL.";
+impl Display for Styled<'_, User> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_fields(
+            f,
+            &[
+                (tr!("user-id"), self.0.id.bold().to_string()),
+                (tr!("user-name"), self.0.username.bold().to_string()),
+                (tr!("user-sex"), self.0.sex.bold().to_string()),
+                (tr!("user-school"), self.0.school.bold().to_string()),
+                (
+                    tr!("user-registered"),
+                    self.0.register_time.bold().to_string(),
+                ),
+            ],
+        )
+    }
+}
 
-impl Display for User {
+impl Display for Styled<'_, ProblemLimits> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "ID:              {}", self.id.bold())?;
-        writeln!(f, "Username:        {}", self.username.bold())?;
-        writeln!(f, "Sex:             {}", self.sex.bold())?;
-        writeln!(f, "School:          {}", self.school.bold())?;
-        writeln!(f, "Registered time: {}", self.register_time.bold())?;
-        Ok(())
+        let mut parts = Vec::new();
+        if let Some(total_time) = self.0.total_time {
+            parts.push(format!("{} {}ms", tr!("limit-time").bold(), total_time));
+        }
+        if let Some(case_time) = self.0.case_time {
+            parts.push(format!("{} {}ms", tr!("limit-case").bold(), case_time));
+        }
+        if let Some(memory) = self.0.memory {
+            parts.push(format!("{} {}kB", tr!("limit-memory").bold(), memory));
+        }
+        if self.0.special_judge {
+            parts.push(tr!("special-judge").cyan().bold().to_string());
+        }
+        write!(f, "{}", parts.join("  "))
     }
 }
 
-impl Display for Problem {
+impl Display for Styled<'_, Problem> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{}/{}\n", self.group, self.probset.bold())?;
-        writeln!(f, "{}\n", self.title.black().on_yellow().bold())?;
-        writeln!(f, "{}\n", self.description)?;
-        if let Some(ref input) = self.input {
-            writeln!(f, "{}", "Input".yellow().bold())?;
+        writeln!(f, "{}/{}\n", self.0.group, self.0.probset.bold())?;
+        writeln!(f, "{}\n", self.0.title.black().on_yellow().bold())?;
+        let limits = Styled(&self.0.limits).to_string();
+        if !limits.is_empty() {
+            writeln!(f, "{}\n", limits)?;
+        }
+        writeln!(f, "{}\n", self.0.description)?;
+        if let Some(ref input) = self.0.input {
+            writeln!(f, "{}", section_heading(tr!("section-input")))?;
             writeln!(f, "{}\n", input)?;
         }
-        if let Some(ref output) = self.output {
-            writeln!(f, "{}", "Output".yellow().bold())?;
+        if let Some(ref output) = self.0.output {
+            writeln!(f, "{}", section_heading(tr!("section-output")))?;
             writeln!(f, "{}\n", output)?;
         }
-        if let Some(ref sample_input) = self.sample_input {
-            writeln!(f, "{}", "Sample Input".yellow().bold())?;
+        if let Some(ref sample_input) = self.0.sample_input {
+            writeln!(f, "{}", section_heading(tr!("section-sample-input")))?;
             writeln!(f, "{}\n", sample_input)?;
         }
-        if let Some(ref sample_output) = self.sample_output {
-            writeln!(f, "{}", "Sample Output".yellow().bold())?;
+        if let Some(ref sample_output) = self.0.sample_output {
+            writeln!(f, "{}", section_heading(tr!("section-sample-output")))?;
             writeln!(f, "{}\n", sample_output)?;
         }
-        if let Some(ref hint) = self.hint {
-            writeln!(f, "{}", "Hint".yellow().bold())?;
+        if let Some(ref hint) = self.0.hint {
+            writeln!(f, "{}", section_heading(tr!("section-hint")))?;
             writeln!(f, "{}\n", hint)?;
         }
-        if let Some(ref source) = self.source {
-            writeln!(f, "{}", "Source".yellow().bold())?;
+        if let Some(ref source) = self.0.source {
+            writeln!(f, "{}", section_heading(tr!("section-source")))?;
             writeln!(f, "{}\n", source)?;
         }
         Ok(())
     }
 }
 
-impl Display for ProblemSearchResult {
+/// Heading of a part of a problem, spelled out for screen readers.
+fn section_heading(name: String) -> String {
+    if screen_reader() {
+        tr!("section-heading", name = name)
+    } else {
+        name.yellow().bold().to_string()
+    }
+}
+
+/// Number of a problem in a list, colored by whether it is solved, which
+/// is spelled out for screen readers.
+fn problem_number(number: String, solved: Option<bool>) -> String {
+    if screen_reader() {
+        return match solved {
+            Some(true) => format!("{} ({})", number, tr!("problem-solved")),
+            Some(false) => format!("{} ({})", number, tr!("problem-attempted")),
+            None => number,
+        };
+    }
+    match solved {
+        Some(true) => number.blue().bold(),
+        Some(false) => number.yellow().bold(),
+        None => number.bold(),
+    }
+    .to_string()
+}
+
+/// Title, limits and the first paragraph of the description of a raw (HTML)
+/// problem, short enough to be shown beside a list.
+pub fn problem_preview(problem: &Problem) -> String {
+    let html = scraper::Html::parse_fragment(&problem.description);
+    let paragraph_selector = scraper::Selector::parse("p").unwrap();
+    let paragraph = html
+        .select(&paragraph_selector)
+        .map(|p| p.text().collect::<String>())
+        .find(|text| !text.trim().is_empty())
+        .unwrap_or_else(|| html.root_element().text().collect());
+    let mut preview = format!("{}\n\n", problem.title.bold());
+    let limits = Styled(&problem.limits).to_string();
+    if !limits.is_empty() {
+        preview.push_str(&limits.replace("  ", "\n"));
+        preview.push_str("\n\n");
+    }
+    preview.push_str(&paragraph.split_whitespace().collect::<Vec<_>>().join(" "));
+    preview
+}
+
+impl Display for Styled<'_, ProblemSearchResult> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(
             f,
             "#{} {} {}/{}",
-            self.problem_number,
-            self.title.yellow().bold(),
-            self.group,
-            self.probset.bold()
+            self.0.problem_number,
+            self.0.title.yellow().bold(),
+            self.0.group,
+            self.0.probset.bold()
         )?;
-        writeln!(f, "{}", self.url.blue().underline().bold())?;
+        writeln!(f, "{}", self.0.url.blue().underline().bold())?;
         writeln!(
             f,
-            "{}/Submissions: {}/{}",
+            "{}/{}: {}/{}",
             "AC".blue(),
-            self.accepted_cnt.to_string().blue(),
-            self.submission_cnt
+            tr!("label-submissions"),
+            self.0.accepted_cnt.to_string().blue(),
+            self.0.submission_cnt
         )?;
         Ok(())
     }
 }
 
-impl Display for Submission {
+impl Display for Styled<'_, Submission> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match &self.result {
-            SubmissionResult::Accepted => {
-                writeln!(f, "{}", "Accepted!".blue().bold())?;
-            }
-            SubmissionResult::CompileError { message } => {
-                writeln!(f, "{}", "Compile Error.".green().bold())?;
-                writeln!(
-                    f,
-                    "\n{}\n{}\n",
-                    "Compiler Diagnostics:".green(),
-                    message
-                        .as_ref()
-                        .map(|v| v.as_str())
-                        .unwrap_or("No message provided.")
-                )?;
-            }
-            _ => {
-                writeln!(
-                    f,
-                    "{}",
-                    match self.result {
-                        SubmissionResult::WrongAnswer => "Wrong Answer.",
-                        SubmissionResult::TimeLimitExceeded => "Time Limit Exceeded.",
-                        SubmissionResult::MemoryLimitExceeded => "Memory Limit Exceeded.",
-                        SubmissionResult::RuntimeError => "Runtime Error.",
-                        SubmissionResult::OutputLimitExceeded => "Output Limit Exceeded.",
-                        SubmissionResult::PresentationError => "Presentation Error.",
-                        _ => "Unknown error.",
-                    }
-                    .red()
-                    .bold()
-                )?;
-            }
+        writeln!(f, "{}", verdict_heading(&self.0.result))?;
+        if let SubmissionResult::CompileError { message } = &self.0.result {
+            writeln!(
+                f,
+                "\n{}\n{}\n",
+                tr!("compiler-diagnostics").green(),
+                message
+                    .as_ref()
+                    .map(|message| render_diagnostics(message, &self.0.code))
+                    .unwrap_or_else(|| tr!("no-message"))
+            )?;
         }
-        writeln!(f, "#{}", self.id.white().bold())?;
-        writeln!(f, "Author:      {}", self.author.white().bold())?;
-        writeln!(f, "Lang:        {}", self.lang.white().bold())?;
-        if let Some(time) = &self.time {
-            writeln!(f, "Time:        {}", time.white().bold())?;
+        writeln!(f, "#{}", self.0.id.white().bold())?;
+        let mut fields = vec![
+            (tr!("submission-author"), self.0.author.white().bold()),
+            (tr!("submission-lang"), self.0.lang.white().bold()),
+        ];
+        if let Some(time) = &self.0.time {
+            fields.push((tr!("submission-time"), time.white().bold()));
         }
-        if let Some(memory) = &self.memory {
-            writeln!(f, "Memory:      {}", memory.white().bold())?;
+        if let Some(memory) = &self.0.memory {
+            fields.push((tr!("submission-memory"), memory.white().bold()));
         }
-        writeln!(f, "Submit Time: {}", self.submission_time.white().bold())?;
-        Ok(())
+        if let Some(submission_time) = &self.0.submission_time {
+            fields.push((
+                tr!("submission-submit-time"),
+                format_time(submission_time).white().bold(),
+            ));
+        }
+        let fields = fields
+            .into_iter()
+            .map(|(label, value)| (label, value.to_string()))
+            .collect::<Vec<_>>();
+        write_fields(f, &fields)
+    }
+}
+
+/// How verdicts are shown, see [`verdict_label`].
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum VerdictStyle {
+    /// Colored words, e.g. "Wrong Ans." in red.
+    #[default]
+    Words,
+    /// An emoji with the abbreviation, e.g. "❌ WA".
+    Emoji,
+    /// Uncolored tags, e.g. "[WA]", for monochrome terminals.
+    Ascii,
+}
+
+impl FromStr for VerdictStyle {
+    type Err = anyhow::Error;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "w" | "words" => Ok(VerdictStyle::Words),
+            "e" | "emoji" => Ok(VerdictStyle::Emoji),
+            "a" | "ascii" => Ok(VerdictStyle::Ascii),
+            _ => Err(anyhow::format_err!(
+                "Invalid value for VerdictStyle: {}",
+                value
+            )),
+        }
+    }
+}
+
+/// Every way a verdict can be shown.
+struct VerdictNames {
+    abbreviation: &'static str,
+    emoji: &'static str,
+    short: String,
+    long: String,
+    color: Color,
+}
+
+fn verdict_names(result: &SubmissionResult) -> VerdictNames {
+    let (abbreviation, emoji, id, color) = match result {
+        SubmissionResult::Accepted => ("AC", "✅", "accepted", Color::Blue),
+        SubmissionResult::CompileError { .. } => ("CE", "🔧", "compile-error", Color::Green),
+        SubmissionResult::WrongAnswer => ("WA", "❌", "wrong-answer", Color::Red),
+        SubmissionResult::TimeLimitExceeded => ("TLE", "⌛", "time-limit-exceeded", Color::Red),
+        SubmissionResult::MemoryLimitExceeded => ("MLE", "💾", "memory-limit-exceeded", Color::Red),
+        SubmissionResult::RuntimeError => ("RE", "💥", "runtime-error", Color::Red),
+        SubmissionResult::OutputLimitExceeded => ("OLE", "📜", "output-limit-exceeded", Color::Red),
+        SubmissionResult::PresentationError => ("PE", "📐", "presentation-error", Color::Red),
+        _ => ("??", "❓", "unknown-error", Color::Red),
+    };
+    let short = translate(&format!("verdict-{}", id), None);
+    let long = translate(&format!("verdict-{}-long", id), None);
+    VerdictNames {
+        abbreviation,
+        emoji,
+        short,
+        long,
+        color,
+    }
+}
+
+/// Short verdict label used in lists.
+pub fn verdict_label(result: &SubmissionResult) -> ColoredString {
+    let names = verdict_names(result);
+    if screen_reader() {
+        return names.long.normal();
+    }
+    match display_options().verdict_style {
+        VerdictStyle::Words => names.short.as_str().color(names.color).bold(),
+        VerdictStyle::Emoji => format!("{} {}", names.emoji, names.abbreviation).bold(),
+        VerdictStyle::Ascii => format!("[{}]", names.abbreviation).normal(),
     }
 }
 
-impl Display for SubmissionHistoryEntry {
+/// Verdict heading of a submission.
+pub fn verdict_heading(result: &SubmissionResult) -> ColoredString {
+    let names = verdict_names(result);
+    if screen_reader() {
+        return tr!("verdict-heading", verdict = names.long).normal();
+    }
+    match display_options().verdict_style {
+        VerdictStyle::Words => names.long.as_str().color(names.color).bold(),
+        VerdictStyle::Emoji => format!("{} {}", names.emoji, names.long).bold(),
+        VerdictStyle::Ascii => format!("[{}] {}", names.abbreviation, names.long).normal(),
+    }
+}
+
+impl Display for Styled<'_, SubmissionHistoryEntry> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let result = match &self.result {
-            SubmissionResult::Accepted => "Accepted".blue().bold(),
-            SubmissionResult::CompileError { .. } => "Comp. Err.".green().bold(),
-            SubmissionResult::WrongAnswer => "Wrong Ans.".red().bold(),
-            SubmissionResult::TimeLimitExceeded => "Time Lim. Ex.".red().bold(),
-            SubmissionResult::MemoryLimitExceeded => "Mem. Lim. Ex.".red().bold(),
-            SubmissionResult::RuntimeError => "Runtime Err.".red().bold(),
-            SubmissionResult::OutputLimitExceeded => "Out. Lim. Ex.".red().bold(),
-            SubmissionResult::PresentationError => "Present. Err.".red().bold(),
-            _ => "Unknown Err.".red().bold(),
-        };
         write!(
             f,
-            "{:<13} {} {}",
-            result,
-            self.time,
-            self.url.blue().underline()
+            "{} {} {}",
+            pad(&verdict_label(&self.0.result).to_string(), 13, Align::Left),
+            format_time(&self.0.time),
+            self.0.url.blue().underline()
         )?;
         Ok(())
     }
 }
 
-impl Display for Group {
+impl Display for Styled<'_, Vec<SubmissionHistoryEntry>> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{}", self.name.bold())?;
-        writeln!(f, "{}", self.url.blue().underline())?;
-        writeln!(f, "{}", self.description)?;
+        let mut table = Table::new([tr!("column-result"), tr!("column-time"), "URL".to_string()]);
+        for submission in self.0 {
+            table.push_row([
+                verdict_label(&submission.result).to_string(),
+                format_time(&submission.time),
+                submission.url.blue().underline().to_string(),
+            ]);
+        }
+        write!(f, "{}", table)
+    }
+}
+
+impl Display for Styled<'_, Group> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.0.name.bold())?;
+        writeln!(f, "{}", self.0.url.blue().underline())?;
+        writeln!(f, "{}", self.0.description)?;
         writeln!(f)?;
-        if self.probsets.is_empty() {
-            writeln!(f, "No problem sets found.")?;
+        if self.0.probsets.is_empty() {
+            writeln!(f, "{}", tr!("no-probsets"))?;
         } else {
             writeln!(
                 f,
-                "Contains {} problem sets:",
-                self.probsets.len().to_string().bold()
+                "{}",
+                tr!(
+                    "contains-probsets",
+                    count = self.0.probsets.len().to_string().bold().to_string()
+                )
             )?;
-            for probset in &self.probsets {
-                writeln!(f, "{}", probset)?;
+            for probset in &self.0.probsets {
+                writeln!(f, "{}", Styled(probset))?;
             }
         }
         Ok(())
     }
 }
 
-impl Display for ProblemSetEntry {
+impl Display for Styled<'_, ProblemSetEntry> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{} {}", self.name.bold(), self.url.blue().underline())?;
+        writeln!(
+            f,
+            "{} {}",
+            self.0.name.bold(),
+            self.0.url.blue().underline()
+        )?;
         Ok(())
     }
 }
 
-impl Display for ProblemListEntry {
+impl Display for Styled<'_, ProblemListEntry> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(
             f,
             "{} {} {}",
-            match self.solved {
-                Some(true) => ("#".to_owned() + &self.problem_number).blue().bold(),
-                Some(false) => ("#".to_owned() + &self.problem_number).yellow().bold(),
-                None => ("#".to_owned() + &self.problem_number).bold(),
-            },
-            self.title.yellow().bold(),
-            self.url.blue().underline()
+            problem_number(format!("#{}", self.0.problem_number), self.0.solved),
+            self.0.title.yellow().bold(),
+            self.0.url.blue().underline()
         )?;
         write!(
             f,
-            "- {}/Submitters: {}/{}",
+            "- {}/{}: {}/{}",
             "AC".blue(),
-            self.accepted_population.to_string().blue(),
-            self.submitters
+            tr!("column-submitters"),
+            self.0.accepted_population.to_string().blue(),
+            self.0.submitters
         )?;
         Ok(())
     }
 }
 
-impl Display for ProblemSetPartial {
+impl Display for Styled<'_, ProblemSetPartial> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{}/{}", self.group_name, self.name.bold())?;
-        writeln!(f, "{}\n", self.url.blue().underline())?;
-        if self.max_page != 1 {
-            writeln!(
-                f,
-                "Displaying page {} of {}\n",
-                self.page.to_string().bold(),
-                self.max_page.to_string().bold()
-            )?;
+        writeln!(f, "{}/{}", self.0.group_name, self.0.name.bold())?;
+        writeln!(f, "{}\n", self.0.url.blue().underline())?;
+        if self.0.max_page != 1 {
+            writeln!(f, "{}\n", page_of(self.0.page, self.0.max_page))?;
         }
-        for problem in &self.problems {
-            writeln!(f, "{}", problem)?;
+        let mut table = Table::new([
+            "#".to_string(),
+            tr!("column-title"),
+            "AC".to_string(),
+            tr!("column-submitters"),
+            "URL".to_string(),
+        ])
+        .align_right(2)
+        .align_right(3);
+        for problem in &self.0.problems {
+            table.push_row([
+                problem_number(problem.problem_number.clone(), problem.solved),
+                problem.title.yellow().bold().to_string(),
+                problem.accepted_population.to_string().blue().to_string(),
+                problem.submitters.to_string(),
+                problem.url.blue().underline().to_string(),
+            ]);
         }
-        if self.max_page != 1 {
-            writeln!(
-                f,
-                "Displaying page {} of {}",
-                self.page.to_string().bold(),
-                self.max_page.to_string().bold()
-            )?;
+        write!(f, "{}", table)?;
+        if self.0.max_page != 1 {
+            writeln!(f, "{}", page_of(self.0.page, self.0.max_page))?;
+        }
+        Ok(())
+    }
+}
+
+/// "Displaying page 2 of 5", with the numbers in bold.
+pub fn page_of(page: u32, max_page: u32) -> String {
+    tr!(
+        "page-of",
+        page = page.to_string().bold().to_string(),
+        max = max_page.to_string().bold().to_string()
+    )
+}
+
+impl Display for Styled<'_, Standings> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}/{}", self.0.group_name, self.0.name.bold())?;
+        writeln!(f, "{}\n", self.0.url.blue().underline())?;
+        let mut table = Table::new(
+            [
+                tr!("column-rank"),
+                tr!("column-user"),
+                tr!("column-solved"),
+                tr!("column-penalty"),
+            ]
+            .into_iter()
+            .chain(self.0.problems.iter().cloned()),
+        )
+        .align_right(0)
+        .align_right(2)
+        .align_right(3);
+        for row in &self.0.rows {
+            let cells = row.cells.iter().map(|cell| match cell {
+                None => String::new(),
+                Some(cell) if cell.accepted => match cell.failed_attempts {
+                    0 => "+".blue().bold().to_string(),
+                    n => format!("+{}", n).blue().bold().to_string(),
+                },
+                Some(cell) => format!("-{}", cell.failed_attempts).red().to_string(),
+            });
+            table.push_row(
+                [
+                    row.rank.to_string(),
+                    row.user.clone(),
+                    row.solved.to_string(),
+                    row.penalty.clone().unwrap_or_default(),
+                ]
+                .into_iter()
+                .chain(cells),
+            );
+        }
+        write!(f, "{}", table)?;
+        if self.0.max_page != 1 {
+            writeln!(f, "{}", page_of(self.0.page, self.0.max_page))?;
         }
         Ok(())
     }
@@ -0,0 +1,147 @@
+use std::{
+    future::Future,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow};
+use libopenjudge::Scheme;
+use tokio::net::{TcpStream, lookup_host};
+
+/// Time after which a stage is given up on.
+const STAGE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A stage of fetching a page, in the order they happen.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Stage {
+    Dns,
+    Connect,
+    Fetch,
+}
+
+impl Stage {
+    pub fn name(self) -> String {
+        match self {
+            Stage::Dns => tr!("stage-dns"),
+            Stage::Connect => tr!("stage-connect"),
+            Stage::Fetch => tr!("stage-fetch"),
+        }
+    }
+
+    /// Duration above which the stage is considered slow. A page fetch
+    /// includes its own connection and TLS handshake, so it gets more.
+    fn slow_after(self) -> Duration {
+        match self {
+            Stage::Dns => Duration::from_millis(500),
+            Stage::Connect => Duration::from_millis(500),
+            Stage::Fetch => Duration::from_secs(3),
+        }
+    }
+}
+
+/// Timings of each stage for one host. A stage is `None` when it was not
+/// reached, and `Err` when it failed or timed out.
+pub struct HostReport {
+    pub host: String,
+    pub address: Option<SocketAddr>,
+    pub dns: Option<Result<Duration>>,
+    pub connect: Option<Result<Duration>>,
+    pub fetch: Option<Result<Duration>>,
+}
+
+impl HostReport {
+    fn stages(&self) -> [(Stage, &Option<Result<Duration>>); 3] {
+        [
+            (Stage::Dns, &self.dns),
+            (Stage::Connect, &self.connect),
+            (Stage::Fetch, &self.fetch),
+        ]
+    }
+
+    /// The first stage that failed, or else the slow stage taking the most
+    /// time over its threshold.
+    pub fn problem(&self) -> Option<Stage> {
+        let stages = self.stages();
+        if let Some((stage, _)) = stages
+            .iter()
+            .find(|(_, timing)| matches!(timing, Some(Err(_))))
+        {
+            return Some(*stage);
+        }
+        stages
+            .iter()
+            .filter_map(|(stage, timing)| match timing {
+                Some(Ok(duration)) if *duration > stage.slow_after() => Some((
+                    *stage,
+                    duration.as_secs_f64() / stage.slow_after().as_secs_f64(),
+                )),
+                _ => None,
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(stage, _)| stage)
+    }
+}
+
+async fn timed<T, F>(future: F) -> Result<(T, Duration)>
+where
+    F: Future<Output = Result<T>>,
+{
+    let start = Instant::now();
+    let output = tokio::time::timeout(STAGE_TIMEOUT, future)
+        .await
+        .map_err(|_| anyhow!("timed out after {}s", STAGE_TIMEOUT.as_secs()))??;
+    Ok((output, start.elapsed()))
+}
+
+/// Measures resolving `host`, connecting to it and fetching its front page.
+/// Later stages are skipped once one fails.
+pub async fn check_host(host: &str, scheme: Scheme, user_agent: &str) -> HostReport {
+    let mut report = HostReport {
+        host: host.to_string(),
+        address: None,
+        dns: None,
+        connect: None,
+        fetch: None,
+    };
+    let port = match scheme {
+        Scheme::Https => 443,
+        Scheme::Http => 80,
+    };
+    let resolved = timed(async {
+        lookup_host((host, port))
+            .await?
+            .next()
+            .ok_or_else(|| anyhow!("no address found"))
+    })
+    .await;
+    let address = match resolved {
+        Ok((address, duration)) => {
+            report.dns = Some(Ok(duration));
+            address
+        }
+        Err(e) => {
+            report.dns = Some(Err(e));
+            return report;
+        }
+    };
+    report.address = Some(address);
+    let connected = timed(async { Ok(TcpStream::connect(address).await?) }).await;
+    report.connect = Some(connected.map(|(_, duration)| duration));
+    if matches!(report.connect, Some(Err(_))) {
+        return report;
+    }
+    // a fresh client, so the fetch does not reuse a pooled connection
+    let fetched = timed(async {
+        let client = reqwest::Client::builder().user_agent(user_agent).build()?;
+        let response = client
+            .get(format!("{}://{}/", scheme.as_str(), host))
+            .send()
+            .await?
+            .error_for_status()?;
+        response.bytes().await?;
+        Ok(())
+    })
+    .await;
+    report.fetch = Some(fetched.map(|(_, duration)| duration));
+    report
+}
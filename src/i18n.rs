@@ -0,0 +1,122 @@
+use std::{str::FromStr, sync::OnceLock};
+
+use fluent_bundle::{FluentArgs, FluentResource, concurrent::FluentBundle};
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+
+/// Language of user-facing messages.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Lang {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "zh-CN")]
+    Chinese,
+}
+
+impl FromStr for Lang {
+    type Err = anyhow::Error;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().replace('_', "-").as_str() {
+            "en" | "en-us" | "english" => Ok(Lang::English),
+            "zh" | "zh-cn" | "zh-hans" | "chinese" => Ok(Lang::Chinese),
+            _ => Err(anyhow::format_err!("Invalid value for Lang: {}", value)),
+        }
+    }
+}
+
+impl Lang {
+    /// Messages of the language, in Fluent syntax.
+    fn source(self) -> &'static str {
+        match self {
+            Lang::English => include_str!("locales/en.ftl"),
+            Lang::Chinese => include_str!("locales/zh-CN.ftl"),
+        }
+    }
+
+    fn id(self) -> LanguageIdentifier {
+        match self {
+            Lang::English => "en-US",
+            Lang::Chinese => "zh-CN",
+        }
+        .parse()
+        .unwrap()
+    }
+}
+
+/// The language of a POSIX or BCP 47 locale such as "zh_CN.UTF-8", English
+/// unless it is Chinese.
+pub fn lang_from_locale(locale: &str) -> Lang {
+    let language = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default();
+    if language.eq_ignore_ascii_case("zh") {
+        Lang::Chinese
+    } else {
+        Lang::English
+    }
+}
+
+/// The language of the system locale.
+pub fn detect_lang() -> Lang {
+    sys_locale::get_locale()
+        .map(|locale| lang_from_locale(&locale))
+        .unwrap_or_default()
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// Sets the language of messages, only the first call has an effect.
+pub fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+pub fn lang() -> Lang {
+    *LANG.get_or_init(Lang::default)
+}
+
+fn bundle(lang: Lang) -> FluentBundle<FluentResource> {
+    let resource =
+        FluentResource::try_new(lang.source().to_string()).unwrap_or_else(|(resource, _)| resource);
+    let mut bundle = FluentBundle::new_concurrent(vec![lang.id()]);
+    // the isolation marks around arguments show up as garbage in terminals
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .expect("message ids are unique");
+    bundle
+}
+
+/// The message `id` in the current language, falling back to English for
+/// messages not translated yet.
+pub fn translate(id: &str, args: Option<&FluentArgs>) -> String {
+    static BUNDLES: OnceLock<(FluentBundle<FluentResource>, FluentBundle<FluentResource>)> =
+        OnceLock::new();
+    let (current, fallback) = BUNDLES.get_or_init(|| (bundle(lang()), bundle(Lang::English)));
+    [current, fallback]
+        .into_iter()
+        .find_map(|bundle| {
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = vec![];
+            Some(
+                bundle
+                    .format_pattern(pattern, args, &mut errors)
+                    .into_owned(),
+            )
+        })
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Looks up a message in the current language, with optional named
+/// arguments: `tr!("found-results", count = 3)`.
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::translate($id, None)
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::translate($id, Some(&args))
+    }};
+}
@@ -0,0 +1,419 @@
+# Messages of the CLI, see src/i18n.rs. Every message here should also be
+# translated in the other files of this directory.
+
+## Errors
+
+no-credentials = No user credentials found. Please run `oj credentials` first.
+no-manifest = No manifest found. Please create an `oj.yaml` in the current directory first.
+no-last-problem = Do not have a record of the last operated problem. Please specify a problem URL.
+no-source-file = No source file specified for { $url }.
+no-samples = No sample input/output found for problem.
+warning = Warning:
+experimental-command = this command is experimental, the OpenJudge pages it uses have not been checked against a real group admin session.
+duplicate-submission = { $file } was already submitted to { $url }.
+resubmit-prompt = Submit it again? [y/N]
+duplicate-skipped = Skipped it, pass --force to submit it again.
+nothing-submitted = Nothing was submitted.
+cpp-std-differs = compiling with { $cpp_std } while OpenJudge uses { $judge_std }, newer features may not compile when submitted.
+file-not-found = File does not exist.
+not-a-file = Path is not a file.
+reading-file = Reading { $path }
+writing-file = Writing { $path }
+invalid-language = Invalid language. Supported values: C, GCC, C++, G++, Py, Python, Py3, Python3, PyPy, PyPy3, Pas, Pascal, FPC
+unknown-language = Source code must provide an extension of '.c', '.cpp', '.py' or '.pas', start with a shebang, or specify the language with the --lang flag.
+invalid-extension = Invalid file extension. Supported values: '.c', '.cpp', '.py', '.pas', or specify the language with the --lang flag.
+recent-out-of-range = Only { $count } recent problems are remembered, see `oj recent`.
+no-such-alias = No alias @{ $name } defined. Add one with `oj alias set { $name } URL`.
+invalid-alias-name = Invalid alias name "{ $name }", use letters, digits, - and _.
+page-saved = The page has been saved to { $path }.
+parsing-file = Parsing { $path }
+image-linked-instead = Cannot fetch image { $url }, it is linked instead.
+bundle-missing-file = { $bundle } has no { $file }.
+bundle-without-url = { $bundle } does not name the problem it was exported from, pass it with --url.
+hook-failed = Pre-submit hook `{ $command }` failed with exit code { $code }. Pass --no-verify to submit anyway.
+cannot-load-problem = Cannot load problem { $url }
+no-manifest-file = No source file mapped for { $key } in manifest.
+no-problem-specified = No problem specified.
+not-a-manifest-key = No source file specified, and { $key } is not a key in the manifest.
+source-not-found = { $file } does not exist, pass --create to create it.
+running-editor = Running editor `{ $editor }`
+editor-failed = `{ $editor }` exited with code { $code }.
+undeclared-language = Language { $name } is not declared in the config.
+no-test-data = No test data found in { $dir }.
+no-desktop-notifications = Desktop notifications are not supported on this platform.
+probset-empty = { $group }/{ $probset } has no problems.
+join-requests-failed = { $failed } of { $total } join requests could not be processed.
+no-unsolved-problem = No unsolved problem left in { $group }/{ $probset }.
+no-friends = No friends configured. Add them with `oj config --add-friend ID`.
+error = Error
+alias-not-in-config = No alias @{ $name } in the config.
+no-such-command = No command #{ $n } in the history.
+command-failed = `{ $command }` exited with code { $code }.
+invalid-time-format = Invalid time format "{ $format }".
+invalid-cpp-std = Invalid C++ standard "{ $cpp_std }", expected e.g. c++17 or gnu++14.
+invalid-image-rows = Image height must be at least one row.
+invalid-concurrency = At least one request must be in flight.
+invalid-duration = Invalid duration "{ $duration }", expected e.g. 10m or 1h30m.
+invalid-image-size = Invalid image size "{ $size }", expected WIDTHxHEIGHT.
+not-a-problem-page = { $url } is not a problem page.
+unknown-section = Unknown section "{ $name }".
+spec-no-title = The problem has no title.
+spec-no-description = The problem has no description.
+not-a-backup = { $path } is not a backup archive
+unsafe-backup-path = Unsafe path { $path } in backup.
+backup-target-exists = { $path } already exists, pass --force to overwrite the current state.
+replacing-file = Replacing { $path }
+fetching-latest-release = Fetching the latest release
+no-prebuilt-binary = No prebuilt binary for this platform ({ $name }).
+no-release-checksum = The release has no checksum for { $name }.
+checksum-mismatch = Checksum mismatch for { $name }: expected { $expected }, got { $actual }. The executable was not replaced.
+
+## Progress
+
+logging-in = Logging in
+validating-credentials = Validating credentials with OpenJudge
+fetching-problem = Fetching problem details
+fetching-problems = Fetching problems
+fetching-probsets = Fetching probsets
+fetching-user = Fetching user details
+fetching-submission = Fetching submission details
+fetching-submissions = Fetching submissions
+fetching-group = Fetching group
+fetching-standings = Fetching standings
+fetching-image = Fetching image
+fetching-board = Fetching the discussion board
+fetching-posts = Fetching posts
+downloading = Downloading { $file }
+searching = Searching for { $query } in group { $group }
+rendering = Rendering { $title }
+running-hook = Running hook
+checking-problems = Checking problems
+submitting = Submitting solution of { $url }
+waiting-for-verdict = Waiting for judgement
+joining-group = Joining group { $group }
+creating-problem = Creating problem { $title }
+uploading-test-data = { $count ->
+    [one] Uploading { $count } test data file
+   *[other] Uploading { $count } test data files
+}
+reminding-in = Reminding in { $remaining } ({ $before } before the end of { $name })
+fetching-join-requests = Fetching join requests
+approving-join-request = Approving join request { $id }
+rejecting-join-request = Rejecting join request { $id }
+fetching-problems-so-far = Fetching problems, { $count } so far
+fetching-users = { $count ->
+    [one] Fetching { $count } user
+   *[other] Fetching { $count } users
+}
+fetching-user-id = Fetching user { $id }
+measuring-network = Measuring network stages
+running = Running
+fetching-submission-count = { $count ->
+    [one] Fetching { $count } submission
+   *[other] Fetching { $count } submissions
+}
+loading-problems = Loading problems…
+loading-page = Loading page…
+loading-problem = Loading problem…
+loading-submissions = Loading submissions…
+loading-submission = Loading submission…
+checking-for-updates = Checking for updates
+downloading-checksums = Downloading checksums
+
+## Results
+
+enter-password = Enter your password:
+credentials-saved = Credentials saved.
+found-results = Found { $count } results:
+found-problems = Found { $count } problems.
+no-problem-selected = No problem selected.
+no-submissions = No submissions found.
+no-pending-submissions = No submissions waiting for a verdict.
+check-retrying = { $error } (retrying)
+no-probsets = No problem sets found.
+no-topics = No topics on the discussion board.
+topic-replies = { $count ->
+    [one] ({ $count } reply)
+   *[other] ({ $count } replies)
+}
+contains-probsets = Contains { $count } problem sets:
+page-of = Displaying page { $page } of { $max }
+bundle-saved = Saved { $title } with { $count ->
+    [one] { $count } image
+   *[other] { $count } images
+} to { $file }.
+bundle-imported = Imported { $title } as { $url }, `oj view problem` and `oj test` now work offline for it.
+submission-created = Submission created at { $url }
+monitor-hint = Run `oj monitor` to follow the verdicts.
+file-created = Created { $file }.
+step-position = Problem { $current } of { $total }.
+step-choices = [n]ext, [p]revious or [q]uit
+step-choices-last = [p]revious or [q]uit
+preview-failed = Failed to load preview: { $error }
+code-heading = Code
+line-count = { $count ->
+    [one] { $count } line
+   *[other] { $count } lines
+}
+copied-with = { $count ->
+    [one] Copied { $count } line to the clipboard with { $command }.
+   *[other] Copied { $count } lines to the clipboard with { $command }.
+}
+copied-by-terminal = { $count ->
+    [one] Asked the terminal to copy { $count } line to the clipboard.
+   *[other] Asked the terminal to copy { $count } lines to the clipboard.
+}
+found-submissions = Found { $count } submissions:
+join-request-sent = Request sent.
+problem-created = Created problem { $title }: { $url }
+test-data-uploaded = { $count ->
+    [one] Uploaded { $count } test data file.
+   *[other] Uploaded { $count } test data files.
+}
+no-contest = No contest in progress in group { $group }.
+contest-upcoming = No contest in progress. { $name } starts in { $duration } ({ $time }).
+contest-remaining = { $duration } left, ends at { $time }
+contest-ends-in = { $name } ends in { $duration }
+notify-at = You will be notified at { $time }.
+manifest-written = { $count ->
+    [one] Wrote { $count } problem to { $path }.
+   *[other] Wrote { $count } problems to { $path }.
+}
+no-join-requests = No pending join requests.
+join-request-approved = Approved
+join-request-rejected = Rejected
+failed = Failed
+rows-exported = Exported { $count } rows to { $path }
+stage-dns = DNS resolution
+stage-connect = TCP connect
+stage-fetch = page fetch
+stage-slow = { $stage } is slow or failing for { $host }.
+host-fine = { $host } looks fine.
+backed-up = Backed up { $count } files to { $file }.
+backup-keyring-note = Passwords stay in the system keyring, run `oj credentials` again after restoring.
+restored = Restored { $count } files from { $file }.
+alias-removed = Removed { $name }.
+no-aliases = No aliases defined. Add one with `oj alias set NAME URL`.
+alias-source-config = config
+no-recent-problems = No problems operated yet.
+no-history = No commands run yet.
+command-ok = ok
+no-problems-viewed = No problems viewed yet.
+stats-viewed = Viewed { $viewed } problems, solved { $solved }.
+stats-times = Time to solve: median { $median }, average { $average }, fastest { $fastest }, slowest { $slowest }.
+stats-streaks = Current streak: { $current } days, longest: { $longest } days.
+breakdown-summary = { $submissions } submissions to { $problems } problems.
+up-to-date = Already up to date ({ $version }).
+new-version-available = New version available: { $version } (current { $current }).
+updated-to = Updated { $path } to { $version }.
+
+## Local tests
+
+testing-solution = Testing solution { $file } of problem { $problem }
+case-input = Case Input:
+expected-output = Expected Output:
+your-output = Your Output:
+diff = Diff:
+exit-code = Exit Code:
+signal = Signal:
+sample-case = sample
+case-heading = Case { $name }
+cases-passed = Passed { $passed } of { $total } cases.
+invalid-case = Invalid case { $case }, expected <input file>:<expected output file>.
+no-expected-output = { $input } has no expected output { $output }.
+no-problem-url = no problem URL
+no-solutions-in = No solutions to test in { $dir }.
+solutions-failed = { $failed } of { $total } solutions did not pass their samples.
+solutions-passed = { $count ->
+    [one] The solution passed its samples.
+   *[other] All { $count } solutions passed their samples.
+}
+archived-to = Archived to { $path }.
+cannot-archive = Cannot archive the solution: { $error }
+warnings-blocking = { $flags } is set to block in the config, fix it before testing again.
+
+## Problems
+
+limit-time = Time Limit:
+limit-case = Per Case:
+limit-memory = Memory Limit:
+special-judge = Special Judge
+section-input = Input
+section-output = Output
+section-sample-input = Sample Input
+section-sample-output = Sample Output
+section-hint = Hint
+section-source = Source
+section-heading = Section: { $name }
+problem-solved = solved
+problem-attempted = attempted
+label-submissions = Submissions
+images-skipped = { $count ->
+    [one] { $count } image not downloaded:
+   *[other] { $count } images not downloaded:
+}
+fetch-image-prompt = Fetch an image by number, or press Enter to continue:
+no-such-image = Enter a number from 1 to { $max }.
+attachments-found = { $count ->
+    [one] { $count } attachment linked from the statement:
+   *[other] { $count } attachments linked from the statement:
+}
+attachments-prompt = Save them into a directory, or press Enter to skip:
+no-attachments = No attachments linked from the statement.
+saved-attachment = Saved { $bytes } bytes to
+select-samples = Select problems to save samples for:
+samples-skipped = { $number } { $title } has no sample input/output, skipped.
+samples-saved = Saved samples of { $number } { $title } to { $path }.
+daily-problem = Daily problem for { $date }:
+daily-solved = { $total ->
+    [one] Solved { $solved } of the last daily problem.
+   *[other] Solved { $solved } of the last { $total } daily problems.
+}
+section-description = Description
+
+## Users and submissions
+
+user-id = ID:
+user-name = Username:
+user-sex = Sex:
+user-school = School:
+user-registered = Registered time:
+submission-author = Author:
+submission-lang = Lang:
+submission-time = Time:
+submission-memory = Memory:
+submission-submit-time = Submit Time:
+compiler-diagnostics = Compiler Diagnostics:
+no-message = No message provided.
+submission-heading = Submission
+
+## Verdicts, short in lists and long in headings
+
+verdict-heading = Verdict: { $verdict }
+verdict-accepted = Accepted
+verdict-accepted-long = Accepted!
+verdict-compile-error = Comp. Err.
+verdict-compile-error-long = Compile Error.
+verdict-wrong-answer = Wrong Ans.
+verdict-wrong-answer-long = Wrong Answer.
+verdict-time-limit-exceeded = Time Lim. Ex.
+verdict-time-limit-exceeded-long = Time Limit Exceeded.
+verdict-memory-limit-exceeded = Mem. Lim. Ex.
+verdict-memory-limit-exceeded-long = Memory Limit Exceeded.
+verdict-runtime-error = Runtime Err.
+verdict-runtime-error-long = Runtime Error.
+verdict-output-limit-exceeded = Out. Lim. Ex.
+verdict-output-limit-exceeded-long = Output Limit Exceeded.
+verdict-presentation-error = Present. Err.
+verdict-presentation-error-long = Presentation Error.
+verdict-unknown-error = Unknown Err.
+verdict-unknown-error-long = Unknown error.
+verdict-waiting = Waiting
+
+## Tables
+
+column-result = Result
+column-time = Time
+column-title = Title
+column-submitters = Submitters
+column-rank = Rank
+column-user = User
+column-solved = Solved
+column-penalty = Penalty
+column-file = File
+column-problem = Problem
+column-submitted = Submitted
+column-key = Key
+not-attempted = Not Attempted
+column-samples = Samples
+file-kept = { $file } (kept)
+no-samples-short = none
+column-message = Message
+column-attempted = Attempted
+you = { $name } (you)
+column-host = Host
+column-address = Address
+column-connect = Connect
+column-fetch = Fetch
+column-alias = Alias
+column-defined-in = Defined In
+column-reference = Reference
+column-command = Command
+column-viewed = Viewed
+column-accepted = Accepted
+column-time-to-solve = Time to Solve
+column-total = Total
+column-other = Other
+column-language = Language
+column-probset = Problem Set
+
+## Times
+
+just-now = just now
+minutes-ago = { $count ->
+    [one] { $count } minute ago
+   *[other] { $count } minutes ago
+}
+hours-ago = { $count ->
+    [one] { $count } hour ago
+   *[other] { $count } hours ago
+}
+days-ago = { $count ->
+    [one] { $count } day ago
+   *[other] { $count } days ago
+}
+# chrono format of the month names above the heatmap
+heatmap-month = %b
+weekday-monday = Mon
+weekday-wednesday = Wed
+weekday-friday = Fri
+heatmap-less = Less
+heatmap-more = More
+
+## Interactive lists
+
+no-matches = No matches.
+next-page = Next page
+previous-page = Previous page
+cancelled = Cancelled
+not-logged-in = Not logged in
+status-page = Page { $page }/{ $max }
+status-pending = { $count } pending
+go-to = Go to:
+filter = Filter:
+hint-move = ↑/↓ move
+hint-page = ←/→ page
+hint-toggle = Space toggle
+hint-confirm = Enter confirm, q quit, ? help
+press-any-key = Press any key to return.
+key-move = Move up / down
+key-move-page = Move by a page
+key-first-last = Go to the first / last option
+key-page = Go to the previous / next page
+key-jump = Go to the option with the typed index
+key-filter = Filter the options by the typed text
+key-clear-filter = Clear the filter
+key-highlight = Highlight an option
+key-scroll = Scroll the list
+key-toggle-highlighted = Toggle the highlighted option
+key-toggle = Toggle an option
+key-confirm-toggled = Confirm the toggled options
+key-confirm = Confirm an option
+key-confirm-highlighted = Confirm the highlighted option
+key-cancel = Cancel
+key-help = Show this help
+loading = Loading...
+key-name-other = Other keys, /
+key-name-escape-filtering = Esc (filtering)
+key-name-click = Click
+key-name-wheel = Wheel
+key-name-double-click = Double-click
+press-enter = Press Enter to return.
+hint-help = ? help
+press-r-submissions = Press r to load your submissions.
+test-with-file = Test with file:{" "}
+submit-file = Submit file:{" "}
+tui-hints-probsets = ↑↓ move · Enter open · q quit
+tui-hints-problems = ↑↓ move · Enter open · n/p page · Esc back · q quit
+tui-hints-problem = ↑↓ scroll · Tab switch pane · t test · s submit · r submissions · Esc back · q quit
+tui-hints-submission = ↑↓ scroll · Esc back · q quit
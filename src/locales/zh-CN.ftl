@@ -0,0 +1,367 @@
+# 命令行的简体中文消息，参见 src/i18n.rs。
+
+## Errors
+
+no-credentials = 未找到用户凭据，请先运行 `oj credentials`。
+no-manifest = 未找到清单，请先在当前目录创建 `oj.yaml`。
+no-last-problem = 没有上次操作的题目记录，请指定题目链接。
+no-source-file = 未给 { $url } 指定源文件。
+no-samples = 题目没有样例输入/输出。
+warning = 警告：
+experimental-command = 此命令为实验性功能，其使用的 OpenJudge 页面尚未在真实的小组管理员会话中验证。
+duplicate-submission = { $file } 已提交过 { $url }。
+resubmit-prompt = 是否再次提交？[y/N]
+duplicate-skipped = 已跳过，如需再次提交，请加上 --force。
+nothing-submitted = 没有提交任何代码。
+cpp-std-differs = 正在以 { $cpp_std } 编译，而 OpenJudge 使用 { $judge_std }，提交时较新的特性可能无法编译。
+file-not-found = 文件不存在。
+not-a-file = 路径不是文件。
+reading-file = 读取 { $path } 时出错
+writing-file = 写入 { $path } 时出错
+invalid-language = 无效的语言。支持的值：C、GCC、C++、G++、Py、Python、Py3、Python3、PyPy、PyPy3、Pas、Pascal、FPC
+unknown-language = 源代码须带有 '.c'、'.cpp'、'.py' 或 '.pas' 扩展名，或以 shebang 开头，或用 --lang 指定语言。
+invalid-extension = 无效的文件扩展名。支持的值：'.c'、'.cpp'、'.py'、'.pas'，或用 --lang 指定语言。
+recent-out-of-range = 只记住了最近 { $count } 道题目，参见 `oj recent`。
+no-such-alias = 未定义别名 @{ $name }。可用 `oj alias set { $name } URL` 添加。
+invalid-alias-name = 无效的别名 "{ $name }"，请使用字母、数字、- 和 _。
+page-saved = 页面已保存到 { $path }。
+parsing-file = 解析 { $path } 时出错
+image-linked-instead = 无法获取图片 { $url }，改为链接。
+bundle-missing-file = { $bundle } 中没有 { $file }。
+bundle-without-url = { $bundle } 未注明导出自哪道题目，请用 --url 指定。
+hook-failed = 提交前钩子 `{ $command }` 失败，退出码为 { $code }。如仍要提交，请加上 --no-verify。
+cannot-load-problem = 无法加载题目 { $url }
+no-manifest-file = 清单中没有为 { $key } 指定源文件。
+no-problem-specified = 未指定题目。
+not-a-manifest-key = 未指定源文件，且 { $key } 不是清单中的键。
+source-not-found = { $file } 不存在，加上 --create 可创建它。
+running-editor = 运行编辑器 `{ $editor }` 时出错
+editor-failed = `{ $editor }` 退出，退出码为 { $code }。
+undeclared-language = 配置中未声明语言 { $name }。
+no-test-data = { $dir } 中没有测试数据。
+no-desktop-notifications = 此平台不支持桌面通知。
+probset-empty = { $group }/{ $probset } 中没有题目。
+join-requests-failed = { $total } 个入组申请中有 { $failed } 个未能处理。
+no-unsolved-problem = { $group }/{ $probset } 中没有未解决的题目了。
+no-friends = 未配置好友。可用 `oj config --add-friend ID` 添加。
+error = 错误
+alias-not-in-config = 配置中没有别名 @{ $name }。
+no-such-command = 历史记录中没有第 { $n } 条命令。
+command-failed = `{ $command }` 退出，退出码为 { $code }。
+invalid-time-format = 无效的时间格式 "{ $format }"。
+invalid-cpp-std = 无效的 C++ 标准 "{ $cpp_std }"，应为 c++17 或 gnu++14 等。
+invalid-image-rows = 图片高度至少为一行。
+invalid-concurrency = 至少要允许一个请求同时进行。
+invalid-duration = 无效的时长 "{ $duration }"，应为 10m 或 1h30m 等。
+invalid-image-size = 无效的图片尺寸 "{ $size }"，应为 宽x高。
+not-a-problem-page = { $url } 不是题目页面。
+unknown-section = 未知的小节 "{ $name }"。
+spec-no-title = 题目没有标题。
+spec-no-description = 题目没有描述。
+not-a-backup = { $path } 不是备份文件
+unsafe-backup-path = 备份中有不安全的路径 { $path }。
+backup-target-exists = { $path } 已存在，使用 --force 覆盖当前状态。
+replacing-file = 替换 { $path } 时出错
+fetching-latest-release = 获取最新版本时出错
+no-prebuilt-binary = 没有适用于此平台的预编译程序（{ $name }）。
+no-release-checksum = 该版本没有 { $name } 的校验和。
+checksum-mismatch = { $name } 的校验和不匹配：应为 { $expected }，实为 { $actual }。可执行文件未被替换。
+
+## Progress
+
+logging-in = 正在登录
+validating-credentials = 正在向 OpenJudge 验证凭据
+fetching-problem = 正在获取题目详情
+fetching-problems = 正在获取题目
+fetching-probsets = 正在获取题集
+fetching-user = 正在获取用户信息
+fetching-submission = 正在获取提交详情
+fetching-submissions = 正在获取提交记录
+fetching-group = 正在获取小组
+fetching-standings = 正在获取排名
+fetching-image = 正在获取图片
+fetching-board = 正在获取讨论区
+fetching-posts = 正在获取帖子
+downloading = 正在下载 { $file }
+searching = 正在小组 { $group } 中搜索 { $query }
+rendering = 正在渲染 { $title }
+running-hook = 正在运行钩子
+checking-problems = 正在检查题目
+submitting = 正在提交 { $url } 的解答
+waiting-for-verdict = 正在等待评测
+joining-group = 正在加入小组 { $group }
+creating-problem = 正在创建题目 { $title }
+uploading-test-data = 正在上传 { $count } 个测试数据文件
+reminding-in = { $remaining } 后提醒（{ $name } 结束前 { $before }）
+fetching-join-requests = 正在获取入组申请
+approving-join-request = 正在批准入组申请 { $id }
+rejecting-join-request = 正在拒绝入组申请 { $id }
+fetching-problems-so-far = 正在获取题目，已获取 { $count } 道
+fetching-users = 正在获取 { $count } 位用户
+fetching-user-id = 获取用户 { $id } 时出错
+measuring-network = 正在测量各网络阶段
+running = 正在运行
+fetching-submission-count = 正在获取 { $count } 条提交
+loading-problems = 正在加载题目…
+loading-page = 正在加载页面…
+loading-problem = 正在加载题目…
+loading-submissions = 正在加载提交记录…
+loading-submission = 正在加载提交…
+checking-for-updates = 正在检查更新
+downloading-checksums = 正在下载校验和
+
+## Results
+
+enter-password = 请输入密码：
+credentials-saved = 凭据已保存。
+found-results = 找到 { $count } 个结果：
+found-problems = 找到 { $count } 道题目。
+no-problem-selected = 未选择题目。
+no-submissions = 没有提交记录。
+no-pending-submissions = 没有等待评测的提交。
+check-retrying = { $error }（重试中）
+no-probsets = 没有题集。
+no-topics = 讨论区没有帖子。
+topic-replies = （{ $count } 条回复）
+contains-probsets = 包含 { $count } 个题集：
+page-of = 第 { $page } 页，共 { $max } 页
+bundle-saved = 已将 { $title } 连同 { $count } 张图片保存到 { $file }。
+bundle-imported = 已将 { $title } 导入为 { $url }，现在可离线使用 `oj view problem` 和 `oj test`。
+submission-created = 已提交，见 { $url }
+monitor-hint = 运行 `oj monitor` 跟踪评测结果。
+file-created = 已创建 { $file }。
+step-position = 第 { $current } 题，共 { $total } 题。
+step-choices = 下一题 [n]、上一题 [p] 或退出 [q]
+step-choices-last = 上一题 [p] 或退出 [q]
+preview-failed = 无法加载预览：{ $error }
+code-heading = 代码
+line-count = { $count } 行
+copied-with = 已用 { $command } 将 { $count } 行复制到剪贴板。
+copied-by-terminal = 已请求终端将 { $count } 行复制到剪贴板。
+found-submissions = 找到 { $count } 条提交：
+join-request-sent = 申请已发送。
+problem-created = 已创建题目 { $title }：{ $url }
+test-data-uploaded = 已上传 { $count } 个测试数据文件。
+no-contest = 小组 { $group } 中没有进行中的比赛。
+contest-upcoming = 没有进行中的比赛。{ $name } 将在 { $duration } 后开始（{ $time }）。
+contest-remaining = 剩余 { $duration }，结束于 { $time }
+contest-ends-in = { $name } 将在 { $duration } 后结束
+notify-at = 将在 { $time } 通知你。
+manifest-written = 已将 { $count } 道题目写入 { $path }。
+no-join-requests = 没有待处理的入组申请。
+join-request-approved = 已批准
+join-request-rejected = 已拒绝
+failed = 失败
+rows-exported = 已将 { $count } 行导出到 { $path }
+stage-dns = DNS 解析
+stage-connect = TCP 连接
+stage-fetch = 页面获取
+stage-slow = { $host } 的{ $stage }较慢或失败。
+host-fine = { $host } 看起来正常。
+backed-up = 已将 { $count } 个文件备份到 { $file }。
+backup-keyring-note = 密码仍保存在系统密钥环中，恢复后请重新运行 `oj credentials`。
+restored = 已从 { $file } 恢复 { $count } 个文件。
+alias-removed = 已删除 { $name }。
+no-aliases = 未定义别名。可用 `oj alias set NAME URL` 添加。
+alias-source-config = 配置
+no-recent-problems = 尚未操作过题目。
+no-history = 尚未运行过命令。
+command-ok = 成功
+no-problems-viewed = 尚未查看过题目。
+stats-viewed = 查看了 { $viewed } 道题目，解决了 { $solved } 道。
+stats-times = 解题用时：中位数 { $median }，平均 { $average }，最快 { $fastest }，最慢 { $slowest }。
+stats-streaks = 当前连续 { $current } 天，最长连续 { $longest } 天。
+breakdown-summary = 共 { $submissions } 条提交，涉及 { $problems } 道题目。
+up-to-date = 已是最新版本（{ $version }）。
+new-version-available = 有新版本可用：{ $version }（当前 { $current }）。
+updated-to = 已将 { $path } 更新到 { $version }。
+
+## Local tests
+
+testing-solution = 正在用题目 { $problem } 测试解答 { $file }
+case-input = 样例输入：
+expected-output = 期望输出：
+your-output = 你的输出：
+diff = 差异：
+exit-code = 退出码：
+signal = 信号：
+sample-case = 样例
+case-heading = 测试点 { $name }
+cases-passed = 通过 { $passed } / { $total } 个测试点。
+invalid-case = 无效的测试点 { $case }，应为 <输入文件>:<期望输出文件>。
+no-expected-output = { $input } 没有期望输出 { $output }。
+no-problem-url = 无题目链接
+no-solutions-in = { $dir } 中没有可测试的解答。
+solutions-failed = { $total } 个解答中有 { $failed } 个未通过样例。
+solutions-passed = 全部 { $count } 个解答均通过样例。
+archived-to = 已归档到 { $path }。
+cannot-archive = 无法归档解答：{ $error }
+warnings-blocking = 配置中将 { $flags } 设为阻止项，请修复后再测试。
+
+## Problems
+
+limit-time = 总时间限制：
+limit-case = 单个测试点时间限制：
+limit-memory = 内存限制：
+special-judge = 特殊评测
+section-input = 输入
+section-output = 输出
+section-sample-input = 样例输入
+section-sample-output = 样例输出
+section-hint = 提示
+section-source = 来源
+section-heading = 部分：{ $name }
+problem-solved = 已解决
+problem-attempted = 已尝试
+label-submissions = 提交
+images-skipped = 未下载 { $count } 张图片：
+fetch-image-prompt = 输入序号获取图片，或按 Enter 继续：
+no-such-image = 请输入 1 到 { $max } 之间的序号。
+attachments-found = 题面链接了 { $count } 个附件：
+attachments-prompt = 输入保存目录，或按 Enter 跳过：
+no-attachments = 题面没有链接附件。
+saved-attachment = 已保存 { $bytes } 字节到
+select-samples = 选择要保存样例的题目：
+samples-skipped = { $number } { $title } 没有样例输入/输出，已跳过。
+samples-saved = 已将 { $number } { $title } 的样例保存到 { $path }。
+daily-problem = { $date } 的每日一题：
+daily-solved = 最近 { $total } 道每日一题中解决了 { $solved } 道。
+section-description = 描述
+
+## Users and submissions
+
+user-id = ID：
+user-name = 用户名：
+user-sex = 性别：
+user-school = 学校：
+user-registered = 注册时间：
+submission-author = 作者：
+submission-lang = 语言：
+submission-time = 时间：
+submission-memory = 内存：
+submission-submit-time = 提交时间：
+compiler-diagnostics = 编译器诊断：
+no-message = 没有提供信息。
+submission-heading = 提交
+
+## Verdicts, short in lists and long in headings
+
+verdict-heading = 评测结果：{ $verdict }
+verdict-accepted = 通过
+verdict-accepted-long = 通过！
+verdict-compile-error = 编译错误
+verdict-compile-error-long = 编译错误。
+verdict-wrong-answer = 答案错误
+verdict-wrong-answer-long = 答案错误。
+verdict-time-limit-exceeded = 超时
+verdict-time-limit-exceeded-long = 超出时间限制。
+verdict-memory-limit-exceeded = 超内存
+verdict-memory-limit-exceeded-long = 超出内存限制。
+verdict-runtime-error = 运行错误
+verdict-runtime-error-long = 运行时错误。
+verdict-output-limit-exceeded = 输出超限
+verdict-output-limit-exceeded-long = 超出输出限制。
+verdict-presentation-error = 格式错误
+verdict-presentation-error-long = 格式错误。
+verdict-unknown-error = 未知错误
+verdict-unknown-error-long = 未知错误。
+verdict-waiting = 等待评测
+
+## Tables
+
+column-result = 结果
+column-time = 时间
+column-title = 标题
+column-submitters = 提交人数
+column-rank = 排名
+column-user = 用户
+column-solved = 解题数
+column-penalty = 罚时
+column-file = 文件
+column-problem = 题目
+column-submitted = 提交时间
+column-key = 键
+not-attempted = 未尝试
+column-samples = 样例
+file-kept = { $file }（保留）
+no-samples-short = 无
+column-message = 留言
+column-attempted = 尝试过
+you = { $name }（你）
+column-host = 主机
+column-address = 地址
+column-connect = 连接
+column-fetch = 获取
+column-alias = 别名
+column-defined-in = 定义于
+column-reference = 引用
+column-command = 命令
+column-viewed = 查看时间
+column-accepted = 通过时间
+column-time-to-solve = 解题用时
+column-total = 总计
+column-other = 其他
+column-language = 语言
+column-probset = 题集
+
+## Times
+
+just-now = 刚刚
+minutes-ago = { $count } 分钟前
+hours-ago = { $count } 小时前
+days-ago = { $count } 天前
+# 热力图上方月份名称的 chrono 格式
+heatmap-month = %-m月
+weekday-monday = 一
+weekday-wednesday = 三
+weekday-friday = 五
+heatmap-less = 少
+heatmap-more = 多
+
+## Interactive lists
+
+no-matches = 无匹配项。
+next-page = 下一页
+previous-page = 上一页
+cancelled = 已取消
+not-logged-in = 未登录
+status-page = 第 { $page }/{ $max } 页
+status-pending = { $count } 个待处理
+go-to = 跳转到：
+filter = 筛选：
+hint-move = ↑/↓ 移动
+hint-page = ←/→ 翻页
+hint-toggle = Space 选中
+hint-confirm = Enter 确认，q 退出，? 帮助
+press-any-key = 按任意键返回。
+key-move = 上移 / 下移
+key-move-page = 按页移动
+key-first-last = 跳到第一个 / 最后一个选项
+key-page = 跳到上一页 / 下一页
+key-jump = 跳到输入序号的选项
+key-filter = 按输入的文字筛选选项
+key-clear-filter = 清除筛选
+key-highlight = 高亮选项
+key-scroll = 滚动列表
+key-toggle-highlighted = 选中或取消高亮的选项
+key-toggle = 选中或取消选项
+key-confirm-toggled = 确认选中的选项
+key-confirm = 确认选项
+key-confirm-highlighted = 确认高亮的选项
+key-cancel = 取消
+key-help = 显示此帮助
+loading = 加载中...
+key-name-other = 其他键、/
+key-name-escape-filtering = Esc（筛选时）
+key-name-click = 单击
+key-name-wheel = 滚轮
+key-name-double-click = 双击
+press-enter = 按回车键返回。
+hint-help = ? 帮助
+press-r-submissions = 按 r 加载你的提交记录。
+test-with-file = 测试文件：
+submit-file = 提交文件：
+tui-hints-probsets = ↑↓ 移动 · Enter 打开 · q 退出
+tui-hints-problems = ↑↓ 移动 · Enter 打开 · n/p 翻页 · Esc 返回 · q 退出
+tui-hints-problem = ↑↓ 滚动 · Tab 切换窗格 · t 测试 · s 提交 · r 提交记录 · Esc 返回 · q 退出
+tui-hints-submission = ↑↓ 滚动 · Esc 返回 · q 退出
@@ -1,14 +1,31 @@
+#[macro_use]
+mod i18n;
+
+mod activity;
 mod app;
+mod backup;
+mod bundle;
 mod code_theme;
+mod crash;
+mod daily;
+mod diagnostics;
+mod diff;
 mod display;
-mod libopenjudge;
+mod doctor;
+mod manifest;
+mod pending;
+mod problem_spec;
+mod sanity;
+mod self_update;
+mod solves;
 mod tests;
+mod tui;
 mod utils;
 
 use app::*;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand, arg, command};
+use clap::{ArgAction, Args, Parser, Subcommand};
 
 const NAME: &str = "OpenJudge CLI";
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -19,6 +36,13 @@ const ABOUT: &str = "CLI for OpenJudge (openjudge.cn)";
 struct Cli {
     #[command(subcommand)]
     command: AppCommand,
+    /// Log requests and timings to stderr, repeat for more detail.
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+    /// Show images as placeholders with their URL instead of downloading
+    /// them. Interactive terminals are then offered to fetch them one by one.
+    #[arg(long, global = true)]
+    no_images: bool,
 }
 
 #[derive(Subcommand)]
@@ -35,24 +59,42 @@ enum AppCommand {
     View {
         #[command(subcommand)]
         view_type: ViewType,
+        /// Draw images with this protocol for this run only, instead of the
+        /// configured one. Takes the values of `oj config --graphics`.
+        #[arg(long, global = true, value_name = "PROTOCOL")]
+        graphics: Option<String>,
     },
 
     #[command(visible_alias = "s")]
     /// Submit a solution to a problem.
     Submit {
-        /// URL(s) of the problem, excluding '/submit'.
-        /// Use "." to submit to the last operated problem.
-        #[arg(required = true)]
-        url: Vec<String>,
-        /// Path to the source code file.
-        #[arg(required = true)]
-        file: String,
+        /// URL(s) of the problem, followed by the path to the source code file.
+        /// URLs may omit the scheme, or be shortened to "group/probset/number".
+        /// Use "." to submit to the last operated problem, "~N" for earlier
+        /// ones (see `oj recent`), or "@name" for an alias.
+        /// Keys in oj.yaml can be used in place of URLs; if only keys are given,
+        /// each is submitted with its mapped file and language.
+        #[arg(required = true, value_name = "URL|KEY... [FILE]")]
+        args: Vec<String>,
+        /// Submit without running the pre-submit hooks.
+        #[arg(long)]
+        no_verify: bool,
+        /// Submit even if the same code was already submitted to the
+        /// problem, without asking.
+        #[arg(long)]
+        force: bool,
+        /// Return once submitted instead of waiting for the verdict, which
+        /// `oj monitor` shows later.
+        #[arg(long)]
+        no_wait: bool,
         /// Language of the source code file, overrides inferred language.
         /// Supported values (case insensitive):
         /// - C, GCC;
         /// - C++, G++;
         /// - Py, Python, Py3, Python3;
-        /// - PyPy, PyPy3.
+        /// - PyPy, PyPy3;
+        /// - Pas, Pascal, FPC;
+        /// - names of languages declared in the config file.
         #[arg(short, long)]
         lang: Option<String>,
     },
@@ -62,33 +104,64 @@ enum AppCommand {
     /// Be aware: testing solution locally requires compiler/interpreter be accessible via command line.
     /// For C, gcc is called;
     /// For C++, g++ is called;
-    /// For Python, python3 is called;
-    /// For PyPy, pypy3 is called.
+    /// For Python, python3 is called, unless another interpreter or virtualenv is configured;
+    /// For PyPy, pypy3 is called;
+    /// For Pascal, fpc is called;
+    /// For languages declared in the config file, their commands are run.
     Test {
-        /// URL of the problem.
-        /// Use "." to test the last operated problem.
+        /// URL of the problem (or "group/probset/number"), or a key in oj.yaml.
+        /// Use "." to test the last operated problem, "~N" for earlier ones
+        /// (see `oj recent`), or "@name" for an alias.
+        #[arg(required_unless_present = "dir")]
+        url: Option<String>,
+        /// Path to the source code file, can be omitted if mapped in oj.yaml.
         #[arg()]
-        url: String,
-        /// Path to the source code file.
-        #[arg()]
-        file: String,
+        file: Option<String>,
+        /// Test every source file in this directory against its problem,
+        /// mapped in oj.yaml or named by a URL in a comment at its top, and
+        /// print a summary.
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["url", "file", "lang", "submit"])]
+        dir: Option<String>,
         /// Language of the source code file, overrides inferred language.
         /// Supported values (case insensitive):
         /// - C, GCC;
         /// - C++, G++;
         /// - Py, Python, Py3, Python3;
-        /// - PyPy, PyPy3.
+        /// - PyPy, PyPy3;
+        /// - Pas, Pascal, FPC;
+        /// - names of languages declared in the config file.
         #[arg(short, long)]
         lang: Option<String>,
         /// Proceed to submit if accepted.
         #[arg(short, long)]
         submit: bool,
+        /// Submit without running the pre-submit hooks.
+        #[arg(long, requires = "submit")]
+        no_verify: bool,
+        /// Submit even if the same code was already submitted to the
+        /// problem, without asking.
+        #[arg(long, requires = "submit")]
+        force: bool,
+        /// How to show the difference on a wrong answer.
+        /// Supported values (case insensitive):
+        /// - u, unified;
+        /// - s, side: expected and actual output in two columns.
+        ///
+        /// Default is "unified".
+        #[arg(long, default_value = "unified")]
+        diff: diff::DiffStyle,
+        /// Also run the solution on a case of your own, given as
+        /// <input file>:<expected output file>. Can be repeated. Cases in
+        /// tests/<key>/ (the key in oj.yaml, or else the problem number),
+        /// as <name>.in and <name>.out pairs, are always run.
+        #[arg(long, value_name = "IN:OUT", conflicts_with = "dir")]
+        case: Vec<String>,
     },
 
     #[command(visible_alias = "S")]
     /// Use keyword to search within a group.
     Search {
-        /// Group name, used to construct query url like http://{group}.openjudge.cn/search/?q=...
+        /// Group name, used to construct query url like https://{group}.openjudge.cn/search/?q=...
         #[arg()]
         group: String,
         /// Search query.
@@ -96,7 +169,8 @@ enum AppCommand {
         query: String,
         /// Whether to use interactive mode.
         ///
-        /// In interactive mode, the program will prompt user to select a problem from the search results.
+        /// In interactive mode, the program will prompt user to select problems from the search results.
+        /// Several can be toggled with Space, they are then shown one after another.
         #[arg(short, long)]
         interactive: bool,
     },
@@ -114,19 +188,368 @@ enum AppCommand {
         interactive: bool,
     },
 
+    /// Show the latest verdict of every problem in oj.yaml.
+    Status,
+
+    /// Follow the submissions still waiting for a verdict in a live table,
+    /// until all of them are judged.
+    Monitor,
+
+    /// List recently operated problems and how to refer to them: "." for
+    /// the latest, "~1" or "^" for the one before it, "~2" or "^^" for the
+    /// one before that, and so on.
+    Recent,
+
+    /// List recent commands and how they ended, or run one of them again.
+    History {
+        /// Most entries to list.
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+        /// Run the Nth latest command again, 1 being the latest.
+        #[arg(long, value_name = "N")]
+        rerun: Option<usize>,
+        /// List problems viewed or solved through the CLI instead, with the
+        /// time from the first view to the first accepted submission.
+        #[arg(long, conflicts_with = "rerun")]
+        problems: bool,
+    },
+
+    /// Summarize how long solving problems took, with a calendar of the days
+    /// you got an Accepted and your streaks of them.
+    Stats {
+        #[command(subcommand)]
+        view: Option<StatsView>,
+    },
+
+    /// Rank friends added with `oj config --add-friend` by the problems of a
+    /// problem set they solved.
+    Leaderboard {
+        #[arg()]
+        group: String,
+        #[arg()]
+        probset: String,
+    },
+
+    /// Replace this executable with the latest release from GitHub.
+    SelfUpdate {
+        /// Only check whether a newer release exists.
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Diagnose problems reaching OpenJudge.
+    Doctor {
+        #[command(subcommand)]
+        check: DoctorCheck,
+    },
+
+    /// Save the config, practice logs and caches into a zip archive.
+    Backup {
+        #[arg()]
+        file: String,
+    },
+
+    /// Restore the config, practice logs and caches from a backup.
+    Restore {
+        #[arg()]
+        file: String,
+        /// Overwrite the current files.
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Show today's practice problem, an unsolved problem picked once a day.
+    /// Problems get harder while daily problems get solved, and easier after
+    /// missed days.
+    Daily {
+        /// Group to pick from. Defaults to the group of the last operated
+        /// problem.
+        #[arg()]
+        group: Option<String>,
+        /// Problem set to pick from.
+        #[arg(short, long, default_value = "practice")]
+        probset: String,
+        /// Draw images with this protocol for this run only, instead of the
+        /// configured one. Takes the values of `oj config --graphics`.
+        #[arg(long, value_name = "PROTOCOL")]
+        graphics: Option<String>,
+    },
+
+    /// Open the source file of a problem in your editor.
+    Edit {
+        /// URL of the problem (or "group/probset/number"), or a key in oj.yaml.
+        /// Use "." for the last operated problem, "~N" for earlier ones (see
+        /// `oj recent`), or "@name" for an alias.
+        #[arg()]
+        url: String,
+        /// Source file to open. By default, the file mapped in oj.yaml, or else
+        /// a file in the current directory naming the problem URL in a comment
+        /// within its first lines.
+        #[arg()]
+        file: Option<String>,
+        /// Create the file if it does not exist, starting with a comment naming
+        /// the problem, followed by "templates/template.<EXTENSION>" in the
+        /// config directory if there is one.
+        #[arg(short, long)]
+        create: bool,
+    },
+
+    /// Share problems as zip archives, for practicing without an account.
+    Bundle {
+        #[command(subcommand)]
+        action: BundleAction,
+    },
+
+    /// Name problem URLs, to use them anywhere as "@name".
+    Alias {
+        #[command(subcommand)]
+        action: AliasAction,
+    },
+
+    /// Export contest standings for use elsewhere.
+    Standings {
+        #[command(subcommand)]
+        action: StandingsAction,
+    },
+
+    /// Join a group, or apply to join it if approval is required.
+    Join {
+        /// Group name, as in https://{group}.openjudge.cn/
+        #[arg()]
+        group: String,
+        /// Message to the group admins, sent along with the application.
+        #[arg(short, long)]
+        message: Option<String>,
+    },
+
+    /// Keep track of the time left in contests.
+    Contest {
+        #[command(subcommand)]
+        action: ContestAction,
+    },
+
+    /// Set problems, requires admin rights of the group.
+    Problem {
+        #[command(subcommand)]
+        action: ProblemAction,
+    },
+
+    /// Manage a group, requires admin rights of the group.
+    Group {
+        #[command(subcommand)]
+        action: GroupAction,
+    },
+
+    /// Browse groups, problems and submissions in a full-screen interface.
     #[command()]
-    Config {
-        /// Configure the graphics protocol for displaying images.
-        /// Supported values (case insensitive):
-        /// - n, none, disabled;
-        /// - s, sixel;
-        /// - k, kitty;
-        /// - i, iterm;
-        /// - a, auto.
-        ///
-        /// Default is "auto".
+    Tui {
+        /// Group to browse, e.g. "cs101". Defaults to the group of the last
+        /// operated problem.
+        #[arg()]
+        group: Option<String>,
+    },
+
+    #[command()]
+    Config(Box<ConfigArgs>),
+}
+
+#[derive(Args)]
+struct ConfigArgs {
+    /// Configure the graphics protocol for displaying images.
+    /// Supported values (case insensitive):
+    /// - n, none, disabled;
+    /// - s, sixel;
+    /// - k, kitty;
+    /// - i, iterm;
+    /// - a, auto.
+    ///
+    /// Default is "auto".
+    #[arg(short, long)]
+    graphics: Option<String>,
+    /// Whether to try HTTPS first, falling back to HTTP for hosts that do not support it.
+    ///
+    /// Default is true.
+    #[arg(long)]
+    https: Option<bool>,
+    /// User-Agent header sent to OpenJudge, pass an empty string to restore the default.
+    ///
+    /// Default is "openjudge-cli/<version>".
+    #[arg(long)]
+    user_agent: Option<String>,
+    /// Syntax highlighting theme for source code, either a bundled theme name
+    /// or a path to a .tmTheme file.
+    ///
+    /// Default is "Enki-Tokyo-Night".
+    #[arg(long)]
+    theme: Option<String>,
+    /// Largest size sixel images are drawn at, as WIDTHxHEIGHT in pixels.
+    /// Bigger images are downscaled before encoding.
+    ///
+    /// Default is "800x600".
+    #[arg(long)]
+    sixel_max_size: Option<String>,
+    /// Error diffusion used when reducing sixel images to their palette.
+    /// Supported values: auto, none, atkinson, fs, jajuni, stucki, burkes,
+    /// adither, xdither.
+    ///
+    /// Default is "auto".
+    #[arg(long)]
+    sixel_diffusion: Option<String>,
+    /// Most terminal rows an iTerm inline image may span, larger images are
+    /// scaled down keeping their aspect ratio.
+    ///
+    /// Default is 30.
+    #[arg(long)]
+    image_max_rows: Option<u32>,
+    /// Format of submission times in the local timezone, using strftime
+    /// syntax. Pass an empty string to restore the default.
+    ///
+    /// Default is "%Y-%m-%d %H:%M:%S".
+    #[arg(long)]
+    time_format: Option<String>,
+    /// Show times within the last week as e.g. "3 minutes ago".
+    ///
+    /// Default is false.
+    #[arg(long)]
+    relative_time: Option<bool>,
+    /// How verdicts are shown.
+    /// Supported values (case insensitive):
+    /// - w, words: colored words;
+    /// - e, emoji: an emoji with the abbreviation, e.g. "❌ WA";
+    /// - a, ascii: uncolored tags, e.g. "[WA]".
+    ///
+    /// Default is "words".
+    #[arg(long)]
+    verdicts: Option<String>,
+    /// Language of messages.
+    /// Supported values (case insensitive):
+    /// - en, english: English;
+    /// - zh, zh-cn, chinese: Simplified Chinese.
+    ///
+    /// Pass an empty string to restore the default.
+    ///
+    /// Default is the language of the system locale, or English.
+    #[arg(long)]
+    lang: Option<String>,
+    /// Draw interactive lists below the prompt instead of on a separate
+    /// screen, leaving the choice visible in the scrollback.
+    ///
+    /// Default is false.
+    #[arg(long)]
+    inline_select: Option<bool>,
+    /// Output for terminal screen readers: no images, spinners, colors or
+    /// box-drawing, and verdicts and sections prefixed with words.
+    ///
+    /// Default is false.
+    #[arg(long)]
+    screen_reader: Option<bool>,
+    /// Comma separated hosts serving OpenJudge, e.g.
+    /// "openjudge.cn,oj-mirror.example.com". Requests go to the first one
+    /// that works, moving on to the next when they keep timing out. Pass
+    /// an empty string to restore the default.
+    ///
+    /// Default is "openjudge.cn".
+    #[arg(long, value_name = "HOSTS")]
+    hosts: Option<String>,
+    /// Requests kept in flight at once when fetching many pages, such as
+    /// all pages of a problem set or the submissions of `oj stats
+    /// breakdown`.
+    ///
+    /// Default is 4.
+    #[arg(long, value_name = "N")]
+    concurrency: Option<usize>,
+    /// Idle connections kept open per host, reused by later requests
+    /// instead of connecting again.
+    ///
+    /// Default is 16.
+    #[arg(long, value_name = "N")]
+    pool_size: Option<usize>,
+    /// Seconds an idle connection is kept open for reuse.
+    ///
+    /// Default is 120.
+    #[arg(long, value_name = "SECONDS")]
+    keep_alive: Option<u64>,
+    /// C++ standard used when testing locally, e.g. "c++17". A warning is
+    /// shown when it differs from the one OpenJudge uses. Pass an empty
+    /// string to restore the default.
+    ///
+    /// Default is "gnu++14".
+    #[arg(long)]
+    cpp_std: Option<String>,
+    /// Comma separated compiler warnings that fail a local test of C or
+    /// C++, so it is not submitted, e.g. "format,return-type" for
+    /// -Wformat and -Wreturn-type. Pass an empty string to restore the
+    /// default.
+    ///
+    /// Default is none, warnings are only shown.
+    #[arg(long, value_name = "WARNINGS")]
+    blocking_warnings: Option<String>,
+    /// Python interpreter used when testing locally, e.g. ".venv/bin/python",
+    /// or a virtualenv directory. Pass an empty string to restore the default.
+    ///
+    /// Default is the interpreter of the activated virtualenv, or "python3".
+    #[arg(long)]
+    python: Option<String>,
+    /// Editor `oj edit` opens files with, e.g. "code --wait". Pass an empty
+    /// string to restore the default.
+    ///
+    /// Default is $VISUAL, then $EDITOR, then "vi" ("notepad" on Windows).
+    #[arg(long, value_name = "COMMAND")]
+    editor: Option<String>,
+    /// Copy every accepted solution into this directory, as
+    /// "<group>/<probset>/<number>-<title>.<extension>". Pass an empty
+    /// string to stop archiving.
+    ///
+    /// Default is not to archive.
+    #[arg(long, value_name = "DIR")]
+    archive_dir: Option<String>,
+    /// Add a friend to `oj leaderboard`, as a user ID or ID=NAME, e.g.
+    /// "1234567=Alice". Can be repeated.
+    #[arg(long, value_name = "ID[=NAME]")]
+    add_friend: Vec<String>,
+    /// Remove a friend by user ID. Can be repeated.
+    #[arg(long, value_name = "ID")]
+    remove_friend: Vec<String>,
+    /// Add a command run on the source file before each submission,
+    /// "{source}" is replaced by its quoted path. A failing command blocks the
+    /// submission unless --no-verify is passed. Can be repeated.
+    #[arg(long, value_name = "COMMAND")]
+    pre_submit_hook: Vec<String>,
+    /// Remove all pre-submit hooks.
+    #[arg(long)]
+    clear_pre_submit_hooks: bool,
+    /// Warn about common pitfalls of C and C++ solutions on OpenJudge
+    /// before submitting, such as C++17 features or `gets`.
+    ///
+    /// Default is false.
+    #[arg(long)]
+    sanity_checks: Option<bool>,
+    /// Ring the terminal bell when a submission is judged, or when local
+    /// tests taking 10 seconds or more finish.
+    ///
+    /// Default is false.
+    #[arg(long)]
+    bell: Option<bool>,
+    /// Shell command run instead of the bell, e.g. "paplay done.oga".
+    /// Pass an empty string to restore the default.
+    ///
+    /// Default is none, the bell is rung if turned on.
+    #[arg(long, value_name = "COMMAND")]
+    sound_command: Option<String>,
+    /// List bundled syntax highlighting themes.
+    #[arg(long)]
+    list_themes: bool,
+}
+
+#[derive(Subcommand)]
+enum DoctorCheck {
+    /// Time DNS resolution, TCP connect and a full page fetch for
+    /// openjudge.cn and a group subdomain, showing which stage is slow.
+    Net {
+        /// Group subdomain to check, e.g. "cs101". Defaults to the group of
+        /// the last problem.
         #[arg(short, long)]
-        graphics: String,
+        group: Option<String>,
     },
 }
 
@@ -137,15 +560,226 @@ enum ViewType {
 
     #[command(alias = "p")]
     Problem {
-        /// URL of the problem.
-        /// Use "." to view the last operated problem.
-        #[arg()]
-        url: String,
+        /// URL(s) of the problem, or "group/probset/number".
+        /// Use "." to view the last operated problem, "~N" for earlier ones
+        /// (see `oj recent`), or "@name" for an alias.
+        /// Multiple problems are fetched concurrently and shown in order.
+        #[arg(required = true)]
+        urls: Vec<String>,
+        /// Save the documents and data files linked from the statement into
+        /// this directory. Interactive terminals are asked otherwise.
+        #[arg(long, value_name = "DIR")]
+        download_attachments: Option<String>,
     },
     #[command(alias = "s")]
     Submission {
         #[arg()]
         url: String,
+        /// Also copy the code to the clipboard.
+        #[arg(long)]
+        copy: bool,
+    },
+    /// View the discussion board of a problem, with the posts of each topic.
+    #[command(alias = "b")]
+    Board {
+        /// URL of the problem (or "group/probset/number"), "." for the last
+        /// operated problem, or "@name" for an alias.
+        #[arg()]
+        url: String,
+        /// Page of topics to show, the first by default.
+        #[arg(short, long)]
+        page: Option<u32>,
+    },
+    /// Draw a single image, such as one left out by --no-images.
+    #[command(alias = "i")]
+    Image {
+        #[arg()]
+        url: String,
+    },
+    /// View the ranking of a contest.
+    /// Requests are constructed as https://{group}.openjudge.cn/{probset}/ranking/
+    #[command(alias = "r")]
+    Standings {
+        #[arg()]
+        group: String,
+        #[arg()]
+        probset: String,
+        #[arg()]
+        page: Option<u32>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ContestAction {
+    /// Show the time remaining in the contests in progress.
+    Status {
+        /// Group name, as in https://{group}.openjudge.cn/
+        #[arg()]
+        group: String,
+    },
+    /// Wait until shortly before the contest in progress ends, then ring the
+    /// bell and show a desktop notification.
+    Remind {
+        #[arg()]
+        group: String,
+        /// How long before the end to remind, e.g. "10m", "1h30m" or "90s".
+        #[arg(short, long, default_value = "10m")]
+        before: String,
+        /// Return immediately and leave the notification to a background
+        /// process.
+        #[arg(short, long)]
+        detach: bool,
+    },
+    /// Prepare a directory for a contest: a source file per problem, their
+    /// samples under tests/ and an oj.yaml mapping them.
+    Init {
+        #[arg()]
+        group: String,
+        #[arg()]
+        probset: String,
+        /// Extension of the source files, picking the template from the
+        /// templates directory of the config root.
+        #[arg(short, long, default_value = "cpp")]
+        ext: String,
+        /// Directory to set up.
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProblemAction {
+    /// Create a problem from a YAML or Markdown description and upload its
+    /// test data. See the README for the description format. Experimental,
+    /// the pages used are not verified against OpenJudge.
+    Create {
+        #[arg()]
+        group: String,
+        #[arg()]
+        probset: String,
+        /// Problem description, a .yaml or .md file.
+        #[arg()]
+        file: String,
+        /// Directory of test data files, overrides `data` of the description.
+        #[arg(short, long)]
+        data: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum GroupAction {
+    /// Process applications to join the group. Experimental, the pages used
+    /// are not verified against OpenJudge.
+    Requests {
+        #[command(subcommand)]
+        action: JoinRequestAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum JoinRequestAction {
+    /// List pending join requests.
+    #[command(visible_alias = "l")]
+    List {
+        /// Group name, as in https://{group}.openjudge.cn/
+        #[arg()]
+        group: String,
+    },
+    /// Approve join requests.
+    #[command(visible_alias = "a")]
+    Approve {
+        #[arg()]
+        group: String,
+        /// IDs of the requests, as shown by `group requests list`.
+        #[arg(required_unless_present = "all")]
+        ids: Vec<String>,
+        /// Approve all pending requests.
+        #[arg(long, conflicts_with = "ids")]
+        all: bool,
+    },
+    /// Reject join requests.
+    #[command(visible_alias = "r")]
+    Reject {
+        #[arg()]
+        group: String,
+        /// IDs of the requests, as shown by `group requests list`.
+        #[arg(required_unless_present = "all")]
+        ids: Vec<String>,
+        /// Reject all pending requests.
+        #[arg(long, conflicts_with = "ids")]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum BundleAction {
+    /// Pack the statement of a problem as Markdown, its images and its
+    /// samples as test files into a zip archive.
+    Export {
+        /// URL of the problem (or "group/probset/number"). Use "." for the last
+        /// operated problem, or "@name" for an alias.
+        #[arg()]
+        url: String,
+        /// Path of the archive to write.
+        #[arg(value_name = "FILE.zip")]
+        file: String,
+    },
+    /// Unpack a bundle into the cache, so the problem can be viewed and
+    /// tested offline.
+    Import {
+        /// Path of the archive to read.
+        #[arg(value_name = "FILE.zip")]
+        file: String,
+        /// Problem to import the bundle as. Default is the problem it was
+        /// exported from.
+        #[arg(long)]
+        url: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum StatsView {
+    /// Count the verdicts of your submissions per language and per problem
+    /// set, over the problems on your home page and those submitted to
+    /// through the CLI.
+    Breakdown,
+}
+
+#[derive(Subcommand)]
+enum AliasAction {
+    /// Save a URL, or "." for the last operated problem, as "@name".
+    Set {
+        #[arg()]
+        name: String,
+        #[arg()]
+        url: String,
+    },
+    /// List the aliases of the config and of oj.yaml.
+    #[command(visible_alias = "ls")]
+    List,
+    /// Remove an alias from the config.
+    #[command(visible_alias = "rm")]
+    Remove {
+        #[arg()]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum StandingsAction {
+    /// Write all pages of the ranking, one row per user with the result of
+    /// every problem (1 if accepted, 0 otherwise) and the totals.
+    Export {
+        #[arg()]
+        group: String,
+        #[arg()]
+        probset: String,
+        /// Write CSV, currently the only supported format.
+        #[arg(long, required = true)]
+        csv: bool,
+        /// File to write to, standard output by default.
+        #[arg(short, long)]
+        output: Option<String>,
     },
 }
 
@@ -159,7 +793,7 @@ enum ListType {
     },
 
     /// List all problem sets under a certain group.
-    /// Requests are constructed as http://{group}.openjudge.cn/
+    /// Requests are constructed as https://{group}.openjudge.cn/
     #[command(visible_alias = "P")]
     Probsets {
         #[arg()]
@@ -167,7 +801,7 @@ enum ListType {
     },
 
     /// List all problems under a problem set.
-    /// Requests are constructed as http://{group}.openjudge.cn/{probset}/
+    /// Requests are constructed as https://{group}.openjudge.cn/{probset}/
     #[command(visible_alias = "p")]
     Problems {
         #[arg()]
@@ -178,70 +812,383 @@ enum ListType {
         page: Option<u32>,
         #[arg(short = 's', long = "status")]
         show_status: bool,
+        /// List problems on all pages, ignores interactive mode.
+        #[arg(short, long, conflicts_with = "page")]
+        all: bool,
+        /// Save sample input/output of the listed problems to DIR as
+        /// {number}.in and {number}.out. In interactive mode, toggle the
+        /// problems to save with Space and confirm with Enter.
+        #[arg(long, value_name = "DIR", conflicts_with = "all")]
+        save_samples: Option<String>,
     },
 }
 
+fn init_tracing(verbose: u8) {
+    use tracing_subscriber::{filter::LevelFilter, prelude::*};
+    let level = match verbose {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_filter(level),
+        )
+        // kept whatever the verbosity, for crash reports
+        .with(crash::recent_logs_layer())
+        .init();
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    crash::install_panic_hook(get_crash_dir(), config_summary);
     let cli = Cli::parse();
+    init_tracing(cli.verbose);
+    let graphics = match &cli.command {
+        AppCommand::View { graphics, .. } | AppCommand::Daily { graphics, .. } => {
+            graphics.as_deref().map(str::parse).transpose()?
+        }
+        _ => None,
+    };
+    let session = Session::new()?
+        .skip_images(cli.no_images)
+        .graphics_protocol(graphics);
+    // browsing the history is left out of it, so its numbers stay put
+    let record = !matches!(cli.command, AppCommand::History { .. });
+    let result = run(&session, cli.command)
+        .await
+        .map_err(|e| session.snapshot_parse_error(e));
+    if record {
+        // a log that cannot be written should not fail the command
+        let _ = session.record_activity(&result);
+    }
+    result
+}
 
-    match cli.command {
+async fn run(session: &Session, command: AppCommand) -> Result<()> {
+    match command {
         AppCommand::Credentials { email } => {
-            process_credentials(email).await?;
+            process_credentials(session, email).await?;
         }
-        AppCommand::View { view_type } => match view_type {
+        AppCommand::View { view_type, .. } => match view_type {
             ViewType::User => {
-                view_user().await?;
+                view_user(session).await?;
+            }
+            ViewType::Problem {
+                urls,
+                download_attachments,
+            } => {
+                let url_refs: Vec<&str> = urls.iter().map(|s| s.as_str()).collect();
+                view_problems(
+                    session,
+                    &url_refs,
+                    download_attachments.as_deref().map(std::path::Path::new),
+                )
+                .await?;
             }
-            ViewType::Problem { url } => {
-                view_problem(&url).await?;
+            ViewType::Submission { url, copy } => {
+                view_submission(session, &url, copy).await?;
             }
-            ViewType::Submission { url } => {
-                view_submission(&url).await?;
+            ViewType::Board { url, page } => {
+                view_board(session, &url, page).await?;
+            }
+            ViewType::Image { url } => {
+                view_image(session, &url).await?;
+            }
+            ViewType::Standings {
+                group,
+                probset,
+                page,
+            } => {
+                view_standings(session, &group, &probset, page).await?;
             }
         },
-        AppCommand::Submit { url, file, lang } => {
-            let url_refs: Vec<&str> = url.iter().map(|s| s.as_str()).collect();
-            submit_solution(url_refs, &file, lang).await?;
+        AppCommand::Submit {
+            args,
+            lang,
+            no_verify,
+            force,
+            no_wait,
+        } => {
+            let arg_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+            submit_solution(session, arg_refs, lang, !no_verify, force, !no_wait).await?;
         }
         AppCommand::Test {
             url,
             file,
             lang,
             submit,
-        } => {
-            test_solution(&url, &file, lang, submit).await?;
-        }
+            no_verify,
+            force,
+            diff,
+            dir,
+            case,
+        } => match (dir, url) {
+            (Some(dir), _) => test_directory(session, &dir, diff).await?,
+            (None, url) => {
+                let url = url.as_deref().unwrap_or(".");
+                let options = TestOptions {
+                    submit,
+                    diff_style: diff,
+                    verify: !no_verify,
+                    force,
+                    cases: case,
+                };
+                test_solution(session, url, file, lang, options).await?;
+            }
+        },
         AppCommand::Search {
             group,
             query,
             interactive,
         } => {
-            search(&group, &query, interactive).await?;
+            search(session, &group, &query, interactive).await?;
         }
         AppCommand::List {
             list_type,
             interactive,
         } => match list_type {
             ListType::Submissions { problem_url } => {
-                list_submissions(&problem_url, interactive).await?;
+                list_submissions(session, &problem_url, interactive).await?;
             }
             ListType::Probsets { group } => {
-                list_probsets(&group, interactive).await?;
+                list_probsets(session, &group, interactive).await?;
+            }
+            ListType::Problems {
+                group,
+                probset,
+                show_status,
+                all: true,
+                ..
+            } => {
+                list_all_problems(session, &group, &probset, show_status).await?;
+            }
+            ListType::Problems {
+                group,
+                probset,
+                page,
+                save_samples: Some(dir),
+                ..
+            } => {
+                save_problem_samples(session, &group, &probset, page, &dir, interactive).await?;
             }
             ListType::Problems {
                 group,
                 probset,
                 page,
                 show_status,
+                ..
             } => {
-                list_problems(&group, &probset, page, show_status, interactive).await?;
+                list_problems(session, &group, &probset, page, show_status, interactive).await?;
             }
         },
-        AppCommand::Config { graphics } => {
-            configure(&graphics)?;
+        AppCommand::Status => {
+            status(session).await?;
+        }
+        AppCommand::Monitor => {
+            monitor(session).await?;
+        }
+        AppCommand::Recent => {
+            recent(session)?;
+        }
+        AppCommand::History { rerun: Some(n), .. } => {
+            rerun(n)?;
+        }
+        AppCommand::History {
+            limit,
+            problems: true,
+            ..
+        } => {
+            problem_history(limit)?;
+        }
+        AppCommand::History { limit, .. } => {
+            history(limit)?;
+        }
+        AppCommand::Stats { view: None } => {
+            stats()?;
+        }
+        AppCommand::Stats {
+            view: Some(StatsView::Breakdown),
+        } => {
+            stats_breakdown(session).await?;
+        }
+        AppCommand::Leaderboard { group, probset } => {
+            leaderboard(session, &group, &probset).await?;
+        }
+        AppCommand::SelfUpdate { check } => {
+            self_update::self_update(check).await?;
+        }
+        AppCommand::Doctor {
+            check: DoctorCheck::Net { group },
+        } => {
+            doctor_net(session, group.as_deref()).await?;
+        }
+        AppCommand::Backup { file } => {
+            backup_state(&file)?;
+        }
+        AppCommand::Restore { file, force } => {
+            restore_state(&file, force)?;
+        }
+        AppCommand::Edit { url, file, create } => {
+            edit(session, &url, file, create)?;
+        }
+        AppCommand::Bundle { action } => match action {
+            BundleAction::Export { url, file } => {
+                bundle_export(session, &url, &file).await?;
+            }
+            BundleAction::Import { file, url } => {
+                bundle_import(session, &file, url.as_deref())?;
+            }
+        },
+        AppCommand::Alias { action } => match action {
+            AliasAction::Set { name, url } => {
+                alias_set(session, &name, &url)?;
+            }
+            AliasAction::List => {
+                alias_list(session)?;
+            }
+            AliasAction::Remove { name } => {
+                alias_remove(session, &name)?;
+            }
+        },
+        AppCommand::Daily { group, probset, .. } => {
+            daily_problem(session, group.as_deref(), &probset).await?;
+        }
+        AppCommand::Standings {
+            action:
+                StandingsAction::Export {
+                    group,
+                    probset,
+                    output,
+                    ..
+                },
+        } => {
+            export_standings(session, &group, &probset, output.as_deref()).await?;
+        }
+        AppCommand::Join { group, message } => {
+            join_group(session, &group, message).await?;
+        }
+        AppCommand::Contest { action } => match action {
+            ContestAction::Status { group } => {
+                contest_status(session, &group).await?;
+            }
+            ContestAction::Remind {
+                group,
+                before,
+                detach,
+            } => {
+                contest_remind(session, &group, &before, detach).await?;
+            }
+            ContestAction::Init {
+                group,
+                probset,
+                ext,
+                dir,
+            } => {
+                contest_init(session, &group, &probset, &ext, &dir).await?;
+            }
+        },
+        AppCommand::Problem {
+            action:
+                ProblemAction::Create {
+                    group,
+                    probset,
+                    file,
+                    data,
+                },
+        } => {
+            create_problem(session, &group, &probset, &file, data).await?;
+        }
+        AppCommand::Group {
+            action: GroupAction::Requests { action },
+        } => match action {
+            JoinRequestAction::List { group } => {
+                list_join_requests(session, &group).await?;
+            }
+            JoinRequestAction::Approve { group, ids, all } => {
+                review_join_requests(session, &group, ids, all, true).await?;
+            }
+            JoinRequestAction::Reject { group, ids, all } => {
+                review_join_requests(session, &group, ids, all, false).await?;
+            }
+        },
+        AppCommand::Tui { group } => {
+            browse(session, group.as_deref()).await?;
+        }
+        AppCommand::Config(args) if args.list_themes => {
+            list_themes();
+        }
+        AppCommand::Config(args) => {
+            let ConfigArgs {
+                graphics,
+                https,
+                user_agent,
+                theme,
+                sixel_max_size,
+                sixel_diffusion,
+                image_max_rows,
+                time_format,
+                relative_time,
+                verdicts,
+                lang,
+                inline_select,
+                screen_reader,
+                hosts,
+                concurrency,
+                pool_size,
+                keep_alive,
+                cpp_std,
+                blocking_warnings,
+                python,
+                editor,
+                archive_dir,
+                add_friend,
+                remove_friend,
+                pre_submit_hook,
+                clear_pre_submit_hooks,
+                sanity_checks,
+                bell,
+                sound_command,
+                ..
+            } = *args;
+            configure(
+                session,
+                ConfigChanges {
+                    graphics,
+                    https,
+                    user_agent,
+                    theme,
+                    sixel_max_size,
+                    sixel_diffusion,
+                    image_max_rows,
+                    time_format,
+                    relative_time,
+                    verdicts,
+                    inline_select,
+                    cpp_std,
+                    blocking_warnings,
+                    python,
+                    editor,
+                    archive_dir,
+                    add_friends: add_friend,
+                    remove_friends: remove_friend,
+                    add_pre_submit_hooks: pre_submit_hook,
+                    clear_pre_submit_hooks,
+                    sanity_checks,
+                    bell,
+                    sound_command,
+                    lang,
+                    screen_reader,
+                    hosts,
+                    concurrency,
+                    pool_size,
+                    keep_alive,
+                },
+            )?;
         }
     }
 
     Ok(())
-}
\ No newline at end of file
+}
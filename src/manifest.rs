@@ -0,0 +1,78 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{Context, Result};
+use libopenjudge::ProblemUrl;
+use serde::{Deserialize, Serialize};
+
+pub const MANIFEST_FILE_NAME: &str = "oj.yaml";
+
+/// Project manifest read from `oj.yaml` in the working directory, like
+///
+/// ```yaml
+/// problems:
+///   A:
+///     url: http://cs101.openjudge.cn/practice/02750/
+///     file: a.cpp
+///     lang: c++
+/// aliases:
+///   hw5: http://cs101.openjudge.cn/practice/01005/
+/// ```
+#[derive(Serialize, Deserialize, Default)]
+pub struct Manifest {
+    #[serde(default)]
+    pub problems: BTreeMap<String, ManifestProblem>,
+    /// Problem URLs by name, used as `@name`. They take precedence over the
+    /// aliases in the config, so a project can share them.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ManifestProblem {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+}
+
+impl Manifest {
+    pub fn read_manifest<P>(manifest_path: P) -> Result<Option<Self>>
+    where
+        P: AsRef<Path>,
+    {
+        let manifest = match fs::read_to_string(manifest_path.as_ref()) {
+            Ok(manifest) => manifest,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let manifest: Manifest = serde_yaml::from_str(&manifest)
+            .context(format!("Parsing {}", manifest_path.as_ref().display()))?;
+        Ok(Some(manifest))
+    }
+
+    pub fn write_manifest<P>(&self, manifest_path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        fs::write(manifest_path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Reads `oj.yaml` from the current working directory, if there is one.
+    pub fn read_current() -> Result<Option<Self>> {
+        Self::read_manifest(MANIFEST_FILE_NAME)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&ManifestProblem> {
+        self.problems.get(key)
+    }
+
+    /// Key of the problem at `url`, if the manifest maps it.
+    pub fn key_of(&self, url: &ProblemUrl) -> Option<&str> {
+        self.problems
+            .iter()
+            .find(|(_, problem)| problem.url.parse::<ProblemUrl>().is_ok_and(|u| &u == url))
+            .map(|(key, _)| key.as_str())
+    }
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+/// Submissions made through the CLI whose verdict has not been seen yet, kept
+/// in `pending.json` in the config root, oldest first.
+#[derive(Serialize, Deserialize, Default)]
+pub struct PendingLog {
+    #[serde(default)]
+    pub submissions: Vec<PendingSubmission>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PendingSubmission {
+    /// URL of the submission page.
+    pub url: String,
+    /// URL of the problem submitted to.
+    pub problem: String,
+    /// Source file submitted.
+    pub file: String,
+    pub time: DateTime<FixedOffset>,
+}
+
+impl PendingLog {
+    pub fn push(&mut self, submission: PendingSubmission) {
+        self.submissions.push(submission);
+    }
+
+    /// Forgets the submission at `url`, once its verdict is known.
+    pub fn remove(&mut self, url: &str) {
+        self.submissions.retain(|submission| submission.url != url);
+    }
+}
@@ -0,0 +1,161 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result, anyhow};
+use libopenjudge::NewProblem;
+use pulldown_cmark::{Parser, html};
+use serde::Deserialize;
+
+/// Limits used when a problem description does not state them.
+const DEFAULT_TIME_LIMIT: u32 = 1000;
+const DEFAULT_MEMORY_LIMIT: u32 = 65536;
+
+/// Problem description written by a problem setter, either as YAML
+///
+/// ```yaml
+/// title: A+B
+/// time_limit: 1000 # milliseconds
+/// memory_limit: 65536 # kilobytes
+/// description: Add two numbers.
+/// sample_input: "1 2"
+/// sample_output: "3"
+/// data: data/ # test data, relative to this file
+/// ```
+///
+/// or as Markdown with the same keys in a front matter and the text in
+/// `## Description`, `## Input`, `## Output`, `## Sample Input`,
+/// `## Sample Output`, `## Hint` and `## Source` sections. Text is Markdown,
+/// samples are taken verbatim.
+#[derive(Deserialize, Default, PartialEq, Eq, Debug)]
+pub struct ProblemSpec {
+    pub title: Option<String>,
+    pub time_limit: Option<u32>,
+    pub memory_limit: Option<u32>,
+    pub description: Option<String>,
+    pub input: Option<String>,
+    pub output: Option<String>,
+    pub sample_input: Option<String>,
+    pub sample_output: Option<String>,
+    pub hint: Option<String>,
+    pub source: Option<String>,
+    /// Directory of test data files.
+    pub data: Option<String>,
+}
+
+impl ProblemSpec {
+    pub fn read<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .with_context(|| tr!("reading-file", path = path.display().to_string()))?;
+        let is_markdown = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("md"));
+        let spec = if is_markdown {
+            Self::from_markdown(&text)
+        } else {
+            serde_yaml::from_str(&text).map_err(Into::into)
+        };
+        spec.with_context(|| tr!("parsing-file", path = path.display().to_string()))
+    }
+
+    pub fn from_markdown(text: &str) -> Result<Self> {
+        let (mut spec, body) = match text
+            .strip_prefix("---")
+            .and_then(|rest| rest.split_once("\n---"))
+        {
+            Some((front_matter, body)) => (
+                serde_yaml::from_str::<Self>(front_matter)?,
+                body.split_once('\n').map_or("", |(_, body)| body),
+            ),
+            None => (Self::default(), text),
+        };
+        let mut section = "description".to_string();
+        let mut sections: Vec<(String, String)> = vec![];
+        for line in body.lines() {
+            if let Some(title) = line.strip_prefix("# ")
+                && spec.title.is_none()
+            {
+                spec.title = Some(title.trim().to_string());
+                continue;
+            }
+            if let Some(heading) = line.strip_prefix("## ") {
+                section = heading.trim().to_lowercase().replace(' ', "_");
+                continue;
+            }
+            match sections.iter_mut().find(|(name, _)| *name == section) {
+                Some((_, content)) => {
+                    content.push_str(line);
+                    content.push('\n');
+                }
+                None => sections.push((section.clone(), format!("{}\n", line))),
+            }
+        }
+        for (name, content) in sections {
+            let content = content.trim();
+            if content.is_empty() {
+                continue;
+            }
+            let field = match name.as_str() {
+                "description" => &mut spec.description,
+                "input" => &mut spec.input,
+                "output" => &mut spec.output,
+                "sample_input" => &mut spec.sample_input,
+                "sample_output" => &mut spec.sample_output,
+                "hint" => &mut spec.hint,
+                "source" => &mut spec.source,
+                _ => return Err(anyhow!(tr!("unknown-section", name = name))),
+            };
+            *field = Some(content.to_string());
+        }
+        Ok(spec)
+    }
+
+    /// The problem as posted to OpenJudge, with Markdown rendered to HTML.
+    pub fn to_new_problem(&self) -> Result<NewProblem> {
+        let title = self
+            .title
+            .clone()
+            .ok_or_else(|| anyhow!(tr!("spec-no-title")))?;
+        let description = self
+            .description
+            .as_deref()
+            .map(markdown_to_html)
+            .ok_or_else(|| anyhow!(tr!("spec-no-description")))?;
+        Ok(NewProblem {
+            title,
+            description,
+            input: self.input.as_deref().map(markdown_to_html),
+            output: self.output.as_deref().map(markdown_to_html),
+            sample_input: self.sample_input.as_deref().map(sample_to_html),
+            sample_output: self.sample_output.as_deref().map(sample_to_html),
+            hint: self.hint.as_deref().map(markdown_to_html),
+            source: self.source.clone(),
+            time_limit: self.time_limit.unwrap_or(DEFAULT_TIME_LIMIT),
+            memory_limit: self.memory_limit.unwrap_or(DEFAULT_MEMORY_LIMIT),
+        })
+    }
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    let mut output = String::new();
+    html::push_html(&mut output, Parser::new(markdown));
+    output
+}
+
+/// Escapes a sample for the `<pre>` it is shown in, dropping the code fence
+/// it may be written in.
+fn sample_to_html(sample: &str) -> String {
+    let sample = sample.trim();
+    let sample = sample
+        .strip_prefix("```")
+        .and_then(|rest| rest.split_once('\n'))
+        .and_then(|(_, rest)| rest.trim_end().strip_suffix("```"))
+        .unwrap_or(sample);
+    sample
+        .trim_end()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,135 @@
+use std::sync::LazyLock;
+
+use libopenjudge::Language;
+use onig::Regex;
+
+use crate::diagnostics::Diagnostic;
+
+/// Features of C++17 and later, which do not compile under the gnu++14 the
+/// judge uses, with what they are called in warnings.
+static CPP17_FEATURES: LazyLock<Vec<(Regex, &str)>> = LazyLock::new(|| {
+    [
+        (
+            r"#\s*include\s*<(optional|variant|string_view|any|charconv|filesystem|execution)>",
+            "this header",
+        ),
+        (
+            r"\bstd::(optional|variant|string_view|any|clamp|gcd|lcm|size)\b",
+            "this part of the standard library",
+        ),
+        (r"\bif\s+constexpr\b", "`if constexpr`"),
+        (
+            r"\bauto\s*&{0,2}\s*\[\s*\w+\s*(,\s*\w+\s*)*\]",
+            "structured bindings",
+        ),
+        (
+            r"\[\[\s*(nodiscard|maybe_unused|fallthrough)\s*\]\]",
+            "this attribute",
+        ),
+    ]
+    .into_iter()
+    .map(|(pattern, name)| (Regex::new(pattern).unwrap(), name))
+    .collect()
+});
+
+static INT_MAIN_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bint\s+main\s*\(").unwrap());
+static RETURN_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\breturn\b").unwrap());
+static INT128_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b__int128").unwrap());
+static GETS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?<![\w:.>])gets\s*\(").unwrap());
+static ENDL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bendl\b").unwrap());
+static SYNC_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"sync_with_stdio\s*\(\s*(false|0)\s*\)").unwrap());
+
+/// `endl`s past which output is considered heavy enough for flushing to cost
+/// time.
+const ENDL_HEAVY: usize = 3;
+
+/// A line of `source` with its line comment removed, so commented out code
+/// does not raise warnings.
+fn code_of(line: &str) -> &str {
+    line.split_once("//").map_or(line, |(code, _)| code)
+}
+
+fn warning(line: usize, column: usize, message: &str) -> Diagnostic {
+    Diagnostic {
+        line,
+        column: Some(column),
+        severity: "warning".to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// First match of `pattern` in the code of `source`, as a 1-based line and
+/// column.
+fn find(source: &str, pattern: &Regex) -> Option<(usize, usize)> {
+    source.lines().enumerate().find_map(|(i, line)| {
+        let code = code_of(line);
+        pattern
+            .find(code)
+            .map(|(start, _)| (i + 1, code[..start].chars().count() + 1))
+    })
+}
+
+/// Looks for things in a C or C++ solution that commonly go wrong on
+/// OpenJudge although they work locally. Other languages are not checked.
+pub fn sanity_check(source: &str, lang: &Language) -> Vec<Diagnostic> {
+    if !matches!(lang, Language::Gcc | Language::Gpp) {
+        return vec![];
+    }
+    let mut warnings = vec![];
+    if let Some((line, column)) = find(source, &INT_MAIN_RE) {
+        let body = source
+            .lines()
+            .skip(line - 1)
+            .map(code_of)
+            .collect::<Vec<_>>();
+        if !body.iter().any(|code| RETURN_RE.find(code).is_some()) {
+            warnings.push(warning(
+                line,
+                column,
+                "`main` does not return a value, some compilers of the judge expect `return 0;`",
+            ));
+        }
+    }
+    if let Some((line, column)) = find(source, &INT128_RE) {
+        warnings.push(warning(
+            line,
+            column,
+            "`__int128` is a compiler extension the judge may not support",
+        ));
+    }
+    if let Some((line, column)) = find(source, &GETS_RE) {
+        warnings.push(warning(
+            line,
+            column,
+            "`gets` was removed from C11 and C++14, use `fgets` or `getline` instead",
+        ));
+    }
+    if *lang == Language::Gpp {
+        for (pattern, name) in CPP17_FEATURES.iter() {
+            if let Some((line, column)) = find(source, pattern) {
+                warnings.push(warning(
+                    line,
+                    column,
+                    &format!("{} needs C++17, but the judge compiles with gnu++14", name),
+                ));
+            }
+        }
+        let endls = source
+            .lines()
+            .map(|line| ENDL_RE.find_iter(code_of(line)).count())
+            .sum::<usize>();
+        if endls >= ENDL_HEAVY
+            && find(source, &SYNC_RE).is_none()
+            && let Some((line, column)) = find(source, &ENDL_RE)
+        {
+            warnings.push(warning(
+                line,
+                column,
+                "`endl` flushes on every line and streams are synced with stdio, large outputs may exceed the time limit; prefer '\\n' and `ios::sync_with_stdio(false)`",
+            ));
+        }
+    }
+    warnings.sort_by_key(|warning| (warning.line, warning.column));
+    warnings
+}
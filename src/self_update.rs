@@ -0,0 +1,188 @@
+use std::{env, fs, path::Path};
+
+use anyhow::{Context, Result, anyhow};
+use colored::Colorize;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::utils::progress::spin;
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/djdjz7/openjudge_cli/releases/latest";
+
+#[derive(Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// Name of the release asset built for this platform, e.g.
+/// `oj-x86_64-linux` or `oj-x86_64-windows.exe`.
+pub fn asset_name() -> String {
+    format!(
+        "oj-{}-{}{}",
+        env::consts::ARCH,
+        env::consts::OS,
+        env::consts::EXE_SUFFIX
+    )
+}
+
+/// Whether version `latest` is newer than `current`, both like `v1.2.3` or
+/// `1.2.3`. Pre-release suffixes are ignored.
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    let parse = |version: &str| {
+        version
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse::<u64>().unwrap_or(0))
+            .collect::<Vec<_>>()
+    };
+    parse(latest) > parse(current)
+}
+
+/// Finds the checksum of `name` in `sha256sum` output, which is either a
+/// bare hash or lines of `HASH  NAME`.
+pub fn find_checksum(sums: &str, name: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        match parts.next() {
+            Some(file) if file.trim_start_matches('*') != name => None,
+            _ => Some(hash.to_lowercase()),
+        }
+    })
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Replaces the running executable with `binary`. The new file is written
+/// next to it first, so a failed write or rename leaves the current one
+/// intact.
+fn replace_executable(binary: &[u8]) -> Result<()> {
+    let current = env::current_exe()?;
+    let staged = current.with_extension("new");
+    fs::write(&staged, binary)
+        .with_context(|| tr!("writing-file", path = staged.display().to_string()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+    // Windows cannot overwrite a running executable, but can rename it
+    let old = current.with_extension("old");
+    if cfg!(windows) {
+        let _ = fs::remove_file(&old);
+        fs::rename(&current, &old)?;
+    }
+    if let Err(e) = fs::rename(&staged, &current) {
+        if cfg!(windows) {
+            // put the running executable back, or there is none left to run
+            let _ = fs::rename(&old, &current);
+        }
+        let _ = fs::remove_file(&staged);
+        return Err(e).with_context(|| tr!("replacing-file", path = current.display().to_string()));
+    }
+    Ok(())
+}
+
+/// Updates to the latest GitHub release if it is newer than this build. With
+/// `check`, only reports whether there is one.
+pub async fn self_update(check: bool) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .user_agent(format!("openjudge-cli/{}", env!("CARGO_PKG_VERSION")))
+        .build()?;
+    let release = spin(tr!("checking-for-updates"), async {
+        let body = client
+            .get(LATEST_RELEASE_URL)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        Ok::<_, anyhow::Error>(serde_json::from_str::<Release>(&body)?)
+    })
+    .await
+    .context(tr!("fetching-latest-release"))?;
+    let current = env!("CARGO_PKG_VERSION");
+    if !is_newer(&release.tag_name, current) {
+        println!(
+            "{}",
+            tr!("up-to-date", version = current.bold().to_string())
+        );
+        return Ok(());
+    }
+    println!(
+        "{}",
+        tr!(
+            "new-version-available",
+            version = release.tag_name.green().bold().to_string(),
+            current = current
+        )
+    );
+    if check {
+        return Ok(());
+    }
+    let name = asset_name();
+    let find_asset = |name: &str| release.assets.iter().find(|asset| asset.name == name);
+    let asset =
+        find_asset(&name).ok_or_else(|| anyhow!(tr!("no-prebuilt-binary", name = name.clone())))?;
+    let sums_asset = find_asset(&format!("{}.sha256", name))
+        .or_else(|| find_asset("SHA256SUMS"))
+        .ok_or_else(|| anyhow!(tr!("no-release-checksum", name = name.clone())))?;
+    let download = |url: String| {
+        let client = &client;
+        async move {
+            let response = client.get(url).send().await?.error_for_status()?;
+            Ok::<_, anyhow::Error>(response.bytes().await?)
+        }
+    };
+    let sums = spin(
+        tr!("downloading-checksums"),
+        download(sums_asset.browser_download_url.clone()),
+    )
+    .await?;
+    let expected = find_checksum(&String::from_utf8_lossy(&sums), &name)
+        .ok_or_else(|| anyhow!(tr!("no-release-checksum", name = name.clone())))?;
+    let binary = spin(
+        tr!("downloading", file = name.clone()),
+        download(asset.browser_download_url.clone()),
+    )
+    .await?;
+    let actual = sha256_hex(&binary);
+    if actual != expected {
+        return Err(anyhow!(tr!(
+            "checksum-mismatch",
+            name = name.clone(),
+            expected = expected,
+            actual = actual
+        )));
+    }
+    replace_executable(&binary)?;
+    println!(
+        "{}",
+        tr!(
+            "updated-to",
+            path = env::current_exe()
+                .as_deref()
+                .map(Path::display)
+                .map(|path| path.to_string())
+                .unwrap_or_default(),
+            version = release.tag_name.green().bold().to_string()
+        )
+    );
+    Ok(())
+}
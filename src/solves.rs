@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeDelta};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// When problems were first viewed and first accepted, kept in `solves.json`
+/// in the config root and keyed by problem URL.
+#[derive(Serialize, Deserialize, Default)]
+pub struct SolveLog {
+    #[serde(default)]
+    pub problems: BTreeMap<String, SolveRecord>,
+    /// Accepted submissions made through the CLI per local date.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub accepted_days: BTreeMap<NaiveDate, u32>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct SolveRecord {
+    pub title: Option<String>,
+    pub first_viewed: Option<DateTime<FixedOffset>>,
+    pub first_accepted: Option<DateTime<FixedOffset>>,
+    /// Submissions made through the CLI, up to and after the first accepted.
+    #[serde(default)]
+    pub submissions: u32,
+    /// Hashes of the sources submitted through the CLI, see [`source_hash`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_hashes: Vec<String>,
+}
+
+/// SHA-256 of `code` with line endings and trailing whitespace normalized,
+/// so saving a file again in another editor does not change it.
+pub fn source_hash(code: &str) -> String {
+    let normalized = code
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    Sha256::digest(normalized.trim_matches('\n').as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+impl SolveRecord {
+    /// Time from the first view to the first accepted submission.
+    pub fn time_to_solve(&self) -> Option<TimeDelta> {
+        let solve_time = self.first_accepted? - self.first_viewed?;
+        (solve_time >= TimeDelta::zero()).then_some(solve_time)
+    }
+
+    /// Time of the latest recorded event.
+    pub fn last_activity(&self) -> Option<DateTime<FixedOffset>> {
+        self.first_accepted.max(self.first_viewed)
+    }
+}
+
+impl SolveLog {
+    /// Notes that the problem at `url` was viewed at `time`, only the first
+    /// view is kept.
+    pub fn record_view(&mut self, url: &str, title: &str, time: DateTime<FixedOffset>) {
+        let record = self.problems.entry(url.to_string()).or_default();
+        record.title = Some(title.to_string());
+        record.first_viewed.get_or_insert(time);
+    }
+
+    /// Notes a submission to the problem at `url`.
+    pub fn record_submission(&mut self, url: &str, accepted: bool, time: DateTime<FixedOffset>) {
+        let record = self.problems.entry(url.to_string()).or_default();
+        record.submissions += 1;
+        if accepted {
+            record.first_accepted.get_or_insert(time);
+            *self.accepted_days.entry(time.date_naive()).or_default() += 1;
+        }
+    }
+
+    /// Notes that the source with `hash` was submitted to the problem at `url`.
+    pub fn record_source(&mut self, url: &str, hash: &str) {
+        let record = self.problems.entry(url.to_string()).or_default();
+        if !record.source_hashes.iter().any(|known| known == hash) {
+            record.source_hashes.push(hash.to_string());
+        }
+    }
+
+    /// Whether the source with `hash` was already submitted to the problem at
+    /// `url` through the CLI.
+    pub fn was_submitted(&self, url: &str, hash: &str) -> bool {
+        self.problems
+            .get(url)
+            .is_some_and(|record| record.source_hashes.iter().any(|known| known == hash))
+    }
+
+    /// Durations of all solved problems with a recorded first view, shortest
+    /// first.
+    pub fn solve_times(&self) -> Vec<TimeDelta> {
+        let mut times = self
+            .problems
+            .values()
+            .filter_map(SolveRecord::time_to_solve)
+            .collect::<Vec<_>>();
+        times.sort();
+        times
+    }
+
+    /// Accepted submissions per local date. Dates from before they were
+    /// counted have their first accepted submissions, one per problem.
+    pub fn accepted_per_day(&self) -> BTreeMap<NaiveDate, u32> {
+        let mut days = self.accepted_days.clone();
+        let mut earlier = BTreeMap::<NaiveDate, u32>::new();
+        for time in self
+            .problems
+            .values()
+            .filter_map(|record| record.first_accepted)
+        {
+            *earlier.entry(time.date_naive()).or_default() += 1;
+        }
+        for (date, count) in earlier {
+            days.entry(date).or_insert(count);
+        }
+        days
+    }
+}
+
+/// Current and longest runs of consecutive dates in `days`. The current run
+/// ends today, or yesterday while nothing has been accepted today yet.
+pub fn streaks(days: &BTreeMap<NaiveDate, u32>, today: NaiveDate) -> (u32, u32) {
+    let (mut longest, mut run, mut previous) = (0, 0, None::<NaiveDate>);
+    for &date in days.keys().filter(|date| **date <= today) {
+        run = match previous {
+            Some(previous) if previous.succ_opt() == Some(date) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(date);
+    }
+    let current = match previous {
+        Some(last) if last == today || last.succ_opt() == Some(today) => run,
+        _ => 0,
+    };
+    (current, longest)
+}
@@ -1,5 +1,10 @@
 #![cfg(test)]
 use crate::app::*;
+use crate::utils::html::{
+    GraphicsProtocol, RenderMode, attachment_links, image_name, image_sources, render_html,
+    wrap_text,
+};
+use crate::utils::width::{Align, display_width, pad, truncate};
 #[test]
 fn prefix() {
     assert_eq!(strip_slashes("/123"), "123");
@@ -19,3 +24,1269 @@ fn both() {
 fn none() {
     assert_eq!(strip_slashes("123"), "123");
 }
+
+#[tokio::test]
+async fn render_plain_keeps_preformatted_text() {
+    let html = "<pre>1 2\n3  4</pre><p><b>bold</b> <img src=\"a.png\"/>text</p>";
+    assert_eq!(
+        render_html(html, RenderMode::Plain).await,
+        "1 2\n3  4\nbold text\n"
+    );
+}
+
+#[tokio::test]
+async fn render_styled_marks_disabled_images() {
+    colored::control::set_override(false);
+    let html = "<p>see <img src=\"a.png\"/></p>";
+    assert_eq!(
+        render_html(html, RenderMode::Styled(GraphicsProtocol::Disabled.into())).await,
+        "\nsee [Image src a.png]\n\n"
+    );
+}
+
+#[test]
+fn image_sources_are_unique_and_ordered() {
+    let html = "<img src=\" b.png \"/><p><img src=\"a.png\"/><img src=\"b.png\"/><img/></p>";
+    assert_eq!(image_sources(html), ["b.png", "a.png"]);
+}
+
+#[test]
+fn attachments_are_linked_files() {
+    let html = "<a href=\"data.ZIP\">data</a><a href=\"/problem/2/\">next</a>\
+                <a href=\"doc/spec.pdf?v=2#p3\">spec</a><a href=\"data.ZIP\">again</a>";
+    assert_eq!(attachment_links(html), ["data.ZIP", "doc/spec.pdf?v=2#p3"]);
+    assert_eq!(
+        attachment_file_name("http://a.cn/files/%E6%95%B0%E6%8D%AE.zip?v=2", 0),
+        "数据.zip"
+    );
+    assert_eq!(
+        attachment_file_name("http://a.cn/files/a%2Fb.in", 0),
+        "a_b.in"
+    );
+    assert_eq!(attachment_file_name("http://a.cn/", 2), "attachment-3");
+}
+
+#[test]
+fn header_problem_urls() {
+    let source = "#!/usr/bin/env python3\n# 01005 (http://cs101.openjudge.cn/practice/01005/).\n";
+    assert_eq!(
+        header_problem_url(source).as_deref(),
+        Some("http://cs101.openjudge.cn/practice/01005/")
+    );
+    let late = format!(
+        "{}// http://cs101.openjudge.cn/practice/01005/\n",
+        "\n".repeat(10)
+    );
+    assert_eq!(header_problem_url(&late), None);
+    assert_eq!(header_problem_url("// https://example.com/a.txt\n"), None);
+}
+
+#[test]
+fn verdicts_are_broken_down_by_key() {
+    use libopenjudge::SubmissionResult;
+    let compile_error = SubmissionResult::CompileError {
+        message: Some("oops".to_string()),
+    };
+    let submissions = [
+        ("Python3", &SubmissionResult::TimeLimitExceeded),
+        ("G++", &SubmissionResult::Accepted),
+        ("Python3", &SubmissionResult::Accepted),
+        ("G++", &compile_error),
+        ("Python3", &SubmissionResult::TimeLimitExceeded),
+        ("G++", &SubmissionResult::PresentationError),
+    ];
+    let breakdown = verdict_breakdown(submissions);
+    assert_eq!(
+        breakdown.into_iter().collect::<Vec<_>>(),
+        [
+            ("G++".to_string(), [1, 0, 0, 0, 0, 1, 1]),
+            ("Python3".to_string(), [1, 0, 2, 0, 0, 0, 0]),
+        ]
+    );
+}
+
+#[test]
+fn duplicate_sources_are_found() {
+    use crate::solves::{SolveLog, source_hash};
+    let hash = source_hash("int main() {}  \r\n\n");
+    assert_eq!(hash, source_hash("\nint main() {}"));
+    assert_ne!(hash, source_hash("int main() { }"));
+    let mut log = SolveLog::default();
+    log.record_source("a", &hash);
+    log.record_source("a", &hash);
+    assert_eq!(log.problems["a"].source_hashes.len(), 1);
+    assert!(log.was_submitted("a", &hash));
+    assert!(!log.was_submitted("b", &hash));
+}
+
+#[test]
+fn new_sources_name_their_problem() {
+    let url = "http://cs101.openjudge.cn/practice/01005/";
+    for extension in ["cpp", "py", "pas"] {
+        let source = new_source(url, extension, Some("main\n"));
+        assert!(source.ends_with("\nmain\n"));
+        assert_eq!(header_problem_url(&source).as_deref(), Some(url));
+    }
+    assert_eq!(new_source(url, "PY", None), format!("# {}\n", url));
+}
+
+#[test]
+fn archive_paths() {
+    use libopenjudge::ProblemUrl;
+    use std::path::Path;
+    assert_eq!(slugify("A+B Problem (Easy)"), "a-b-problem-easy");
+    assert_eq!(slugify("  两数之和 II "), "两数之和-ii");
+    let url: ProblemUrl = "http://cs101.openjudge.cn/practice/01005/".parse().unwrap();
+    assert_eq!(
+        archive_path(
+            Path::new("archive"),
+            &url,
+            "I Think I Need a Houseboat",
+            "py"
+        ),
+        Path::new("archive/cs101/practice/01005-i-think-i-need-a-houseboat.py")
+    );
+    assert_eq!(
+        archive_path(Path::new("archive"), &url, "!!!", "cpp"),
+        Path::new("archive/cs101/practice/01005.cpp")
+    );
+}
+
+#[test]
+fn statements_convert_to_markdown() {
+    use crate::utils::html::html_to_markdown;
+    let html = "<h2>Note</h2><p>Given <b>n</b>, print 2<sup>n</sup>.<br>Then \
+                <a href=\"/faq/\">read</a> <code>stdin</code>.</p>\
+                <ul><li>one</li><li>two</li></ul><pre>1  2\n3</pre>\
+                <p><img src=\"http://media.openjudge.cn/a.png\" alt=\"fig\"><img src=\"b.png\"></p>";
+    let image_path = |src: &str| src.ends_with("a.png").then(|| "images/1-a.png".to_string());
+    assert_eq!(
+        html_to_markdown(html, &image_path),
+        "### Note\n\n\
+         Given **n**, print 2<sup>n</sup>.\n\
+         Then [read](/faq/) `stdin`.\n\n\
+         - one\n- two\n\n\
+         ```\n1  2\n3\n```\n\n\
+         ![fig](images/1-a.png)![](b.png)"
+    );
+}
+
+#[test]
+fn bundled_statements_read_as_problem_specs() {
+    use crate::bundle::problem_markdown;
+    use crate::problem_spec::ProblemSpec;
+    use libopenjudge::{Problem, ProblemLimits};
+    let problem = Problem {
+        title: "A+B".to_string(),
+        group: "cs101".to_string(),
+        probset: "practice".to_string(),
+        limits: ProblemLimits {
+            total_time: Some(1000),
+            memory: Some(65536),
+            ..Default::default()
+        },
+        description: "<p>Add two numbers.</p>".to_string(),
+        input: Some("<p>Two integers.</p>".to_string()),
+        output: None,
+        sample_input: Some("<pre>1 2</pre>".to_string()),
+        sample_output: Some("<pre>3</pre>".to_string()),
+        hint: None,
+        source: Some("Classic".to_string()),
+    };
+    let markdown = problem_markdown(
+        &problem,
+        "http://cs101.openjudge.cn/practice/1/",
+        ("1 2\n", "3\n"),
+        &|_| None,
+    );
+    let spec = ProblemSpec::from_markdown(&markdown).unwrap();
+    assert_eq!(spec.title.as_deref(), Some("A+B"));
+    assert_eq!(spec.time_limit, Some(1000));
+    assert_eq!(spec.memory_limit, Some(65536));
+    assert_eq!(spec.description.as_deref(), Some("Add two numbers."));
+    assert_eq!(spec.input.as_deref(), Some("Two integers."));
+    assert_eq!(spec.output, None);
+    assert_eq!(spec.sample_input.as_deref(), Some("```\n1 2\n```"));
+    assert_eq!(spec.source.as_deref(), Some("Classic"));
+    assert_eq!(spec.data.as_deref(), Some("tests/"));
+}
+
+#[test]
+fn imported_bundles_read_offline() {
+    use crate::bundle::{exported_from, imported_problem, read_bundle, write_bundle};
+    let dir = std::env::temp_dir().join(format!("oj-bundle-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let statement = "---\n# exported from http://cs101.openjudge.cn/practice/1/\n\
+                     time_limit: 2000\n---\n# A+B\n\nAdd ![fig](images/1-a.png).\n\n\
+                     ## Sample Input\n\n```\n1 2\n```\n";
+    assert_eq!(
+        exported_from(statement),
+        Some("http://cs101.openjudge.cn/practice/1/")
+    );
+    assert_eq!(exported_from("# A+B\n# exported from nowhere\n"), None);
+    let archive = dir.join("bundle.zip");
+    let files = vec![
+        ("problem.md".to_string(), statement.as_bytes().to_vec()),
+        ("tests/1.in".to_string(), b"1 2\n".to_vec()),
+    ];
+    write_bundle(&archive, &files).unwrap();
+    assert_eq!(read_bundle(&archive).unwrap(), files);
+    let url = libopenjudge::ProblemUrl::new("cs101", "practice", "1");
+    let unpacked = dir.join("unpacked");
+    assert!(imported_problem(&unpacked, &url).unwrap().is_none());
+    std::fs::create_dir_all(&unpacked).unwrap();
+    std::fs::write(unpacked.join("problem.md"), statement).unwrap();
+    let problem = imported_problem(&unpacked, &url).unwrap().unwrap();
+    assert_eq!(problem.title, "A+B");
+    assert_eq!(problem.group, "cs101");
+    assert_eq!(problem.limits.total_time, Some(2000));
+    let image = url::Url::from_directory_path(&unpacked)
+        .unwrap()
+        .join("images/1-a.png")
+        .unwrap();
+    assert!(problem.description.contains(&format!("src=\"{}\"", image)));
+    assert_eq!(problem.sample_output, None);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn solve_streaks() {
+    use crate::solves::{SolveLog, streaks};
+    use chrono::{NaiveDate, TimeZone};
+    let date = |day| NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+    let time = |day| {
+        chrono::FixedOffset::east_opt(8 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2024, 3, day, 23, 30, 0)
+            .unwrap()
+    };
+    let mut log = SolveLog::default();
+    log.record_submission("a", true, time(1));
+    log.record_submission("b", true, time(2));
+    log.record_submission("b", true, time(2));
+    log.record_submission("c", false, time(3));
+    for day in [5, 6, 7] {
+        log.record_submission("d", true, time(day));
+    }
+    // solved before days were counted
+    log.problems.get_mut("a").unwrap().first_accepted = Some(time(1));
+    log.accepted_days.remove(&date(1));
+    let days = log.accepted_per_day();
+    assert_eq!(
+        days.into_iter().collect::<Vec<_>>(),
+        [
+            (date(1), 1),
+            (date(2), 2),
+            (date(5), 1),
+            (date(6), 1),
+            (date(7), 1)
+        ]
+    );
+    let days = log.accepted_per_day();
+    assert_eq!(streaks(&days, date(7)), (3, 3));
+    // the streak holds until a day passes without a solve
+    assert_eq!(streaks(&days, date(8)), (3, 3));
+    assert_eq!(streaks(&days, date(9)), (0, 3));
+    assert_eq!(streaks(&days, date(2)), (2, 2));
+    assert_eq!(streaks(&Default::default(), date(2)), (0, 0));
+}
+
+#[test]
+fn heatmap_layout() {
+    use crate::display::heatmap;
+    use chrono::NaiveDate;
+    colored::control::set_override(false);
+    let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+    let days = [(date(2, 26), 1), (date(3, 6), 5)].into_iter().collect();
+    // Wednesday, so the last week stops after it. March starts a week after
+    // February and has no room for its name.
+    let map = heatmap(&days, date(3, 6), 2);
+    assert_eq!(
+        map,
+        "    Feb\n\
+         Mon ■ ·\n\
+         \x20   · ·\n\
+         Wed · ■\n\
+         \x20   ·\n\
+         Fri ·\n\
+         \x20   ·\n\
+         \x20   ·\n\
+         \x20   Less · ■ ■ ■ ■ More"
+    );
+}
+
+#[test]
+fn clipboard_sequences() {
+    use crate::utils::{clipboard::osc52, terminal::Multiplexer};
+    assert_eq!(osc52("int main", None), "\x1b]52;c;aW50IG1haW4=\x07");
+    assert_eq!(
+        osc52("", Some(Multiplexer::Tmux)),
+        "\x1bPtmux;\x1b\x1b]52;c;\x07\x1b\\"
+    );
+}
+
+#[test]
+fn recent_problem_references() {
+    for (reference, index) in [
+        (".", Some(0)),
+        ("~", Some(1)),
+        ("~0", Some(0)),
+        ("~3", Some(3)),
+        ("^", Some(1)),
+        ("^^", Some(2)),
+        ("~x", None),
+        ("", None),
+        ("cs101/practice/1", None),
+    ] {
+        assert_eq!(recent_index(reference), index, "{}", reference);
+    }
+    let mut recent = vec![];
+    for i in 0..12 {
+        push_recent(&mut recent, i.to_string());
+    }
+    push_recent(&mut recent, "5".to_string());
+    assert_eq!(recent, ["5", "11", "10", "9", "8", "7", "6", "4", "3", "2"]);
+}
+
+#[test]
+fn stepping_through_problems() {
+    assert_eq!(step_through("\n", 0, 3), Some(1));
+    assert_eq!(step_through("N", 1, 3), Some(2));
+    assert_eq!(step_through("next", 2, 3), None);
+    assert_eq!(step_through("p", 2, 3), Some(1));
+    assert_eq!(step_through("previous", 0, 3), Some(0));
+    assert_eq!(step_through("q", 1, 3), None);
+    assert_eq!(step_through("what", 1, 3), Some(1));
+}
+
+#[test]
+fn contest_keys() {
+    let keys = [0, 1, 25, 26, 27, 701, 702].map(contest_key);
+    assert_eq!(keys, ["A", "B", "Z", "AA", "AB", "ZZ", "AAA"]);
+}
+
+#[test]
+fn compiler_warnings_deduplicated() {
+    use crate::diagnostics::compiler_warnings;
+    let output = "a.cpp: In function 'int main()':\n\
+        a.cpp:2:35: warning: format '%d' expects argument of type 'int', but argument 2 has type 'long long int' [-Wformat=]\n\
+        a.cpp:2:47: warning: unused variable 'y' [-Wunused-variable]\n\
+        a.cpp:2:35: warning: format '%d' expects argument of type 'int', but argument 2 has type 'long long int' [-Wformat=]\n\
+        a.cpp:3:1: note: declared here\n";
+    let warnings = compiler_warnings(output);
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings[0].warning_flag(), Some("format"));
+    assert_eq!(warnings[1].warning_flag(), Some("unused-variable"));
+    let blocking = ["-Wformat".to_string(), "return-type".to_string()];
+    assert!(warnings[0].is_blocking(&blocking));
+    assert!(!warnings[1].is_blocking(&blocking));
+    let output = "cc1: warning: command-line option '-std=gnu++14' is valid for C++/ObjC++ but not for C\n\
+        collect2: error: ld returned 1 exit status\n";
+    let warnings = compiler_warnings(output);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].line, 0);
+    assert!(warnings[0].message.starts_with("command-line option"));
+}
+
+#[test]
+fn custom_test_cases() {
+    use crate::manifest::{Manifest, ManifestProblem};
+    let dir = std::env::temp_dir().join(format!("oj-cases-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for (name, input, output) in [("10", "3", "4"), ("2", "1", "2"), ("big", "5", "6")] {
+        std::fs::write(dir.join(format!("{}.in", name)), input).unwrap();
+        std::fs::write(dir.join(format!("{}.out", name)), output).unwrap();
+    }
+    let cases = read_case_dir(&dir).unwrap();
+    let inputs = cases
+        .iter()
+        .map(|case| case.input.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(inputs, ["1", "3", "5"]);
+    assert_eq!(cases[0].output, "2");
+    std::fs::write(dir.join("lonely.in"), "").unwrap();
+    assert!(read_case_dir(&dir).is_err());
+    assert!(read_case_dir(&dir.join("missing")).unwrap().is_empty());
+
+    let arg = format!(
+        "{}:{}",
+        dir.join("2.in").display(),
+        dir.join("2.out").display()
+    );
+    assert_eq!(read_case_arg(&arg).unwrap().output, "2");
+    assert!(read_case_arg("no-colon").is_err());
+    assert!(read_case_arg(":2.out").is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let url = libopenjudge::ProblemUrl::new("cs101", "practice", "02750");
+    assert_eq!(case_dir(&url, None), std::path::Path::new("tests/02750"));
+    let mut manifest = Manifest::default();
+    manifest.problems.insert(
+        "A".to_string(),
+        ManifestProblem {
+            url: url.to_string(),
+            file: None,
+            lang: None,
+        },
+    );
+    assert_eq!(
+        case_dir(&url, Some(&manifest)),
+        std::path::Path::new("tests/A")
+    );
+}
+
+#[test]
+fn json_state_files() {
+    use crate::pending::{PendingLog, PendingSubmission};
+    use crate::utils::json_file::{load_json, save_json};
+    let dir = std::env::temp_dir().join(format!("oj-state-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("pending.json");
+    let log: PendingLog = load_json(&path).unwrap();
+    assert!(log.submissions.is_empty());
+
+    let mut log = PendingLog::default();
+    log.push(PendingSubmission {
+        url: "https://cs101.openjudge.cn/practice/solution/1/".to_string(),
+        problem: "https://cs101.openjudge.cn/practice/02000/".to_string(),
+        file: "a.cpp".to_string(),
+        time: chrono::Local::now().fixed_offset(),
+    });
+    save_json(&path, &log).unwrap();
+    let read: PendingLog = load_json(&path).unwrap();
+    assert_eq!(read.submissions, log.submissions);
+    let files = std::fs::read_dir(&dir).unwrap().count();
+    assert_eq!(
+        files, 1,
+        "the temporary file is renamed over the state file"
+    );
+
+    std::fs::write(&path, "{").unwrap();
+    assert!(load_json::<PendingLog>(&path).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn theme_lookup() {
+    use crate::code_theme::load_theme;
+    assert!(load_theme(None).is_ok());
+    assert!(load_theme(Some("enki-tokyo-night")).is_ok());
+    assert!(load_theme(Some("inspiredgithub")).is_ok());
+    assert!(load_theme(Some("no-such-theme")).is_err());
+}
+
+#[test]
+fn wrap_breaks_at_spaces() {
+    assert_eq!(
+        wrap_text("hello brave new world", 11),
+        "hello brave\nnew world"
+    );
+    assert_eq!(wrap_text("abcdefgh", 3), "abc\ndef\ngh");
+}
+
+#[test]
+fn wrap_counts_cjk_as_double_width() {
+    assert_eq!(
+        wrap_text("一个笼子里面关了鸡和兔子", 8),
+        "一个笼子\n里面关了\n鸡和兔子"
+    );
+    assert_eq!(wrap_text("输入 n 个数", 6), "输入 n\n个数");
+}
+
+#[test]
+fn wrap_ignores_ansi_styling() {
+    let styled = "\x1b[1mbold\x1b[0m text";
+    assert_eq!(display_width(styled), 9);
+    assert_eq!(wrap_text(styled, 9), styled);
+    assert_eq!(wrap_text(styled, 5), "\x1b[1mbold\x1b[0m\ntext");
+}
+
+#[test]
+fn table_aligns_wide_cells() {
+    use crate::utils::table::Table;
+    colored::control::set_override(false);
+    let mut table = Table::new(["#", "Title"]).align_right(0);
+    table.push_row(["1", "鸡兔同笼"]);
+    table.push_row(["10", "A+B"]);
+    assert_eq!(
+        table.to_string(),
+        "┌────┬──────────┐\n\
+         │  # │ Title    │\n\
+         ├────┼──────────┤\n\
+         │  1 │ 鸡兔同笼 │\n\
+         │ 10 │ A+B      │\n\
+         └────┴──────────┘\n"
+    );
+}
+
+#[tokio::test]
+async fn render_plain_blockquote_and_code() {
+    let html = "<blockquote><p>note</p>second</blockquote>use <code>long long</code>";
+    assert_eq!(
+        render_html(html, RenderMode::Plain).await,
+        "\n> note\n> second\nuse long long"
+    );
+}
+
+#[tokio::test]
+async fn render_sub_and_superscripts() {
+    assert_eq!(
+        render_html("n ≤ 10<sup>5</sup>, a<sub>i</sub>", RenderMode::Plain).await,
+        "n ≤ 10⁵, aᵢ"
+    );
+    assert_eq!(
+        render_html("2<sup>k</sup> x<sub>bc</sub>", RenderMode::Plain).await,
+        "2^k x_(bc)"
+    );
+}
+
+#[tokio::test]
+async fn render_teletype_keeps_spacing() {
+    assert_eq!(
+        render_html("输入 <tt>n  m</tt> 和 <samp>a b</samp>", RenderMode::Plain).await,
+        "输入 n  m 和 a b"
+    );
+}
+
+#[test]
+fn probe_response_detection() {
+    use crate::utils::terminal::parse_probe_response;
+    assert!(matches!(
+        parse_probe_response("\x1b_Gi=31;OK\x1b\\\x1b[?62;22c"),
+        Some(GraphicsProtocol::Kitty)
+    ));
+    assert!(parse_probe_response("\x1b[?62;22c").is_none());
+    assert!(parse_probe_response("").is_none());
+    assert_eq!(
+        parse_probe_response("\x1b[?62;4;22c").is_some(),
+        cfg!(feature = "sixel")
+    );
+}
+
+#[test]
+fn tmux_passthrough_doubles_escapes() {
+    use crate::utils::terminal::{Multiplexer, passthrough};
+    assert_eq!(
+        passthrough("\x1b_Ga=q\x1b\\", Some(Multiplexer::Tmux)),
+        "\x1bPtmux;\x1b\x1b_Ga=q\x1b\x1b\\\x1b\\"
+    );
+    assert_eq!(passthrough("\x1b[c", None), "\x1b[c");
+}
+
+#[test]
+fn image_size_parsing() {
+    assert_eq!(parse_image_size("800x600").unwrap(), (800, 600));
+    assert_eq!(parse_image_size(" 1024X768 ").unwrap(), (1024, 768));
+    assert!(parse_image_size("800").is_err());
+    assert!(parse_image_size("0x600").is_err());
+}
+
+#[test]
+fn image_name_from_url() {
+    assert_eq!(
+        image_name("http://media.openjudge.cn/images/g1/2750/1.jpg?v=2"),
+        "1.jpg"
+    );
+    assert_eq!(
+        image_name("http://media.openjudge.cn/"),
+        "media.openjudge.cn"
+    );
+    assert_eq!(image_name("data:image/png;base64,AAAA"), "image");
+}
+
+#[test]
+fn side_by_side_diff_pairs_changed_lines() {
+    use crate::diff::side_by_side_diff;
+    colored::control::set_override(false);
+    assert_eq!(
+        side_by_side_diff("1 2\n3 4\n5", "1 2\n3 5\n5", 32),
+        "  │ Expected   │   │ Actual\n\
+         1 │ 1 2        │ 1 │ 1 2\n\
+         2 │ 3 4        │ 2 │ 3 5\n\
+         3 │ 5          │ 3 │ 5\n"
+    );
+    assert!(side_by_side_diff("a very long expected line", "b", 24).contains("a ver…"));
+}
+
+#[test]
+fn inline_diff_marks_changed_characters() {
+    use crate::diff::inline_segments;
+    let (left, right) = inline_segments("1 2 3", "1 4 3");
+    assert_eq!(
+        left,
+        vec![
+            (false, "1 ".to_string()),
+            (true, "2".to_string()),
+            (false, " 3".to_string())
+        ]
+    );
+    assert_eq!(
+        right,
+        vec![
+            (false, "1 ".to_string()),
+            (true, "4".to_string()),
+            (false, " 3".to_string())
+        ]
+    );
+}
+
+#[test]
+fn relative_times() {
+    use crate::display::relative_time;
+    let now = libopenjudge::parse_server_time("2025-03-01 12:00:00").unwrap();
+    let at = |text| libopenjudge::parse_server_time(text).unwrap();
+    assert_eq!(
+        relative_time(&at("2025-03-01 11:59:30"), now).unwrap(),
+        "just now"
+    );
+    assert_eq!(
+        relative_time(&at("2025-03-01 11:57:00"), now).unwrap(),
+        "3 minutes ago"
+    );
+    assert_eq!(
+        relative_time(&at("2025-03-01 11:00:00"), now).unwrap(),
+        "1 hour ago"
+    );
+    assert_eq!(
+        relative_time(&at("2025-02-27 12:00:00"), now).unwrap(),
+        "2 days ago"
+    );
+    assert!(relative_time(&at("2025-01-01 12:00:00"), now).is_none());
+    assert!(relative_time(&at("2025-03-02 12:00:00"), now).is_none());
+}
+
+#[test]
+fn width_aware_padding() {
+    assert_eq!(pad("张三", 6, Align::Left), "张三  ");
+    assert_eq!(pad("ab", 4, Align::Right), "  ab");
+    assert_eq!(pad("\x1b[1mab\x1b[0m", 3, Align::Left), "\x1b[1mab\x1b[0m ");
+    assert_eq!(truncate("北京大学", 5), "北京…");
+    assert_eq!(truncate("abc", 3), "abc");
+}
+
+#[test]
+fn compile_diagnostics_point_at_source() {
+    use crate::diagnostics::{Diagnostic, parse_diagnostics, render_diagnostics};
+    colored::control::set_override(false);
+    let message = "Main.cpp: In function 'int main()':\n\
+                   Main.cpp:2:5: error: 'x' was not declared in this scope\n";
+    assert_eq!(
+        parse_diagnostics(message),
+        vec![Diagnostic {
+            line: 2,
+            column: Some(5),
+            severity: "error".to_string(),
+            message: "'x' was not declared in this scope".to_string(),
+        }]
+    );
+    assert_eq!(
+        render_diagnostics(message, "int main() {\n\tx = 1;\n}"),
+        "error: 'x' was not declared in this scope\n \
+         --> line 2:5\n\
+         2 │ \tx = 1;\n  \
+         │ \t   ^\n"
+    );
+    assert_eq!(render_diagnostics("ld: oops", ""), "ld: oops");
+}
+
+#[tokio::test]
+async fn headings_and_rules_are_separated() {
+    colored::control::set_override(false);
+    let html = "<h2>输入</h2><hr/>";
+    let rendered = render_html(html, RenderMode::Styled(GraphicsProtocol::Disabled.into())).await;
+    assert!(rendered.starts_with("输入\n────\n\n─"));
+    assert_eq!(render_html("a<hr>b", RenderMode::Plain).await, "a\nb");
+}
+
+#[test]
+fn selector_scrolls_to_selection() {
+    use crate::utils::interactions::{row_starts, scroll_offset};
+    // viewport of 10 rows, options of 2 rows each
+    let starts = row_starts([2; 50]);
+    assert_eq!(scroll_offset(3, &starts, 10, 0), 0);
+    assert_eq!(scroll_offset(5, &starts, 10, 0), 2);
+    assert_eq!(scroll_offset(49, &starts, 10, 0), 90);
+    assert_eq!(scroll_offset(0, &starts, 10, 90), 0);
+    assert_eq!(scroll_offset(46, &starts, 10, 90), 90);
+    // options wrapped to different heights, one taller than the viewport
+    let starts = row_starts([1, 3, 12, 2]);
+    assert_eq!(starts, vec![0, 1, 4, 16, 18]);
+    assert_eq!(scroll_offset(1, &starts, 10, 0), 0);
+    assert_eq!(scroll_offset(2, &starts, 10, 0), 4);
+    assert_eq!(scroll_offset(3, &starts, 10, 4), 8);
+}
+
+#[test]
+fn selector_maps_clicks_to_options() {
+    use crate::utils::interactions::{option_at_row, row_starts};
+    // options of 2 rows below a 2 row header, scrolled down by 3 rows
+    let starts = row_starts([2; 10]);
+    assert_eq!(option_at_row(2, 2, 10, 3, &starts), Some(1));
+    assert_eq!(option_at_row(4, 2, 10, 3, &starts), Some(2));
+    assert_eq!(option_at_row(1, 2, 10, 3, &starts), None);
+    assert_eq!(option_at_row(12, 2, 10, 3, &starts), None);
+    // below the last option
+    assert_eq!(option_at_row(11, 2, 10, 3, &row_starts([2; 4])), None);
+}
+
+#[test]
+fn problem_preview_keeps_first_paragraph() {
+    use crate::display::problem_preview;
+    colored::control::set_override(false);
+    let problem = libopenjudge::Problem {
+        title: "A+B".to_string(),
+        group: "cs101".to_string(),
+        probset: "practice".to_string(),
+        limits: libopenjudge::ProblemLimits {
+            total_time: Some(1000),
+            memory: Some(65536),
+            ..Default::default()
+        },
+        description: "<p> </p><p>Add  two\nnumbers.</p><p>More.</p>".to_string(),
+        input: None,
+        output: None,
+        sample_input: None,
+        sample_output: None,
+        hint: None,
+        source: None,
+    };
+    assert_eq!(
+        problem_preview(&problem),
+        "A+B\n\nTime Limit: 1000ms\nMemory Limit: 65536kB\n\nAdd two numbers."
+    );
+}
+
+#[test]
+fn selector_jumps_to_typed_index() {
+    use crate::utils::interactions::jump_target;
+    assert_eq!(jump_target("1", 50), Some(0));
+    assert_eq!(jump_target("050", 50), Some(49));
+    assert_eq!(jump_target("51", 50), None);
+    assert_eq!(jump_target("0", 50), None);
+}
+
+#[test]
+fn status_bar_keeps_hint_visible() {
+    use crate::utils::interactions::StatusBar;
+    let status = StatusBar {
+        user: Some("me@example.com".to_string()),
+        group: Some("cs101".to_string()),
+        probset: Some("practice".to_string()),
+        page: Some((2, 5)),
+        pending: 1,
+    };
+    assert_eq!(
+        status.to_string(),
+        "me@example.com │ cs101/practice │ Page 2/5 │ 1 pending"
+    );
+    assert_eq!(status.line("? help", 30), " me@example.com │ cs1… ? help ");
+    assert_eq!(StatusBar::default().line("", 16), " Not logged in  ");
+}
+
+#[test]
+fn filter_matches_are_highlighted() {
+    use crate::utils::interactions::highlight_matches;
+    assert_eq!(
+        highlight_matches("A+B Problem", "b"),
+        "A+\x1b[7mB\x1b[27m Pro\x1b[7mb\x1b[27mlem"
+    );
+    assert_eq!(
+        highlight_matches("\x1b[1m鸡兔\x1b[0m同笼", "兔同"),
+        "\x1b[1m鸡\x1b[7m兔\x1b[0m\x1b[7m同\x1b[27m笼"
+    );
+    assert_eq!(highlight_matches("abc", ""), "abc");
+}
+
+#[test]
+fn sanity_checks_find_judge_pitfalls() {
+    use crate::sanity::sanity_check;
+    use libopenjudge::Language;
+    let source = "#include <optional>\n\
+                  int main() {\n\
+                  \t__int128 x;\n\
+                  \tchar s[9]; gets(s); // fgets(s)\n\
+                  \tauto [a, b] = f();\n\
+                  \tstd::cout << 1 << endl << 2 << endl << 3 << endl;\n\
+                  }";
+    let warnings = sanity_check(source, &Language::Gpp)
+        .into_iter()
+        .map(|warning| (warning.line, warning.column.unwrap_or(0)))
+        .collect::<Vec<_>>();
+    assert_eq!(warnings, [(1, 1), (2, 1), (3, 2), (4, 13), (5, 2), (6, 20)]);
+    assert_eq!(sanity_check(source, &Language::Gcc).len(), 3);
+    let fine = "int main() {\n\tstd::ios::sync_with_stdio(false);\n\treturn 0; // std::optional\n}";
+    assert!(sanity_check(fine, &Language::Gpp).is_empty());
+    assert!(sanity_check(source, &Language::Python3).is_empty());
+}
+
+#[test]
+fn free_pascal_diagnostics() {
+    use crate::diagnostics::{Diagnostic, parse_diagnostics};
+    let message = "Free Pascal Compiler version 3.2.2\n\
+                   main.pas(3,9) Error: Identifier not found \"x\"\n\
+                   main.pas(5) Fatal: There were 1 errors compiling module, stopping\n";
+    assert_eq!(
+        parse_diagnostics(message),
+        vec![Diagnostic {
+            line: 3,
+            column: Some(9),
+            severity: "error".to_string(),
+            message: "Identifier not found \"x\"".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn custom_language_templates() {
+    assert_eq!(
+        expand_command_template(
+            "gfortran -o {executable} {source} && echo {dir}/{stem}",
+            "src/a.f90",
+            "./sol.exe"
+        ),
+        "gfortran -o ./sol.exe src/a.f90 && echo src/a"
+    );
+    assert_eq!(
+        expand_command_template("java -cp {dir} {stem}", "Main.java", ""),
+        "java -cp . Main"
+    );
+}
+
+#[cfg(not(windows))]
+#[test]
+fn command_template_quoting() {
+    assert_eq!(
+        expand_command_template(
+            "g++ {source} -o {executable}",
+            "my solutions/a.cpp",
+            "my solutions/a"
+        ),
+        "g++ 'my solutions/a.cpp' -o 'my solutions/a'"
+    );
+    assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    assert_eq!(shell_quote(""), "''");
+
+    let dir = std::env::temp_dir().join(format!("oj quoting {}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("a b.txt");
+    std::fs::write(&source, "hello").unwrap();
+    let command = expand_command_template("cat {source}", source.to_str().unwrap(), "");
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "hello");
+
+    let hooks = ["test -f {source}".to_string()];
+    let file = source.to_str().unwrap();
+    let checked = run_pre_submit_hooks(&hooks, file);
+    std::fs::remove_dir_all(&dir).unwrap();
+    checked.unwrap();
+    assert!(run_pre_submit_hooks(&hooks, file).is_err());
+}
+
+#[test]
+fn cpp_standards() {
+    assert!(is_cpp_std("c++17"));
+    assert!(is_cpp_std("gnu++14"));
+    assert!(is_cpp_std("c++2a"));
+    assert!(!is_cpp_std("c++"));
+    assert!(!is_cpp_std("c17"));
+    assert!(!is_cpp_std("c++17 -O2"));
+}
+
+#[test]
+fn python_interpreter_from_venv() {
+    assert_eq!(
+        python_interpreter(Some(".venv/bin/python3.12")),
+        std::path::PathBuf::from(".venv/bin/python3.12")
+    );
+    let venv = std::env::temp_dir().join(format!("oj-venv-{}", std::process::id()));
+    std::fs::create_dir_all(&venv).unwrap();
+    let interpreter = python_interpreter(venv.to_str());
+    std::fs::remove_dir_all(&venv).unwrap();
+    assert!(interpreter.starts_with(&venv));
+    assert_ne!(interpreter, venv);
+}
+
+#[test]
+fn shebang_languages() {
+    use libopenjudge::Language;
+    assert_eq!(
+        language_from_shebang("#!/usr/bin/env python3"),
+        Some(Language::Python3)
+    );
+    assert_eq!(
+        language_from_shebang("#!/usr/bin/env -S pypy3 -O"),
+        Some(Language::PyPy3)
+    );
+    assert_eq!(
+        language_from_shebang("#! /usr/local/bin/python3.12"),
+        Some(Language::Python3)
+    );
+    assert_eq!(language_from_shebang("#!/usr/bin/python2"), None);
+    assert_eq!(language_from_shebang("#!/bin/sh"), None);
+    assert_eq!(language_from_shebang("import sys"), None);
+}
+
+#[test]
+fn standings_as_csv() {
+    use libopenjudge::{StandingsCell, StandingsRow};
+    let rows = vec![StandingsRow {
+        rank: 1,
+        user: "Doe, \"J\"".to_string(),
+        user_url: None,
+        solved: 1,
+        penalty: Some("1:05:00".to_string()),
+        cells: vec![
+            Some(StandingsCell {
+                accepted: true,
+                failed_attempts: 2,
+                time: Some("0:25:00".to_string()),
+            }),
+            Some(StandingsCell {
+                accepted: false,
+                failed_attempts: 1,
+                time: None,
+            }),
+        ],
+    }];
+    assert_eq!(
+        standings_csv(&["A".to_string(), "B".to_string(), "C".to_string()], &rows),
+        "Rank,User,Profile,A,B,C,Solved,Failed Attempts,Penalty\r\n\
+         1,\"Doe, \"\"J\"\"\",,1,0,0,1,3,1:05:00\r\n"
+    );
+}
+
+#[test]
+fn markdown_problem_description() {
+    use crate::problem_spec::ProblemSpec;
+    let spec = ProblemSpec::from_markdown(
+        "---\ntime_limit: 2000\ndata: data/\n---\n# A+B\n\nAdd *two* numbers.\n\n\
+         ## Input\nTwo integers.\n\n## Sample Input\n```\n1 2\n```\n\n## Sample Output\n3 <\n",
+    )
+    .unwrap();
+    assert_eq!(spec.title.as_deref(), Some("A+B"));
+    assert_eq!(spec.data.as_deref(), Some("data/"));
+    let problem = spec.to_new_problem().unwrap();
+    assert_eq!(problem.description, "<p>Add <em>two</em> numbers.</p>\n");
+    assert_eq!(problem.input.as_deref(), Some("<p>Two integers.</p>\n"));
+    assert_eq!(problem.sample_input.as_deref(), Some("1 2"));
+    assert_eq!(problem.sample_output.as_deref(), Some("3 &lt;"));
+    assert_eq!(problem.output, None);
+    assert_eq!((problem.time_limit, problem.memory_limit), (2000, 65536));
+    assert!(ProblemSpec::from_markdown("## Notes\nx").is_err());
+}
+
+#[test]
+fn durations_and_countdowns() {
+    use crate::display::format_duration;
+    use chrono::TimeDelta;
+    assert_eq!(parse_duration("10").unwrap(), TimeDelta::minutes(10));
+    assert_eq!(parse_duration("1h30m").unwrap(), TimeDelta::minutes(90));
+    assert_eq!(parse_duration("90s").unwrap(), TimeDelta::seconds(90));
+    assert!(parse_duration("10 minutes").is_err());
+    assert!(parse_duration("h").is_err());
+    assert!(parse_duration("1h5").is_err());
+    assert_eq!(format_duration(TimeDelta::seconds(42)), "42s");
+    assert_eq!(format_duration(TimeDelta::seconds(3909)), "1h 05m 09s");
+    assert_eq!(
+        format_duration(TimeDelta::days(2) + TimeDelta::minutes(195)),
+        "2d 03h 15m"
+    );
+    assert_eq!(format_duration(TimeDelta::seconds(-5)), "0s");
+}
+
+#[test]
+fn daily_pick_follows_recent_results() {
+    use crate::daily::{Candidate, DailyEntry, DailyLog, pick};
+    let entry = |solved| DailyEntry {
+        date: "2025-03-01".to_string(),
+        url: String::new(),
+        title: String::new(),
+        solved,
+    };
+    let mut log = DailyLog::default();
+    assert_eq!(log.target_rate(), 0.55);
+    log.entries = (0..10).map(|i| entry(i < 3)).collect();
+    // only the last seven count, none of them solved
+    assert_eq!(log.recent().len(), 7);
+    assert_eq!(log.target_rate(), 0.8);
+
+    let candidates = [
+        Candidate {
+            accepted: 10,
+            submitters: 100,
+        },
+        Candidate {
+            accepted: 80,
+            submitters: 100,
+        },
+    ];
+    let easy = (0..100)
+        .filter(|seed| pick(&candidates, 0.8, *seed) == Some(1))
+        .count();
+    assert!(easy > 80, "{}", easy);
+    assert_eq!(pick(&candidates, 0.5, 7), pick(&candidates, 0.5, 7));
+    assert_eq!(pick(&[], 0.5, 7), None);
+}
+
+#[test]
+fn solve_times() {
+    use crate::solves::SolveLog;
+    use chrono::TimeDelta;
+    let at = |text: &str| libopenjudge::parse_server_time(text).unwrap();
+    let url = "https://cs101.openjudge.cn/practice/02750/";
+    let mut log = SolveLog::default();
+    log.record_view(url, "鸡兔同笼", at("2025-03-01 12:00:00"));
+    log.record_submission(url, false, at("2025-03-01 12:10:00"));
+    log.record_view(url, "鸡兔同笼", at("2025-03-01 12:15:00"));
+    log.record_submission(url, true, at("2025-03-01 12:20:00"));
+    log.record_submission(url, true, at("2025-03-01 13:00:00"));
+    let record = &log.problems[url];
+    assert_eq!(record.submissions, 3);
+    assert_eq!(record.time_to_solve(), Some(TimeDelta::minutes(20)));
+    // accepted before the CLI saw the problem
+    log.record_submission("other", true, at("2025-03-01 12:00:00"));
+    assert_eq!(log.solve_times(), [TimeDelta::minutes(20)]);
+}
+
+#[test]
+fn friend_parsing() {
+    assert_eq!(
+        "1234567=Alice".parse::<Friend>().unwrap(),
+        Friend {
+            id: "1234567".to_string(),
+            name: Some("Alice".to_string()),
+        }
+    );
+    assert_eq!("7654321".parse::<Friend>().unwrap().name, None);
+    assert!("alice".parse::<Friend>().is_err());
+    assert!("=Alice".parse::<Friend>().is_err());
+}
+
+#[test]
+fn backup_round_trip() {
+    use crate::backup::{backup, restore};
+    let base = std::env::temp_dir().join(format!("oj-backup-{}", std::process::id()));
+    let (root, copy) = (base.join("root"), base.join("copy"));
+    std::fs::create_dir_all(root.join("cache")).unwrap();
+    std::fs::write(root.join("config.json"), "{}").unwrap();
+    std::fs::write(root.join("cache").join("page"), [0u8, 1, 2]).unwrap();
+    // an archive inside the backed up directory leaves itself out
+    let archive = root.join("backup.zip");
+    assert_eq!(backup(&root, &archive).unwrap(), 2);
+    assert_eq!(restore(&archive, &copy, false).unwrap(), 2);
+    assert_eq!(
+        std::fs::read(copy.join("cache").join("page")).unwrap(),
+        [0, 1, 2]
+    );
+    assert!(restore(&archive, &copy, false).is_err());
+    assert_eq!(restore(&archive, &copy, true).unwrap(), 2);
+    std::fs::remove_dir_all(&base).unwrap();
+}
+
+#[test]
+fn self_update_helpers() {
+    use crate::self_update::{asset_name, find_checksum, is_newer};
+    assert!(is_newer("v0.2.0", "0.1.9"));
+    assert!(is_newer("v0.10.0", "0.9.0"));
+    assert!(!is_newer("v0.1.0", "0.1.0"));
+    assert!(!is_newer("v0.1.0-rc1", "0.1.0"));
+    let sums = "0a1b  oj-x86_64-linux\nFFEE *oj-x86_64-windows.exe\n";
+    assert_eq!(
+        find_checksum(sums, "oj-x86_64-windows.exe").as_deref(),
+        Some("ffee")
+    );
+    assert_eq!(find_checksum(sums, "oj-aarch64-macos"), None);
+    assert_eq!(find_checksum("0A1B\n", "anything").as_deref(), Some("0a1b"));
+    assert!(asset_name().starts_with("oj-"));
+}
+
+#[test]
+fn locales() {
+    use crate::i18n::{Lang, lang_from_locale};
+    assert_eq!(lang_from_locale("zh_CN.UTF-8"), Lang::Chinese);
+    assert_eq!(lang_from_locale("zh-Hans-CN"), Lang::Chinese);
+    assert_eq!(lang_from_locale("en_US.UTF-8"), Lang::English);
+    assert_eq!(lang_from_locale("C"), Lang::English);
+    assert_eq!("zh_cn".parse::<Lang>().unwrap(), Lang::Chinese);
+    assert!("fr".parse::<Lang>().is_err());
+    // every message is translated
+    let ids = |source: &str| {
+        source
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once(" =").map(|(id, _)| id.to_string()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        ids(include_str!("../locales/en.ftl")),
+        ids(include_str!("../locales/zh-CN.ftl"))
+    );
+}
+
+#[test]
+fn doctor_finds_slow_stage() {
+    use crate::doctor::{HostReport, Stage};
+    use std::time::Duration;
+    let ms = |ms| Some(Ok(Duration::from_millis(ms)));
+    let report = |dns, connect, fetch| HostReport {
+        host: "openjudge.cn".to_string(),
+        address: None,
+        dns,
+        connect,
+        fetch,
+    };
+    assert_eq!(report(ms(20), ms(30), ms(400)).problem(), None);
+    assert_eq!(
+        report(ms(2000), ms(30), ms(4000)).problem(),
+        Some(Stage::Dns)
+    );
+    assert_eq!(
+        report(ms(20), ms(30), ms(9000)).problem(),
+        Some(Stage::Fetch)
+    );
+    assert_eq!(
+        report(ms(20), Some(Err(anyhow::anyhow!("refused"))), None).problem(),
+        Some(Stage::Connect)
+    );
+}
+
+#[test]
+fn parse_snapshot_names_page_and_selector() {
+    use chrono::TimeZone;
+    let error = libopenjudge::ParseError {
+        url: "https://cs101.openjudge.cn/search/?q=a".to_string(),
+        selector: ".title a",
+        message: "Cannot find element".to_string(),
+    };
+    let time = chrono::Local
+        .with_ymd_and_hms(2025, 3, 1, 12, 0, 0)
+        .unwrap();
+    let snapshot = parse_snapshot(&error, "<html></html>", time);
+    assert!(snapshot.starts_with("<!--\nurl: https://cs101.openjudge.cn/search/?q=a\n"));
+    assert!(snapshot.contains("\nselector: .title a\nerror: Cannot find element\n"));
+    assert!(snapshot.ends_with("-->\n<html></html>"));
+}
+
+#[test]
+fn crash_report_has_every_section() {
+    use crate::crash::{CrashContext, crash_report};
+    let report = crash_report(&CrashContext {
+        message: "index out of bounds",
+        location: Some("src/app.rs:1:1".to_string()),
+        args: &["oj".to_string(), "status".to_string()],
+        config: "{\n  \"user_email\": \"<redacted>\"\n}\n",
+        backtrace: "0: main\n",
+        logs: &[],
+    });
+    assert!(report.contains("## Panic\n\nindex out of bounds\nat src/app.rs:1:1\n"));
+    assert!(report.contains("## Command line\n\noj status\n"));
+    assert!(report.contains("\"user_email\": \"<redacted>\"\n}\n\n## Backtrace\n\n0: main\n"));
+    assert!(report.ends_with("## Recent logs\n\n(none)\n"));
+}
+
+/// Answers every request with `401 Unauthorized`.
+struct RejectingFetcher;
+
+impl libopenjudge::Fetcher for RejectingFetcher {
+    async fn get_text(&self, _url: &str) -> anyhow::Result<String> {
+        Ok(String::new())
+    }
+
+    async fn post_form(
+        &self,
+        _url: &str,
+        _form: &[(&str, &str)],
+    ) -> anyhow::Result<libopenjudge::FetchResponse> {
+        Ok(libopenjudge::FetchResponse {
+            status: reqwest::StatusCode::UNAUTHORIZED,
+            body: String::new(),
+        })
+    }
+
+    async fn post_multipart(
+        &self,
+        url: &str,
+        form: &[(&str, &str)],
+        _files: &[libopenjudge::FormFile],
+    ) -> anyhow::Result<libopenjudge::FetchResponse> {
+        self.post_form(url, form).await
+    }
+}
+
+#[tokio::test]
+async fn crash_report_logs_leave_out_credentials() {
+    use crate::crash::{CrashContext, crash_report, recent_logs, recent_logs_layer};
+    use tracing_subscriber::layer::SubscriberExt;
+    let subscriber = tracing_subscriber::registry().with(recent_logs_layer());
+    let _guard = tracing::subscriber::set_default(subscriber);
+    let email = "someone@example.com";
+    assert!(
+        libopenjudge::login(&RejectingFetcher, email, "hunter2")
+            .await
+            .is_err()
+    );
+    let logs = recent_logs();
+    assert!(logs.iter().any(|line| line.contains("login")));
+    let report = crash_report(&CrashContext {
+        message: "after login",
+        location: None,
+        args: &[],
+        config: "",
+        backtrace: "",
+        logs: &logs,
+    });
+    assert!(!report.contains(email));
+    assert!(!report.contains("hunter2"));
+}
+
+#[test]
+fn activity_log_keeps_latest_entries() {
+    use crate::activity::{Activity, ActivityLog};
+    let activity = |i: usize| Activity {
+        time: chrono::DateTime::parse_from_rfc3339("2025-03-01T12:00:00+08:00").unwrap(),
+        args: vec!["view".to_string(), i.to_string()],
+        dir: None,
+        target: None,
+        error: None,
+    };
+    let mut log = ActivityLog::default();
+    for i in 0..250 {
+        log.push(activity(i));
+    }
+    assert_eq!(log.entries.len(), 200);
+    assert_eq!(log.entries[0], activity(50));
+    assert_eq!(log.nth_latest(1), Some(&activity(249)));
+    assert_eq!(log.nth_latest(3), Some(&activity(247)));
+    assert_eq!(log.nth_latest(0), None);
+    assert_eq!(log.nth_latest(201), None);
+}
+
+#[test]
+fn pending_submissions_are_removed_once_judged() {
+    use crate::pending::{PendingLog, PendingSubmission};
+    let submission = |id: usize| PendingSubmission {
+        url: format!("http://cs101.openjudge.cn/practice/solution/{}/", id),
+        problem: "http://cs101.openjudge.cn/practice/01005/".to_string(),
+        file: "a.cpp".to_string(),
+        time: chrono::DateTime::parse_from_rfc3339("2025-03-01T12:00:00+08:00").unwrap(),
+    };
+    let mut log = PendingLog::default();
+    for id in 1..=3 {
+        log.push(submission(id));
+    }
+    log.remove(&submission(2).url);
+    log.remove("http://cs101.openjudge.cn/practice/solution/9/");
+    assert_eq!(log.submissions, [submission(1), submission(3)]);
+}
+
+#[test]
+fn alias_names() {
+    assert_eq!(parse_alias_name("hw5").unwrap(), "hw5");
+    assert_eq!(parse_alias_name("@week_3-a").unwrap(), "week_3-a");
+    assert!(parse_alias_name("@").is_err());
+    assert!(parse_alias_name("a/b").is_err());
+}
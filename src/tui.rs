@@ -0,0 +1,645 @@
+use std::io::stdout;
+
+use anyhow::Result;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use libopenjudge::{
+    Group, Problem, ProblemSetPartial, ProblemUrl, Submission, SubmissionHistoryEntry,
+    SubmissionResult,
+};
+use ratatui::{
+    DefaultTerminal, Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::{
+    app::{Session, TestOptions, strip_slashes, submit_solution, test_solution},
+    display::{format_time, verdict_heading, verdict_label},
+    manifest::Manifest,
+    utils::{
+        html::{RenderMode, render_html},
+        interactions::StatusBar,
+    },
+};
+
+/// One level of the group → probset → problem → submission navigation.
+enum Screen {
+    Probsets {
+        group: Group,
+        list: ListState,
+    },
+    Problems {
+        probset: String,
+        problems: ProblemSetPartial,
+        list: ListState,
+    },
+    Problem {
+        url: ProblemUrl,
+        title: String,
+        statement: String,
+        scroll: u16,
+        /// `None` until loaded, which needs credentials.
+        submissions: Option<Vec<SubmissionHistoryEntry>>,
+        list: ListState,
+        focus: ProblemFocus,
+    },
+    Submission {
+        submission: Submission,
+        scroll: u16,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ProblemFocus {
+    Statement,
+    Submissions,
+}
+
+#[derive(Clone, Copy)]
+enum Action {
+    Test,
+    Submit,
+}
+
+/// Source file prompt shown before testing or submitting.
+struct Prompt {
+    action: Action,
+    file: String,
+}
+
+struct Tui<'a> {
+    session: &'a Session,
+    group: String,
+    stack: Vec<Screen>,
+    status: String,
+    prompt: Option<Prompt>,
+}
+
+/// Runs the full-screen browser for `group` until the user quits.
+pub async fn run(session: &Session, group: &str) -> Result<()> {
+    let group_info = libopenjudge::get_group_info(session.client().await?, group).await?;
+    let mut tui = Tui {
+        session,
+        group: group.to_string(),
+        stack: vec![Screen::Probsets {
+            group: group_info,
+            list: ListState::default().with_selected(Some(0)),
+        }],
+        status: String::new(),
+        prompt: None,
+    };
+    let mut terminal = ratatui::init();
+    let result = tui.event_loop(&mut terminal).await;
+    ratatui::restore();
+    result
+}
+
+impl Tui<'_> {
+    async fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.render(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if self.prompt.is_some() {
+                self.handle_prompt_key(key, terminal).await?;
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(()),
+                KeyCode::Esc | KeyCode::Backspace | KeyCode::Left => {
+                    if self.stack.len() > 1 {
+                        self.stack.pop();
+                    }
+                }
+                _ => self.handle_key(key, terminal).await,
+            }
+        }
+    }
+
+    /// Shows `status` while `operation` runs, then the error it failed with.
+    async fn load<T>(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        status: &str,
+        operation: impl AsyncFnOnce(&Session) -> Result<T>,
+    ) -> Option<T> {
+        self.status = status.to_string();
+        let _ = terminal.draw(|frame| self.render(frame));
+        match operation(self.session).await {
+            Ok(value) => {
+                self.status.clear();
+                Some(value)
+            }
+            Err(e) => {
+                self.status = format!("{}: {:#}", tr!("error"), e);
+                None
+            }
+        }
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent, terminal: &mut DefaultTerminal) {
+        let group = self.group.clone();
+        match self.stack.last_mut() {
+            Some(Screen::Probsets { group: info, list }) => {
+                if move_selection(list, key.code) || !is_confirm(key.code) {
+                    return;
+                }
+                let Some(entry) = list.selected().and_then(|i| info.probsets.get(i)) else {
+                    return;
+                };
+                let Ok(url) = url::Url::parse(&entry.url) else {
+                    return;
+                };
+                let probset = strip_slashes(url.path()).to_string();
+                let problems = self
+                    .load(terminal, &tr!("loading-problems"), async |session| {
+                        let client = session.client().await?;
+                        libopenjudge::get_partial_probset_info(client, &group, &probset, None).await
+                    })
+                    .await;
+                if let Some(problems) = problems {
+                    self.stack.push(Screen::Problems {
+                        probset,
+                        problems,
+                        list: ListState::default().with_selected(Some(0)),
+                    });
+                }
+            }
+            Some(Screen::Problems {
+                probset,
+                problems,
+                list,
+            }) => {
+                let page = match key.code {
+                    KeyCode::Char('n') if problems.page < problems.max_page => {
+                        Some(problems.page + 1)
+                    }
+                    KeyCode::Char('p') if problems.page > 1 => Some(problems.page - 1),
+                    _ => None,
+                };
+                if let Some(page) = page {
+                    let probset = probset.clone();
+                    let loaded = self
+                        .load(terminal, &tr!("loading-page"), async |session| {
+                            let client = session.client().await?;
+                            libopenjudge::get_partial_probset_info(
+                                client,
+                                &group,
+                                &probset,
+                                Some(page),
+                            )
+                            .await
+                        })
+                        .await;
+                    if let (Some(loaded), Some(Screen::Problems { problems, list, .. })) =
+                        (loaded, self.stack.last_mut())
+                    {
+                        *problems = loaded;
+                        list.select(Some(0));
+                    }
+                    return;
+                }
+                if move_selection(list, key.code) || !is_confirm(key.code) {
+                    return;
+                }
+                let Some(entry) = list.selected().and_then(|i| problems.problems.get(i)) else {
+                    return;
+                };
+                let Ok(url) = entry.url.parse::<ProblemUrl>() else {
+                    self.status = format!(
+                        "{}: {}",
+                        tr!("error"),
+                        tr!("not-a-problem-page", url = entry.url.clone())
+                    );
+                    return;
+                };
+                self.open_problem(url, terminal).await;
+            }
+            Some(Screen::Problem {
+                url,
+                scroll,
+                submissions,
+                list,
+                focus,
+                ..
+            }) => match key.code {
+                KeyCode::Tab => {
+                    *focus = match focus {
+                        ProblemFocus::Statement => ProblemFocus::Submissions,
+                        ProblemFocus::Submissions => ProblemFocus::Statement,
+                    }
+                }
+                KeyCode::Char('t') | KeyCode::Char('s') => {
+                    let action = if key.code == KeyCode::Char('t') {
+                        Action::Test
+                    } else {
+                        Action::Submit
+                    };
+                    let file = Manifest::read_current()
+                        .ok()
+                        .flatten()
+                        .and_then(|manifest| {
+                            let key = manifest.key_of(url)?;
+                            manifest.get(key)?.file.clone()
+                        })
+                        .unwrap_or_default();
+                    self.prompt = Some(Prompt { action, file });
+                }
+                KeyCode::Char('r') => {
+                    let url = url.clone();
+                    let loaded = self
+                        .load(terminal, &tr!("loading-submissions"), async |session| {
+                            let client = session.authenticated_client().await?;
+                            libopenjudge::list_submissions(client, &url).await
+                        })
+                        .await;
+                    if let (
+                        Some(loaded),
+                        Some(Screen::Problem {
+                            submissions, list, ..
+                        }),
+                    ) = (loaded, self.stack.last_mut())
+                    {
+                        *submissions = Some(loaded);
+                        list.select(Some(0));
+                    }
+                }
+                _ if *focus == ProblemFocus::Statement => scroll_text(scroll, key.code),
+                _ => {
+                    if move_selection(list, key.code) || !is_confirm(key.code) {
+                        return;
+                    }
+                    let Some(entry) = list.selected().and_then(|i| submissions.as_ref()?.get(i))
+                    else {
+                        return;
+                    };
+                    let submission_url = entry.url.clone();
+                    let submission = self
+                        .load(terminal, &tr!("loading-submission"), async |session| {
+                            let client = session.authenticated_client().await?;
+                            libopenjudge::query_submission_result(client, &submission_url).await
+                        })
+                        .await;
+                    if let Some(submission) = submission {
+                        self.stack.push(Screen::Submission {
+                            submission,
+                            scroll: 0,
+                        });
+                    }
+                }
+            },
+            Some(Screen::Submission { scroll, .. }) => scroll_text(scroll, key.code),
+            None => {}
+        }
+    }
+
+    async fn open_problem(&mut self, url: ProblemUrl, terminal: &mut DefaultTerminal) {
+        let problem = self
+            .load(terminal, &tr!("loading-problem"), async |session| {
+                session.problem(&url).await
+            })
+            .await;
+        let Some(problem) = problem else {
+            return;
+        };
+        let _ = self.session.remember_problem(&url);
+        let _ = self.session.record_view(&url, &problem);
+        let statement = statement_text(&problem).await;
+        self.stack.push(Screen::Problem {
+            url,
+            title: problem.title,
+            statement,
+            scroll: 0,
+            submissions: None,
+            list: ListState::default(),
+            focus: ProblemFocus::Statement,
+        });
+    }
+
+    async fn handle_prompt_key(
+        &mut self,
+        key: KeyEvent,
+        terminal: &mut DefaultTerminal,
+    ) -> Result<()> {
+        let Some(prompt) = &mut self.prompt else {
+            return Ok(());
+        };
+        match key.code {
+            KeyCode::Esc => self.prompt = None,
+            KeyCode::Backspace => {
+                prompt.file.pop();
+            }
+            KeyCode::Char(c) => prompt.file.push(c),
+            KeyCode::Enter => {
+                let Some(Prompt { action, file }) = self.prompt.take() else {
+                    return Ok(());
+                };
+                let Some(Screen::Problem { url, .. }) = self.stack.last() else {
+                    return Ok(());
+                };
+                let url = url.to_string();
+                // the commands print to the normal screen, as on the command line
+                suspend(terminal)?;
+                let result = match action {
+                    Action::Test => {
+                        test_solution(
+                            self.session,
+                            &url,
+                            Some(file),
+                            None,
+                            TestOptions {
+                                verify: true,
+                                ..TestOptions::default()
+                            },
+                        )
+                        .await
+                    }
+                    Action::Submit => {
+                        submit_solution(self.session, vec![&url, &file], None, true, false, true)
+                            .await
+                    }
+                };
+                if let Err(e) = result {
+                    println!("{}: {:#}", tr!("error"), e);
+                }
+                println!("\n{}", tr!("press-enter"));
+                let mut line = String::new();
+                std::io::stdin().read_line(&mut line)?;
+                resume(terminal)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn render(&mut self, frame: &mut Frame) {
+        let [body, status_area, context_area] = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let context = self
+            .status_bar()
+            .line(&tr!("hint-help"), context_area.width as usize);
+        frame.render_widget(Paragraph::new(context.reversed()), context_area);
+        let highlight = Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD);
+        let hints = match self.stack.last_mut() {
+            Some(Screen::Probsets { group, list }) => {
+                let [list_area, description_area] =
+                    Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .areas(body);
+                let items = group
+                    .probsets
+                    .iter()
+                    .map(|probset| ListItem::new(probset.name.clone()))
+                    .collect::<Vec<_>>();
+                frame.render_stateful_widget(
+                    List::new(items)
+                        .block(Block::bordered().title(group.name.clone()))
+                        .highlight_style(highlight)
+                        .highlight_symbol("> "),
+                    list_area,
+                    list,
+                );
+                frame.render_widget(
+                    Paragraph::new(group.description.clone())
+                        .wrap(Wrap { trim: false })
+                        .block(Block::bordered().title(tr!("section-description"))),
+                    description_area,
+                );
+                tr!("tui-hints-probsets")
+            }
+            Some(Screen::Problems { problems, list, .. }) => {
+                let items = problems
+                    .problems
+                    .iter()
+                    .map(|problem| {
+                        let marker = match problem.solved {
+                            Some(true) => "✓ ".green(),
+                            Some(false) => "✗ ".yellow(),
+                            None => "  ".into(),
+                        };
+                        ListItem::new(Line::from(vec![
+                            marker,
+                            format!("#{} ", problem.problem_number).bold(),
+                            problem.title.clone().into(),
+                            format!("  {}/{}", problem.accepted_population, problem.submitters)
+                                .dim(),
+                        ]))
+                    })
+                    .collect::<Vec<_>>();
+                let title = format!(
+                    "{}/{} · {}",
+                    problems.group_name,
+                    problems.name,
+                    tr!("status-page", page = problems.page, max = problems.max_page)
+                );
+                frame.render_stateful_widget(
+                    List::new(items)
+                        .block(Block::bordered().title(title))
+                        .highlight_style(highlight)
+                        .highlight_symbol("> "),
+                    body,
+                    list,
+                );
+                tr!("tui-hints-problems")
+            }
+            Some(Screen::Problem {
+                title,
+                statement,
+                scroll,
+                submissions,
+                list,
+                focus,
+                ..
+            }) => {
+                let [statement_area, submissions_area] =
+                    Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)])
+                        .areas(body);
+                let focused = |pane| {
+                    if *focus == pane {
+                        Style::default().fg(Color::Cyan)
+                    } else {
+                        Style::default()
+                    }
+                };
+                frame.render_widget(
+                    Paragraph::new(statement.clone())
+                        .wrap(Wrap { trim: false })
+                        .scroll((*scroll, 0))
+                        .block(
+                            Block::bordered()
+                                .title(title.clone())
+                                .border_style(focused(ProblemFocus::Statement)),
+                        ),
+                    statement_area,
+                );
+                let block = Block::bordered()
+                    .title(tr!("label-submissions"))
+                    .border_style(focused(ProblemFocus::Submissions));
+                match submissions {
+                    Some(submissions) => {
+                        let items = submissions
+                            .iter()
+                            .map(|submission| {
+                                ListItem::new(format!(
+                                    "{}  {}",
+                                    &*verdict_label(&submission.result),
+                                    format_time(&submission.time)
+                                ))
+                            })
+                            .collect::<Vec<_>>();
+                        frame.render_stateful_widget(
+                            List::new(items)
+                                .block(block)
+                                .highlight_style(highlight)
+                                .highlight_symbol("> "),
+                            submissions_area,
+                            list,
+                        );
+                    }
+                    None => frame.render_widget(
+                        Paragraph::new(tr!("press-r-submissions")).block(block),
+                        submissions_area,
+                    ),
+                }
+                tr!("tui-hints-problem")
+            }
+            Some(Screen::Submission { submission, scroll }) => {
+                let mut text = Text::from(vec![
+                    Line::from((*verdict_heading(&submission.result)).to_string().bold()),
+                    Line::from(format!(
+                        "#{} · {} · {}",
+                        submission.id, submission.author, submission.lang
+                    )),
+                    Line::default(),
+                ]);
+                text.extend(Text::raw(submission.code.clone()));
+                frame.render_widget(
+                    Paragraph::new(text)
+                        .scroll((*scroll, 0))
+                        .block(Block::bordered().title(tr!("submission-heading"))),
+                    body,
+                );
+                tr!("tui-hints-submission")
+            }
+            None => String::new(),
+        };
+        let status = match &self.prompt {
+            Some(prompt) => Line::from(vec![
+                match prompt.action {
+                    Action::Test => tr!("test-with-file"),
+                    Action::Submit => tr!("submit-file"),
+                }
+                .bold(),
+                prompt.file.clone().into(),
+                "█".into(),
+            ]),
+            None if !self.status.is_empty() => Line::from(self.status.clone().yellow()),
+            None => Line::from(hints.dim()),
+        };
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+}
+
+impl Tui<'_> {
+    /// Group, probset and page of the innermost screens that have them.
+    fn status_bar(&self) -> StatusBar {
+        let mut status = StatusBar {
+            group: Some(self.group.clone()),
+            ..self.session.status_bar()
+        };
+        for screen in &self.stack {
+            match screen {
+                Screen::Problems {
+                    probset, problems, ..
+                } => {
+                    status.probset = Some(probset.clone());
+                    status.page = Some((problems.page, problems.max_page));
+                }
+                Screen::Problem {
+                    url, submissions, ..
+                } => {
+                    status.probset = Some(url.probset().to_string());
+                    status.pending = submissions
+                        .iter()
+                        .flatten()
+                        .filter(|entry| matches!(entry.result, SubmissionResult::Waiting))
+                        .count();
+                }
+                _ => {}
+            }
+        }
+        status
+    }
+}
+
+/// Moves the selection for navigation keys, returns whether `code` was one.
+fn move_selection(list: &mut ListState, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => list.select_previous(),
+        KeyCode::Down | KeyCode::Char('j') => list.select_next(),
+        _ => return false,
+    }
+    true
+}
+
+fn is_confirm(code: KeyCode) -> bool {
+    matches!(code, KeyCode::Enter | KeyCode::Right)
+}
+
+fn scroll_text(scroll: &mut u16, code: KeyCode) {
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => *scroll = scroll.saturating_add(1),
+        _ => {}
+    }
+}
+
+/// The problem as plain text, images are left out.
+async fn statement_text(problem: &Problem) -> String {
+    let mut text = format!("{}\n\n", problem.title);
+    let sections = [
+        (tr!("section-description"), Some(&problem.description)),
+        (tr!("section-input"), problem.input.as_ref()),
+        (tr!("section-output"), problem.output.as_ref()),
+        (tr!("section-sample-input"), problem.sample_input.as_ref()),
+        (tr!("section-sample-output"), problem.sample_output.as_ref()),
+        (tr!("section-hint"), problem.hint.as_ref()),
+        (tr!("section-source"), problem.source.as_ref()),
+    ];
+    for (heading, content) in sections {
+        if let Some(content) = content {
+            let content = render_html(content, RenderMode::Plain).await;
+            text.push_str(&format!("{}\n{}\n\n", heading, content.trim()));
+        }
+    }
+    text
+}
+
+fn suspend(terminal: &mut DefaultTerminal) -> Result<()> {
+    terminal::disable_raw_mode()?;
+    execute!(stdout(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+fn resume(terminal: &mut DefaultTerminal) -> Result<()> {
+    terminal::enable_raw_mode()?;
+    execute!(stdout(), EnterAlternateScreen)?;
+    terminal.clear()?;
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use std::{
+    io::{IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+use anyhow::{Result, anyhow};
+use base64::{Engine, prelude::BASE64_STANDARD};
+
+use super::terminal::{Multiplexer, multiplexer, passthrough};
+
+/// Clipboard commands tried in order, each reading the text from stdin.
+/// `clip.exe` also serves WSL.
+const COMMANDS: &[&[&str]] = &[
+    &["pbcopy"],
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+    &["clip.exe"],
+];
+
+/// How text was put on the clipboard.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Copied {
+    /// By the named clipboard command.
+    Command(&'static str),
+    /// By asking the terminal, which may have ignored it.
+    Terminal,
+}
+
+/// OSC 52 sequence asking the terminal to set the clipboard to `text`, which
+/// also reaches the local terminal over SSH.
+pub fn osc52(text: &str, multiplexer: Option<Multiplexer>) -> String {
+    passthrough(
+        &format!("\x1b]52;c;{}\x07", BASE64_STANDARD.encode(text)),
+        multiplexer,
+    )
+}
+
+fn run(command: &[&str], text: &str) -> bool {
+    let child = Command::new(command[0])
+        .args(&command[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let Ok(mut child) = child else {
+        return false;
+    };
+    let written = child
+        .stdin
+        .take()
+        .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+    child.wait().is_ok_and(|status| status.success()) && written
+}
+
+/// Puts `text` on the system clipboard with the first clipboard command that
+/// works, or else through the terminal if stdout is one.
+pub fn copy(text: &str) -> Result<Copied> {
+    if let Some(command) = COMMANDS.iter().find(|command| run(command, text)) {
+        return Ok(Copied::Command(command[0]));
+    }
+    let mut stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        return Err(anyhow!(
+            "No clipboard command found, install one of {}.",
+            COMMANDS
+                .iter()
+                .map(|command| command[0])
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    write!(stdout, "{}", osc52(text, multiplexer()))?;
+    stdout.flush()?;
+    Ok(Copied::Terminal)
+}
@@ -1,14 +1,30 @@
-use std::{env, fmt::Write, str::FromStr, sync::LazyLock};
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    fmt::Write,
+    str::FromStr,
+    sync::{
+        LazyLock,
+        atomic::{AtomicU32, Ordering},
+    },
+};
 
 use anyhow::Result;
 use base64::{Engine, engine::Config, prelude::BASE64_STANDARD};
 use colored::Colorize;
 use ego_tree::NodeRef;
+use futures::StreamExt;
 use image::{DynamicImage, ImageEncoder, ImageReader, codecs::png::PngEncoder};
 use markup5ever::local_name;
 use onig::Regex;
 use scraper::{ElementRef, Node};
 use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthChar;
+
+use super::{
+    terminal::{self, Multiplexer, screen_reader},
+    width::{copy_escape_sequence, display_width},
+};
 
 static WHITESPACE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").unwrap());
 
@@ -58,30 +74,373 @@ fn use_sixel() -> Result<GraphicsProtocol, anyhow::Error> {
     ))
 }
 
-pub async fn get_printable_html_text(text: &str, graphics_protocol: GraphicsProtocol) -> String {
+/// Error diffusion used when reducing images to the sixel palette.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SixelDiffusion {
+    #[default]
+    Auto,
+    None,
+    Atkinson,
+    Fs,
+    Jajuni,
+    Stucki,
+    Burkes,
+    ADither,
+    XDither,
+}
+
+impl FromStr for SixelDiffusion {
+    type Err = anyhow::Error;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "auto" => Ok(SixelDiffusion::Auto),
+            "none" => Ok(SixelDiffusion::None),
+            "atkinson" => Ok(SixelDiffusion::Atkinson),
+            "fs" | "floyd-steinberg" => Ok(SixelDiffusion::Fs),
+            "jajuni" => Ok(SixelDiffusion::Jajuni),
+            "stucki" => Ok(SixelDiffusion::Stucki),
+            "burkes" => Ok(SixelDiffusion::Burkes),
+            "adither" => Ok(SixelDiffusion::ADither),
+            "xdither" => Ok(SixelDiffusion::XDither),
+            _ => Err(anyhow::format_err!(
+                "Invalid value for SixelDiffusion: {}",
+                value
+            )),
+        }
+    }
+}
+
+/// Limits for images drawn with sixel. Sixel data grows with the pixel count,
+/// and large blobs stall some terminals, so bigger images are downscaled.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct SixelOptions {
+    pub max_width: u32,
+    pub max_height: u32,
+    pub diffusion: SixelDiffusion,
+}
+
+impl Default for SixelOptions {
+    fn default() -> Self {
+        Self {
+            max_width: 800,
+            max_height: 600,
+            diffusion: SixelDiffusion::Auto,
+        }
+    }
+}
+
+/// Default for [`GraphicsOptions::max_rows`].
+pub const DEFAULT_IMAGE_MAX_ROWS: u32 = 30;
+
+/// How images are drawn in styled output.
+#[derive(Clone, Copy)]
+pub struct GraphicsOptions {
+    pub protocol: GraphicsProtocol,
+    pub sixel: SixelOptions,
+    /// Most terminal rows an iTerm image may span.
+    pub max_rows: u32,
+}
+
+impl From<GraphicsProtocol> for GraphicsOptions {
+    fn from(protocol: GraphicsProtocol) -> Self {
+        Self {
+            protocol,
+            sixel: SixelOptions::default(),
+            max_rows: DEFAULT_IMAGE_MAX_ROWS,
+        }
+    }
+}
+
+/// How [`render_html`] turns problem HTML into text.
+#[derive(Clone, Copy)]
+pub enum RenderMode {
+    /// Bare text without styling or images, e.g. sample data to diff against.
+    Plain,
+    /// Terminal output with styling, and images drawn as configured.
+    Styled(GraphicsOptions),
+}
+
+/// Renders an HTML fragment scraped from OpenJudge as text. Styled output is
+/// wrapped to the terminal width.
+pub async fn render_html(text: &str, mode: RenderMode) -> String {
     let html = scraper::Html::parse_fragment(text);
-    let mut output = vec![];
-    for child in html.root_element().children() {
-        output.push(
-            html_to_terminal_output_neo(
-                child,
-                graphics_protocol,
-                false, // do not preserve whitespace by default
-            )
-            .await,
-        );
+    let images = match mode {
+        RenderMode::Styled(graphics)
+            if !matches!(
+                transform_protocol(graphics.protocol),
+                GraphicsProtocol::Disabled
+            ) =>
+        {
+            fetch_images(sources(&html)).await
+        }
+        _ => HashMap::new(),
+    };
+    let output = html
+        .root_element()
+        .children()
+        // do not preserve whitespace by default
+        .map(|child| render_node(child, mode, false, &images))
+        .collect::<Vec<_>>()
+        .concat();
+    match (mode, crossterm::terminal::size()) {
+        (RenderMode::Styled(_), Ok((columns, _))) => wrap_text(&output, columns as usize),
+        _ => output,
     }
-    output.concat()
+}
+
+const SUPERSCRIPTS: &[(char, char)] = &[
+    ('0', '⁰'),
+    ('1', '¹'),
+    ('2', '²'),
+    ('3', '³'),
+    ('4', '⁴'),
+    ('5', '⁵'),
+    ('6', '⁶'),
+    ('7', '⁷'),
+    ('8', '⁸'),
+    ('9', '⁹'),
+    ('+', '⁺'),
+    ('-', '⁻'),
+    ('=', '⁼'),
+    ('(', '⁽'),
+    (')', '⁾'),
+    ('n', 'ⁿ'),
+    ('i', 'ⁱ'),
+];
+
+const SUBSCRIPTS: &[(char, char)] = &[
+    ('0', '₀'),
+    ('1', '₁'),
+    ('2', '₂'),
+    ('3', '₃'),
+    ('4', '₄'),
+    ('5', '₅'),
+    ('6', '₆'),
+    ('7', '₇'),
+    ('8', '₈'),
+    ('9', '₉'),
+    ('+', '₊'),
+    ('-', '₋'),
+    ('=', '₌'),
+    ('(', '₍'),
+    (')', '₎'),
+    ('a', 'ₐ'),
+    ('e', 'ₑ'),
+    ('i', 'ᵢ'),
+    ('j', 'ⱼ'),
+    ('k', 'ₖ'),
+    ('m', 'ₘ'),
+    ('n', 'ₙ'),
+    ('o', 'ₒ'),
+    ('x', 'ₓ'),
+];
+
+/// Maps `text` to unicode super/subscript characters, falling back to
+/// `marker` followed by the text (parenthesized if longer than one character)
+/// when any character has no such form.
+fn to_script(text: &str, table: &[(char, char)], marker: char) -> String {
+    let text = text.trim();
+    let mapped = text
+        .chars()
+        .map(|c| table.iter().find(|(from, _)| *from == c).map(|(_, to)| *to))
+        .collect::<Option<String>>();
+    match mapped {
+        Some(mapped) if !mapped.is_empty() => mapped,
+        _ if text.chars().count() == 1 => format!("{}{}", marker, text),
+        _ => format!("{}({})", marker, text),
+    }
+}
+
+/// Dimmed line of `width` repetitions of `c`, separating sections. Left out
+/// for screen readers.
+fn rule(c: char, width: usize) -> String {
+    if screen_reader() {
+        return String::new();
+    }
+    c.to_string().repeat(width).dimmed().to_string()
+}
+
+fn terminal_width() -> usize {
+    crossterm::terminal::size().map_or(80, |(columns, _)| columns as usize)
+}
+
+/// Prefixes each line of `text` with a vertical bar, dimming it when styled.
+fn quote(text: &str, styled: bool) -> String {
+    text.lines()
+        .map(|line| {
+            if styled && !screen_reader() {
+                format!("{} {}", "│".dimmed(), line.dimmed())
+            } else {
+                format!("> {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Wraps `text` so that no line is wider than `width` columns. Lines break at
+/// spaces, or between any two wide (CJK) characters, and words longer than a
+/// line are broken hard. ANSI styling does not count towards the width, and
+/// lines holding inline images are left alone.
+pub fn wrap_text(text: &str, width: usize) -> String {
+    text.split('\n')
+        .map(|line| wrap_line(line, width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn wrap_line(line: &str, width: usize) -> String {
+    // DCS (sixel), APC (kitty) and OSC (iTerm) sequences carry images
+    if width == 0
+        || ["\x1bP", "\x1b_", "\x1b]"]
+            .iter()
+            .any(|seq| line.contains(seq))
+    {
+        return line.to_string();
+    }
+    let mut output = String::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    // byte offset into `current` where it may be broken
+    let mut break_at: Option<usize> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            current.push(c);
+            copy_escape_sequence(&mut chars, &mut current);
+            continue;
+        }
+        let char_width = c.width().unwrap_or(0);
+        let wide = char_width > 1;
+        if wide && current_width > 0 {
+            break_at = Some(current.len());
+        }
+        if current_width + char_width > width && current_width > 0 {
+            if c == ' ' {
+                output.push_str(current.trim_end());
+                output.push('\n');
+                current.clear();
+                current_width = 0;
+                break_at = None;
+                continue;
+            }
+            let rest = match break_at.take() {
+                Some(at) => current.split_off(at),
+                None => String::new(),
+            };
+            output.push_str(current.trim_end());
+            output.push('\n');
+            current = rest.trim_start().to_string();
+            current_width = display_width(&current);
+        }
+        current.push(c);
+        current_width += char_width;
+        if c == ' ' {
+            break_at = Some(current.len());
+        }
+    }
+    output.push_str(&current);
+    output
 }
 
 pub fn shrink_whitespace(text: &str) -> String {
     WHITESPACE_RE.replace_all(text, " ")
 }
 
-pub async fn html_to_terminal_output_neo(
+/// Images of a fragment by `src`, or why they could not be fetched.
+pub type Images = HashMap<String, Result<Vec<u8>, String>>;
+
+/// Most images of a fragment downloaded at once.
+const IMAGE_CONCURRENCY: usize = 8;
+
+/// Sources of the images of `html`, in document order.
+fn sources(html: &scraper::Html) -> Vec<String> {
+    let selector = scraper::Selector::parse("img[src]").unwrap();
+    let mut seen = HashSet::new();
+    html.select(&selector)
+        .filter_map(|img| img.attr("src"))
+        .map(|src| src.trim().to_string())
+        .filter(|src| seen.insert(src.clone()))
+        .collect()
+}
+
+/// Sources of the images of an HTML fragment, in order and without repeats.
+pub fn image_sources(text: &str) -> Vec<String> {
+    sources(&scraper::Html::parse_fragment(text))
+}
+
+/// Extensions of linked files taken for attachments, such as test data.
+const ATTACHMENT_EXTENSIONS: &[&str] = &[
+    "pdf", "zip", "rar", "7z", "gz", "tar", "txt", "in", "out", "ans", "csv", "doc", "docx", "xls",
+    "xlsx", "ppt", "pptx",
+];
+
+/// Targets of the links to documents and data files in an HTML fragment, in
+/// order and without repeats.
+pub fn attachment_links(text: &str) -> Vec<String> {
+    let html = scraper::Html::parse_fragment(text);
+    let selector = scraper::Selector::parse("a[href]").unwrap();
+    let mut seen = HashSet::new();
+    html.select(&selector)
+        .filter_map(|anchor| anchor.attr("href"))
+        .map(|href| href.trim().to_string())
+        .filter(|href| {
+            let path = href.split(['?', '#']).next().unwrap_or_default();
+            let name = path.rsplit('/').next().unwrap_or_default();
+            name.rsplit_once('.').is_some_and(|(_, extension)| {
+                ATTACHMENT_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+            })
+        })
+        .filter(|href| seen.insert(href.clone()))
+        .collect()
+}
+
+/// Fetches and draws the single image at `src`, for images left out of a
+/// render.
+pub async fn render_image(src: &str, graphics: GraphicsOptions) -> String {
+    let images = fetch_images(vec![src.to_string()]).await;
+    get_image(src, graphics, &images)
+}
+
+/// Fetches `sources` concurrently, through one client shared by all renders.
+/// `file:` sources, such as the images of imported bundles, are read from
+/// disk.
+pub async fn fetch_images(sources: Vec<String>) -> Images {
+    static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+    futures::stream::iter(sources)
+        .map(|src| async move {
+            let local = url::Url::parse(&src)
+                .ok()
+                .filter(|url| url.scheme() == "file")
+                .and_then(|url| url.to_file_path().ok());
+            if let Some(path) = local {
+                let bytes = tokio::fs::read(&path)
+                    .await
+                    .map_err(|_| format!("[Image src {} read failed]", src));
+                return (src, bytes);
+            }
+            let bytes = match CLIENT.get(&src).send().await {
+                Ok(response) => response
+                    .bytes()
+                    .await
+                    .map(|bytes| bytes.to_vec())
+                    .map_err(|_| format!("[Image src {} read bytes failed]", src)),
+                Err(_) => Err(format!("[Image src {} fetch failed]", src)),
+            };
+            (src, bytes)
+        })
+        .buffer_unordered(IMAGE_CONCURRENCY)
+        .collect()
+        .await
+}
+
+fn render_node(
     node: NodeRef<'_, Node>,
-    graphics_protocol: GraphicsProtocol,
+    mode: RenderMode,
     preserve_whitespace: bool,
+    images: &Images,
 ) -> String {
     match node.value() {
         Node::Text(text) => {
@@ -98,64 +457,178 @@ pub async fn html_to_terminal_output_neo(
         Node::Element(_) => {
             let element_ref = ElementRef::wrap(node).unwrap();
             if let local_name!("img") = element_ref.value().name.local {
-                return get_image(&element_ref, graphics_protocol).await;
+                match mode {
+                    RenderMode::Plain => String::new(),
+                    RenderMode::Styled(graphics) => match element_ref.attr("src") {
+                        Some(src) => get_image(src.trim(), graphics, images),
+                        None => String::new(),
+                    },
+                }
             } else if let local_name!("br") = element_ref.value().name.local {
-                return "\n".to_string();
+                "\n".to_string()
+            } else if let local_name!("hr") = element_ref.value().name.local {
+                match mode {
+                    RenderMode::Plain => "\n".to_string(),
+                    RenderMode::Styled(_) => format!("\n{}\n", rule('─', terminal_width())),
+                }
             } else {
                 let preserve_whitespace = preserve_whitespace
-                    || matches!(element_ref.value().name.local, local_name!("pre"));
-                let mut output = vec![];
-                for child in element_ref.children() {
-                    output.push(
-                        Box::pin(html_to_terminal_output_neo(
-                            child,
-                            graphics_protocol,
-                            preserve_whitespace,
-                        ))
-                        .await,
+                    || matches!(
+                        element_ref.value().name.local,
+                        local_name!("pre")
+                            | local_name!("tt")
+                            | local_name!("kbd")
+                            | local_name!("samp")
                     );
-                }
+                let text = element_ref
+                    .children()
+                    .map(|child| render_node(child, mode, preserve_whitespace, images))
+                    .collect::<Vec<_>>()
+                    .concat();
+                let styled = matches!(mode, RenderMode::Styled(_));
                 match element_ref.value().name.local {
-                    local_name!("b") | local_name!("strong") => output.concat().bold().to_string(),
-                    local_name!("h1") => {
-                        output.concat().bold().underline().white().to_string() + "\n"
-                    }
-                    local_name!("h2") => output.concat().bold().underline().to_string() + "\n",
+                    local_name!("b") | local_name!("strong") if styled => text.bold().to_string(),
+                    local_name!("h1") if styled => format!(
+                        "{}\n{}\n",
+                        text.bold().white(),
+                        rule('═', display_width(&text).min(terminal_width()))
+                    ),
+                    local_name!("h2") if styled => format!(
+                        "{}\n{}\n",
+                        text.bold(),
+                        rule('─', display_width(&text).min(terminal_width()))
+                    ),
                     local_name!("h3")
                     | local_name!("h4")
                     | local_name!("h5")
-                    | local_name!("h6") => output.concat().bold().to_string() + "\n",
-                    local_name!("div") => output.concat() + "\n",
-                    local_name!("p") => format!("\n{}\n", output.concat()),
-                    local_name!("i") | local_name!("em") => output.concat().italic().to_string(),
-                    local_name!("mark") => output.concat().black().on_yellow().to_string(),
-                    _ => output.concat(),
+                    | local_name!("h6")
+                        if styled =>
+                    {
+                        text.bold().to_string() + "\n"
+                    }
+                    local_name!("h1")
+                    | local_name!("h2")
+                    | local_name!("h3")
+                    | local_name!("h4")
+                    | local_name!("h5")
+                    | local_name!("h6") => text + "\n",
+                    local_name!("div") => text + "\n",
+                    local_name!("p") => format!("\n{}\n", text),
+                    local_name!("i") | local_name!("em") if styled => text.italic().to_string(),
+                    local_name!("mark") if styled => text.black().on_yellow().to_string(),
+                    // code blocks are already set apart by <pre>
+                    local_name!("code") if styled && !preserve_whitespace => {
+                        text.on_truecolor(48, 52, 70).to_string()
+                    }
+                    local_name!("tt") | local_name!("samp") if styled => text.cyan().to_string(),
+                    local_name!("kbd") if styled => text.cyan().bold().to_string(),
+                    local_name!("sup") => to_script(&text, SUPERSCRIPTS, '^'),
+                    local_name!("sub") => to_script(&text, SUBSCRIPTS, '_'),
+                    local_name!("blockquote") => format!("\n{}\n", quote(text.trim(), styled)),
+                    _ => text,
                 }
             }
         }
     }
 }
 
-async fn get_image(img: &ElementRef<'_>, graphics_protocol: GraphicsProtocol) -> String {
-    let src = img.attr("src");
-    if src.is_none() {
-        return "".to_string();
+/// Converts an HTML fragment scraped from OpenJudge to Markdown. Images point
+/// at `image_path(src)`, or keep their source if it is `None`. Headings start
+/// at level 3, leaving levels 1 and 2 to the document around it, and markup
+/// Markdown has no syntax for is kept as HTML.
+pub fn html_to_markdown(text: &str, image_path: &dyn Fn(&str) -> Option<String>) -> String {
+    static BLANK_LINES_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\n{3,}").unwrap());
+    let html = scraper::Html::parse_fragment(text);
+    let markdown = html
+        .root_element()
+        .children()
+        .map(|child| markdown_node(child, false, image_path))
+        .collect::<Vec<_>>()
+        .concat();
+    BLANK_LINES_RE
+        .replace_all(markdown.trim(), "\n\n")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn markdown_node(
+    node: NodeRef<'_, Node>,
+    preserve_whitespace: bool,
+    image_path: &dyn Fn(&str) -> Option<String>,
+) -> String {
+    let element_ref = match node.value() {
+        Node::Text(text) if preserve_whitespace => return text.to_string(),
+        Node::Text(text) => return shrink_whitespace(text),
+        Node::Element(_) => ElementRef::wrap(node).unwrap(),
+        _ => return String::new(),
+    };
+    let name = &element_ref.value().name.local;
+    match *name {
+        local_name!("img") => {
+            let src = element_ref.attr("src").unwrap_or_default().trim();
+            let alt = element_ref.attr("alt").unwrap_or_default();
+            return format!(
+                "![{}]({})",
+                alt,
+                image_path(src).unwrap_or_else(|| src.to_string())
+            );
+        }
+        local_name!("br") => return "  \n".to_string(),
+        local_name!("hr") => return "\n\n---\n\n".to_string(),
+        local_name!("table") => return format!("\n\n{}\n\n", element_ref.html()),
+        _ => {}
+    }
+    let preserve_whitespace = preserve_whitespace || *name == local_name!("pre");
+    let text = element_ref
+        .children()
+        .map(|child| markdown_node(child, preserve_whitespace, image_path))
+        .collect::<Vec<_>>()
+        .concat();
+    match *name {
+        local_name!("pre") => format!("\n\n```\n{}\n```\n\n", text.trim_matches('\n')),
+        local_name!("code") | local_name!("tt") | local_name!("kbd") | local_name!("samp")
+            if !preserve_whitespace =>
+        {
+            format!("`{}`", text)
+        }
+        local_name!("b") | local_name!("strong") if !text.trim().is_empty() => {
+            format!("**{}**", text.trim())
+        }
+        local_name!("i") | local_name!("em") if !text.trim().is_empty() => {
+            format!("*{}*", text.trim())
+        }
+        local_name!("h1") | local_name!("h2") | local_name!("h3") => {
+            format!("\n\n### {}\n\n", text.trim())
+        }
+        local_name!("h4") | local_name!("h5") | local_name!("h6") => {
+            format!("\n\n#### {}\n\n", text.trim())
+        }
+        local_name!("p") | local_name!("div") | local_name!("ul") | local_name!("ol") => {
+            format!("\n\n{}\n\n", text.trim())
+        }
+        local_name!("li") => format!("- {}\n", text.trim()),
+        local_name!("a") => match element_ref.attr("href") {
+            Some(href) => format!("[{}]({})", text.trim(), href.trim()),
+            None => text,
+        },
+        local_name!("sup") | local_name!("sub") => format!("<{0}>{1}</{0}>", name, text),
+        local_name!("blockquote") => format!("\n\n{}\n\n", quote(text.trim(), false)),
+        _ => text,
     }
-    let src = src.unwrap().trim();
-    let graphics_protocol = transform_protocol(graphics_protocol);
+}
+
+fn get_image(src: &str, graphics: GraphicsOptions, images: &Images) -> String {
+    let graphics_protocol = transform_protocol(graphics.protocol);
     if let GraphicsProtocol::Disabled = graphics_protocol {
         return format!("[Image src {}]\n", src);
     }
-    let client = reqwest::Client::new();
-    let resp = client.get(src).send().await;
-    if resp.is_err() {
-        return format!("[Image src {} fetch failed]", src);
-    }
-    let bytes = resp.unwrap().bytes().await;
-    if bytes.is_err() {
-        return format!("[Image src {} read bytes failed]", src);
-    }
-    let bytes = bytes.unwrap();
+    let bytes = match images.get(src) {
+        Some(Ok(bytes)) => bytes.as_slice(),
+        Some(Err(message)) => return message.clone(),
+        None => return format!("[Image src {} fetch failed]", src),
+    };
     ImageReader::new(std::io::Cursor::new(bytes))
         .with_guessed_format()
         .map(|reader| {
@@ -163,21 +636,23 @@ async fn get_image(img: &ElementRef<'_>, graphics_protocol: GraphicsProtocol) ->
                 .decode()
                 .map(|image| match graphics_protocol {
                     GraphicsProtocol::Disabled => unreachable!(),
-                    GraphicsProtocol::Sixel => encode_image_as_sixel(image).unwrap_or_else(|_| {
-                        format!("[Image src {} cannot be encoded as sixel]", src)
-                    }),
+                    GraphicsProtocol::Sixel => encode_image_as_sixel(image, &graphics.sixel)
+                        .unwrap_or_else(|_| {
+                            format!("[Image src {} cannot be encoded as sixel]", src)
+                        }),
                     GraphicsProtocol::Kitty => encode_image_as_kitty(image).unwrap_or_else(|_| {
                         format!(
                             "[Image src {} cannot be encoded into kitty image protocol]",
                             src
                         )
                     }),
-                    GraphicsProtocol::ITerm => encode_image_as_iterm(image).unwrap_or_else(|_| {
-                        format!(
-                            "[Image src {} cannot be encoded into iTerm inline image]",
-                            src
-                        )
-                    }),
+                    GraphicsProtocol::ITerm => encode_image_as_iterm(image, src, graphics.max_rows)
+                        .unwrap_or_else(|_| {
+                            format!(
+                                "[Image src {} cannot be encoded into iTerm inline image]",
+                                src
+                            )
+                        }),
                     GraphicsProtocol::Auto => unreachable!(),
                 })
                 .unwrap_or_else(|_| format!("[Image src {} cannot be decoded]", src))
@@ -186,7 +661,25 @@ async fn get_image(img: &ElementRef<'_>, graphics_protocol: GraphicsProtocol) ->
 }
 
 #[cfg(feature = "sixel")]
-fn encode_image_as_sixel(img: DynamicImage) -> Result<String, ()> {
+fn encode_image_as_sixel(img: DynamicImage, options: &SixelOptions) -> Result<String, ()> {
+    let (max_width, max_height) = sixel_size_limit(options);
+    let img = if img.width() > max_width || img.height() > max_height {
+        // keeps the aspect ratio, fitting within the limits
+        img.resize(max_width, max_height, image::imageops::FilterType::Triangle)
+    } else {
+        img
+    };
+    let diffusion = match options.diffusion {
+        SixelDiffusion::Auto => sixel_bytes::DiffusionMethod::Auto,
+        SixelDiffusion::None => sixel_bytes::DiffusionMethod::None,
+        SixelDiffusion::Atkinson => sixel_bytes::DiffusionMethod::Atkinson,
+        SixelDiffusion::Fs => sixel_bytes::DiffusionMethod::FS,
+        SixelDiffusion::Jajuni => sixel_bytes::DiffusionMethod::JaJuNi,
+        SixelDiffusion::Stucki => sixel_bytes::DiffusionMethod::Stucki,
+        SixelDiffusion::Burkes => sixel_bytes::DiffusionMethod::Burkes,
+        SixelDiffusion::ADither => sixel_bytes::DiffusionMethod::ADither,
+        SixelDiffusion::XDither => sixel_bytes::DiffusionMethod::XDither,
+    };
     let rgb_image = img.into_rgb8();
     let bytes = rgb_image.as_raw();
     sixel_bytes::sixel_string(
@@ -194,21 +687,114 @@ fn encode_image_as_sixel(img: DynamicImage) -> Result<String, ()> {
         rgb_image.width() as _,
         rgb_image.height() as _,
         sixel_bytes::PixelFormat::RGB888,
-        sixel_bytes::DiffusionMethod::Auto,
+        diffusion,
     )
     .map_err(|_| ())
 }
 
 #[cfg(not(feature = "sixel"))]
-fn encode_image_as_sixel(_img: DynamicImage) -> Result<String> {
+fn encode_image_as_sixel(_img: DynamicImage, _options: &SixelOptions) -> Result<String> {
     Ok("[No sixel support, please build with sixel feature enabled.]\n".to_string())
 }
 
+/// Largest size a sixel image is drawn at, the configured limits narrowed to
+/// the terminal's pixel width when the terminal reports it.
+#[cfg(feature = "sixel")]
+fn sixel_size_limit(options: &SixelOptions) -> (u32, u32) {
+    let max_width = match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 => options.max_width.min(size.width as u32),
+        _ => options.max_width,
+    };
+    (max_width.max(1), options.max_height.max(1))
+}
+
 fn encode_image_as_kitty(img: DynamicImage) -> Result<String> {
-    Ok(get_image_kitty_data(img).join(""))
+    match terminal::multiplexer() {
+        None => Ok(get_image_kitty_data(img, "a=T").join("")),
+        Some(Multiplexer::Tmux) => Ok(encode_image_as_kitty_placeholders(img, Multiplexer::Tmux)),
+        // screen ends passthrough at the first ST, which also ends every kitty
+        // command, so the image data cannot reach the terminal intact
+        Some(Multiplexer::Screen) => Err(anyhow::anyhow!(
+            "Kitty images are not supported inside screen."
+        )),
+    }
 }
 
-fn get_image_kitty_data(img: DynamicImage) -> Vec<String> {
+/// Unicode placeholder character of the kitty graphics protocol, cells filled
+/// with it display the part of an image selected by the diacritics following it.
+const KITTY_PLACEHOLDER: char = '\u{10EEEE}';
+
+/// Leading entries of kitty's row/column diacritics, the n-th encodes the
+/// number n. The row count of placeholder images is limited by its length.
+const KITTY_DIACRITICS: &[char] = &[
+    '\u{0305}', '\u{030D}', '\u{030E}', '\u{0310}', '\u{0312}', '\u{033D}', '\u{033E}', '\u{033F}',
+    '\u{0346}', '\u{034A}', '\u{034B}', '\u{034C}', '\u{0350}', '\u{0351}', '\u{0352}', '\u{0357}',
+    '\u{035B}', '\u{0363}', '\u{0364}', '\u{0365}', '\u{0366}', '\u{0367}', '\u{0368}', '\u{0369}',
+    '\u{036A}', '\u{036B}', '\u{036C}', '\u{036D}', '\u{036E}', '\u{036F}', '\u{0483}', '\u{0484}',
+    '\u{0485}', '\u{0486}', '\u{0487}', '\u{0592}', '\u{0593}', '\u{0594}', '\u{0595}', '\u{0597}',
+    '\u{0598}', '\u{0599}', '\u{059C}', '\u{059D}', '\u{059E}', '\u{059F}', '\u{05A0}', '\u{05A1}',
+    '\u{05A8}', '\u{05A9}', '\u{05AB}', '\u{05AC}', '\u{05AF}', '\u{05C4}', '\u{0610}', '\u{0611}',
+    '\u{0612}', '\u{0613}', '\u{0614}', '\u{0615}', '\u{0616}', '\u{0617}', '\u{0657}', '\u{0658}',
+];
+
+static NEXT_KITTY_IMAGE_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Transmits the image as a virtual placement and draws it with placeholder
+/// characters. Being plain text, the placeholders survive multiplexers
+/// redrawing the screen, which would wipe directly placed images.
+fn encode_image_as_kitty_placeholders(img: DynamicImage, multiplexer: Multiplexer) -> String {
+    let (columns, rows) = image_cells(img.width(), img.height(), KITTY_DIACRITICS.len() as u32);
+    // ids are encoded in the 24-bit foreground color, avoid 0 and collisions
+    // with images left on screen by earlier runs
+    let id = (std::process::id().wrapping_mul(131)
+        + NEXT_KITTY_IMAGE_ID.fetch_add(1, Ordering::Relaxed))
+        % 0xFF_FFFF
+        + 1;
+    let controls = format!("a=T,U=1,i={},c={},r={},q=2", id, columns, rows);
+    let mut output = get_image_kitty_data(img, &controls)
+        .iter()
+        .map(|chunk| terminal::passthrough(chunk, Some(multiplexer)))
+        .collect::<String>();
+    let color = format!("\x1b[38;2;{};{};{}m", id >> 16, (id >> 8) & 0xFF, id & 0xFF);
+    for row_diacritic in KITTY_DIACRITICS.iter().take(rows as usize) {
+        output.push_str(&color);
+        output.push(KITTY_PLACEHOLDER);
+        output.push(*row_diacritic);
+        output.push(KITTY_DIACRITICS[0]);
+        // following cells continue the row and column of their left neighbor
+        for _ in 1..columns {
+            output.push(KITTY_PLACEHOLDER);
+        }
+        output.push_str("\x1b[39m\n");
+    }
+    output
+}
+
+/// Number of terminal cells an image of `width` x `height` pixels covers,
+/// limited to the terminal width and `max_rows`, keeping the aspect ratio.
+fn image_cells(width: u32, height: u32, max_rows: u32) -> (u32, u32) {
+    let (cell_width, cell_height, max_columns) = match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.columns > 0 => (
+            (size.width / size.columns).max(1) as f64,
+            (size.height / size.rows.max(1)).max(1) as f64,
+            size.columns as u32,
+        ),
+        _ => (10.0, 20.0, 80),
+    };
+    let columns = (width as f64 / cell_width).ceil().max(1.0);
+    let rows = (height as f64 / cell_height).ceil().max(1.0);
+    let scale = (max_columns as f64 / columns)
+        .min(max_rows as f64 / rows)
+        .min(1.0);
+    (
+        ((columns * scale).round() as u32).max(1),
+        ((rows * scale).round() as u32).max(1),
+    )
+}
+
+/// Kitty graphics commands transmitting `img`, with `controls` added to the
+/// keys of the first command.
+fn get_image_kitty_data(img: DynamicImage, controls: &str) -> Vec<String> {
     let rgb_image = img.to_rgb8();
     let rgb_data: Vec<u8> = rgb_image.pixels().flat_map(|pix| pix.0).collect();
     let pixels_encoded = BASE64_STANDARD.encode(rgb_data);
@@ -216,9 +802,10 @@ fn get_image_kitty_data(img: DynamicImage) -> Vec<String> {
     // no need to split if len <= 4096.
     if pixels_encoded.len() <= 4096 {
         return vec![format!(
-            "\x1b_Gf=24,s={},v={},a=T;{}\x1b\\",
+            "\x1b_Gf=24,s={},v={},{};{}\x1b\\",
             rgb_image.width(),
             rgb_image.height(),
+            controls,
             pixels_encoded
         )];
     }
@@ -227,16 +814,16 @@ fn get_image_kitty_data(img: DynamicImage) -> Vec<String> {
         chunk_cnt += 1
     }
     let mut result = vec![format!(
-        "\x1b_Gf=24,s={},v={},a=T,m=1;{}\x1b\\",
+        "\x1b_Gf=24,s={},v={},{},m=1;{}\x1b\\",
         rgb_image.width(),
         rgb_image.height(),
+        controls,
         // since encoded base64 is guaranteed to be ascii
         // slicing will be fine.
         &pixels_encoded[..4096]
     )];
 
-    pixels_encoded[4096..]
-        .as_bytes()
+    pixels_encoded.as_bytes()[4096..]
         .chunks(4096)
         .enumerate()
         .for_each(|(i, chunk)| {
@@ -250,9 +837,12 @@ fn get_image_kitty_data(img: DynamicImage) -> Vec<String> {
     result
 }
 
-fn encode_image_as_iterm(img: DynamicImage) -> Result<String> {
+/// Encodes `img` as an iTerm inline image sized in cells, so the terminal
+/// scales it with the font, keeping the aspect ratio within `max_rows`.
+fn encode_image_as_iterm(img: DynamicImage, src: &str, max_rows: u32) -> Result<String> {
     let mut bytes = vec![];
     let (w, h) = (img.width(), img.height());
+    let (columns, rows) = image_cells(w, h, max_rows.max(1));
     PngEncoder::new(&mut bytes).write_image(
         &img.into_rgba8(),
         w,
@@ -265,7 +855,9 @@ fn encode_image_as_iterm(img: DynamicImage) -> Result<String> {
     );
     write!(
         buf,
-        "\x1b]1337;File=inline=1;size={};width={w}px;height={h}px;doNotMoveCursor=1:",
+        "\x1b]1337;File=inline=1;name={};size={};width={columns};height={rows};\
+         preserveAspectRatio=1;doNotMoveCursor=1:",
+        BASE64_STANDARD.encode(image_name(src)),
         bytes.len(),
     )?;
     BASE64_STANDARD.encode_string(bytes, &mut buf);
@@ -273,15 +865,36 @@ fn encode_image_as_iterm(img: DynamicImage) -> Result<String> {
     Ok(buf)
 }
 
+/// File name of an image URL, shown by iTerm e.g. when saving the image.
+pub fn image_name(src: &str) -> &str {
+    if src.starts_with("data:") {
+        return "image";
+    }
+    let path = src.split(['?', '#']).next().unwrap_or_default();
+    match path.trim_end_matches('/').rsplit('/').next() {
+        Some(name) if !name.is_empty() && !name.contains(':') => name,
+        _ => "image",
+    }
+}
+
+/// Terminal probe result, the terminal is only queried once per process.
+static PROBED_PROTOCOL: LazyLock<Option<GraphicsProtocol>> =
+    LazyLock::new(|| terminal::probe_graphics(terminal::PROBE_TIMEOUT));
+
+/// Resolves `Auto` by querying the terminal, falling back to guessing from
+/// the environment for terminals that do not answer, such as iTerm.
 fn transform_protocol(original: GraphicsProtocol) -> GraphicsProtocol {
     if !matches!(original, GraphicsProtocol::Auto) {
         return original;
     }
+    if let Some(protocol) = *PROBED_PROTOCOL {
+        return protocol;
+    }
     let term = env::var("TERM");
-    if let Ok(term) = term {
-        if term.contains("kitty") {
-            return GraphicsProtocol::Kitty;
-        }
+    if let Ok(term) = term
+        && term.contains("kitty")
+    {
+        return GraphicsProtocol::Kitty;
     }
     let term_program = env::var("TERM_PROGRAM");
     if term_program.is_err() {
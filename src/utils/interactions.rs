@@ -1,7 +1,15 @@
+use super::{
+    html::wrap_text,
+    terminal::{screen_reader, vertical_bar},
+    width::{Align, copy_escape_sequence, display_width, pad, strip_styling, truncate},
+};
+use crate::i18n::translate;
 use colored::Colorize;
 use crossterm::{
     cursor::{self, MoveTo},
-    event::{self, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, MouseButton, MouseEventKind,
+    },
     execute, queue,
     style::Print,
     terminal::{self, ClearType},
@@ -9,79 +17,261 @@ use crossterm::{
 use std::{
     cmp::min,
     io::{Write, stdout},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
 };
 
-pub fn select_within<T>(
+/// Two clicks on the same option within this interval confirm it.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+/// Narrower terminals leave out the preview pane.
+const MIN_PREVIEW_COLUMNS: u16 = 60;
+const PREVIEW_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// What the user did in a selector.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Selection<T = usize> {
+    Selected(T),
+    /// → was pressed on a list that is not on its last page.
+    NextPage,
+    /// ← was pressed on a list that is not on its first page.
+    PrevPage,
+    Cancelled,
+}
+
+impl<T> Selection<T> {
+    fn map<U>(self, f: impl FnOnce(T) -> U) -> Selection<U> {
+        match self {
+            Selection::Selected(selected) => Selection::Selected(f(selected)),
+            Selection::NextPage => Selection::NextPage,
+            Selection::PrevPage => Selection::PrevPage,
+            Selection::Cancelled => Selection::Cancelled,
+        }
+    }
+}
+
+/// Lets the user pick one of `options` in a full-screen list below `prompt`.
+/// Options may span several lines, they are wrapped to the terminal width and
+/// laid out again whenever it is resized. ←/→ turn the page if `status` has
+/// one.
+pub fn select_within<T>(prompt: &str, options: &[T], status: &StatusBar) -> Selection
+where
+    T: std::fmt::Display,
+{
+    select(prompt, options, status, false, None).map(|selected| selected[0])
+}
+
+/// Like [`select_within`], with `preview` of the highlighted option shown on
+/// the right half of wide terminals. `preview` returns `None` while the text
+/// is not available yet, and is asked again shortly after.
+pub fn select_with_preview<T>(
     prompt: &str,
     options: &[T],
-    per_option_height: u16,
-    prompt_height: u16,
-) -> Option<usize>
+    status: &StatusBar,
+    preview: &mut dyn FnMut(usize) -> Option<String>,
+) -> Selection
+where
+    T: std::fmt::Display,
+{
+    select(prompt, options, status, false, Some(preview)).map(|selected| selected[0])
+}
+
+/// Like [`select_within`], but Space (or a double-click) toggles options and
+/// Enter confirms all toggled ones, in list order. Confirming with nothing
+/// toggled picks the highlighted option.
+pub fn select_many_within<T>(
+    prompt: &str,
+    options: &[T],
+    status: &StatusBar,
+) -> Selection<Vec<usize>>
+where
+    T: std::fmt::Display,
+{
+    select(prompt, options, status, true, None)
+}
+
+/// Like [`select_many_within`], with `preview` of the highlighted option as
+/// in [`select_with_preview`].
+pub fn select_many_with_preview<T>(
+    prompt: &str,
+    options: &[T],
+    status: &StatusBar,
+    preview: &mut dyn FnMut(usize) -> Option<String>,
+) -> Selection<Vec<usize>>
+where
+    T: std::fmt::Display,
+{
+    select(prompt, options, status, true, Some(preview))
+}
+
+fn select<T>(
+    prompt: &str,
+    options: &[T],
+    status: &StatusBar,
+    multiple: bool,
+    mut preview: Option<&mut dyn FnMut(usize) -> Option<String>>,
+) -> Selection<Vec<usize>>
 where
     T: std::fmt::Display,
 {
     if options.is_empty() {
-        return None;
+        return Selection::Cancelled;
     }
-    let mut selected_index = 0;
+    let (has_prev_page, has_next_page) = match status.page {
+        Some((page, max_page)) => (page > 1, page < max_page),
+        None => (false, false),
+    };
+    let texts = options.iter().map(|o| o.to_string()).collect::<Vec<_>>();
+    let searchable = texts
+        .iter()
+        .map(|text| strip_styling(text).to_lowercase())
+        .collect::<Vec<_>>();
+    let prompt = prompt.trim_end_matches('\n');
+    let mut toggled = vec![false; options.len()];
+    // position of the highlighted option among those shown
+    let mut cursor = 0;
     let mut options_offset_rows = 0;
-    // prompt, ellipsis top, ellipsis bottom, key prompt.
-    let fixed_rows = 3 + prompt_height;
-    // prompt, ellipsis top.
-    let display_offset_rows = 1 + prompt_height;
     let options_len = options.len();
+    // index label, checkbox and the "> " cursor in front of each option
+    let prefix_width = index_label(0, options_len).len() + mark(multiple, None).len() + 2;
+    let inline = INLINE.load(Ordering::Relaxed);
     let mut stdout = stdout();
     terminal::enable_raw_mode().unwrap();
-    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide).unwrap();
-    let result = loop {
+    // first terminal row of the selector
+    let mut origin = 0;
+    if inline {
+        // make room below the cursor, scrolling the terminal if needed
         let (_, terminal_rows) = terminal::size().unwrap();
-        let scroll_height = terminal_rows - fixed_rows;
-        queue!(stdout, terminal::Clear(ClearType::All)).unwrap();
-        for (i, line) in prompt.lines().take(prompt_height as usize).enumerate() {
+        let height = INLINE_ROWS.min(terminal_rows);
+        execute!(stdout, Print("\r\n".repeat(height as usize - 1))).unwrap();
+        let (_, row) = cursor::position().unwrap();
+        origin = row.saturating_sub(height - 1);
+        execute!(stdout, EnableMouseCapture, cursor::Hide).unwrap();
+    } else {
+        execute!(
+            stdout,
+            terminal::EnterAlternateScreen,
+            EnableMouseCapture,
+            cursor::Hide
+        )
+        .unwrap();
+    }
+    let mut last_click: Option<(usize, Instant)> = None;
+    let mut jump = String::new();
+    let mut filter = String::new();
+    let mut filtering = false;
+    let mut resized = false;
+    let result = loop {
+        let needle = filter.to_lowercase();
+        let shown = (0..options_len)
+            .filter(|&i| searchable[i].contains(&needle))
+            .collect::<Vec<_>>();
+        let shown_len = shown.len();
+        let (terminal_columns, mut terminal_rows) = terminal::size().unwrap();
+        if inline {
+            terminal_rows = INLINE_ROWS.min(terminal_rows);
+            origin = origin.min(terminal::size().unwrap().1 - terminal_rows);
+        }
+        let preview_width = match preview {
+            Some(_) if terminal_columns >= MIN_PREVIEW_COLUMNS => terminal_columns / 2,
+            _ => 0,
+        };
+        let list_width = (terminal_columns - preview_width) as usize;
+        let prompt_lines = wrap_text(prompt, terminal_columns as usize);
+        // long prompts are cut to leave room for the list
+        let prompt_height = (prompt_lines.split('\n').count() as u16).min(terminal_rows / 2);
+        // prompt, ellipsis top, ellipsis bottom, key prompt.
+        let fixed_rows = 3 + prompt_height;
+        // prompt, ellipsis top.
+        let display_offset_rows = 1 + prompt_height;
+        let scroll_height = terminal_rows.saturating_sub(fixed_rows).max(1);
+        let content_width = list_width.saturating_sub(prefix_width + 1).max(1);
+        let wrapped = shown
+            .iter()
+            .map(|&i| wrap_text(&texts[i], content_width))
+            .collect::<Vec<_>>();
+        let starts = row_starts(wrapped.iter().map(|text| text.split('\n').count()));
+        let total_rows = starts[shown_len];
+        if resized && shown_len > 0 {
+            // options wrap differently now, find the selection again
+            options_offset_rows =
+                scroll_offset(cursor, &starts, scroll_height as usize, options_offset_rows);
+            resized = false;
+        }
+        options_offset_rows =
+            options_offset_rows.min(total_rows.saturating_sub(scroll_height as usize));
+        queue!(
+            stdout,
+            MoveTo(0, origin),
+            terminal::Clear(ClearType::FromCursorDown)
+        )
+        .unwrap();
+        for (i, line) in prompt_lines
+            .split('\n')
+            .take(prompt_height as usize)
+            .enumerate()
+        {
             queue!(
                 stdout,
-                MoveTo(0, i as u16),
+                MoveTo(0, origin + i as u16),
                 Print(format!("{}", line.white().bold()))
             )
             .unwrap();
         }
-        for (i, option) in options
-            .iter()
-            .enumerate()
-            .skip((options_offset_rows as f32 / per_option_height as f32).floor() as usize)
-            .take((scroll_height as f32 / per_option_height as f32).ceil() as usize)
-        {
-            for (j, line) in option
-                .to_string()
-                .lines()
-                .enumerate()
-                .take(per_option_height as usize)
-            {
-                if i * per_option_height as usize + j < options_offset_rows {
+        if shown.is_empty() {
+            queue!(
+                stdout,
+                MoveTo(0, origin + display_offset_rows),
+                Print(format!("  {}", tr!("no-matches").dimmed()))
+            )
+            .unwrap();
+        }
+        let visible = options_offset_rows..options_offset_rows + scroll_height as usize;
+        for (position, text) in wrapped.iter().enumerate() {
+            if starts[position + 1] <= visible.start || starts[position] >= visible.end {
+                continue;
+            }
+            let i = shown[position];
+            for (j, line) in text.split('\n').enumerate() {
+                let row = starts[position] + j;
+                if !visible.contains(&row) {
                     continue;
                 }
-                if i * per_option_height as usize + j
-                    >= options_offset_rows + scroll_height as usize
-                {
-                    break;
-                }
+                let prefix = if j == 0 {
+                    format!(
+                        "{}{}",
+                        index_label(i, options_len),
+                        mark(multiple, toggled[i])
+                    )
+                } else {
+                    format!(
+                        "{}{}",
+                        " ".repeat(index_label(i, options_len).len()),
+                        mark(multiple, None)
+                    )
+                };
+                let line = if colored::control::SHOULD_COLORIZE.should_colorize() {
+                    highlight_matches(line, &filter)
+                } else {
+                    line.to_string()
+                };
                 queue!(
                     stdout,
                     MoveTo(
                         0,
-                        display_offset_rows
-                            + (i * per_option_height as usize + j - options_offset_rows) as u16
+                        origin + display_offset_rows + (row - visible.start) as u16
                     ),
                     terminal::Clear(ClearType::CurrentLine),
-                    if i == selected_index {
-                        Print(if j == 0 {
-                            format!("{}", format!("> {}", line.clear()).green().bold())
-                        } else {
-                            format!("  {}", line.normal().green().bold())
-                        })
-                    } else {
-                        Print(format!("  {}", line))
-                    }
+                    Print(match (position == cursor, j == 0) {
+                        (true, true) => format!(
+                            "{}",
+                            format!("> {}{}", prefix, line.as_str().clear())
+                                .green()
+                                .bold()
+                        ),
+                        (true, false) =>
+                            format!("  {}{}", prefix, line.as_str().normal().green().bold()),
+                        (false, _) => format!("  {}{}", prefix.dimmed(), line),
+                    })
                 )
                 .unwrap();
             }
@@ -89,17 +279,16 @@ where
         if options_offset_rows > 0 {
             queue!(
                 stdout,
-                MoveTo(0, display_offset_rows - 1),
+                MoveTo(0, origin + display_offset_rows - 1),
                 terminal::Clear(ClearType::CurrentLine),
                 Print("  ...")
             )
             .unwrap();
         }
-        if options_offset_rows + (scroll_height as usize) < options_len * per_option_height as usize
-        {
+        if visible.end < total_rows {
             queue!(
                 stdout,
-                MoveTo(0, terminal_rows - 2),
+                MoveTo(0, origin + terminal_rows.saturating_sub(2)),
                 terminal::Clear(ClearType::CurrentLine),
                 Print("  ...")
             )
@@ -107,47 +296,562 @@ where
         }
         queue!(
             stdout,
-            MoveTo(0, terminal_rows - 1),
+            MoveTo(0, origin + terminal_rows.saturating_sub(1)),
             terminal::Clear(ClearType::CurrentLine),
-            Print("↑/k/↓/j/q/Esc/Enter"),
+            Print(
+                status
+                    .line(
+                        &if !jump.is_empty() {
+                            format!("{} {}", tr!("go-to"), jump)
+                        } else if filtering {
+                            format!(
+                                "{} {}█ ({}/{})",
+                                tr!("filter"),
+                                filter,
+                                shown_len,
+                                options_len
+                            )
+                        } else {
+                            let mut hints = vec![tr!("hint-move")];
+                            if status.page.is_some() {
+                                hints.push(tr!("hint-page"));
+                            }
+                            if multiple {
+                                hints.push(tr!("hint-toggle"));
+                            }
+                            hints.push(tr!("hint-confirm"));
+                            hints.join(", ")
+                        },
+                        terminal_columns as usize
+                    )
+                    .reversed()
+            ),
         )
         .unwrap();
+        let mut loading = false;
+        if preview_width > 0
+            && let Some(preview) = preview.as_mut()
+            && let Some(&selected) = shown.get(cursor)
+        {
+            let text = preview(selected);
+            loading = text.is_none();
+            let text = text.unwrap_or_else(|| tr!("loading").dimmed().to_string());
+            let wrapped = wrap_text(&text, preview_width.saturating_sub(2) as usize);
+            let mut lines = wrapped.lines();
+            for row in 0..scroll_height {
+                queue!(
+                    stdout,
+                    MoveTo(list_width as u16, origin + display_offset_rows + row),
+                    Print(vertical_bar().dimmed()),
+                    Print(" "),
+                    Print(lines.next().unwrap_or_default())
+                )
+                .unwrap();
+            }
+        }
         stdout.flush().unwrap();
-        let e = event::read().unwrap();
-        if !e.is_key() {
+        // redraw until the preview is loaded
+        if loading && !event::poll(PREVIEW_POLL_INTERVAL).unwrap() {
             continue;
         }
-        let key = e.as_key_event().unwrap();
+        let key = match event::read().unwrap() {
+            Event::Key(key) => key,
+            Event::Mouse(mouse) => {
+                match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        let Some(position) = mouse.row.checked_sub(origin).and_then(|row| {
+                            option_at_row(
+                                row,
+                                display_offset_rows,
+                                scroll_height,
+                                options_offset_rows,
+                                &starts,
+                            )
+                        }) else {
+                            continue;
+                        };
+                        let clicked = shown[position];
+                        if let Some((previous, at)) = last_click
+                            && previous == clicked
+                            && at.elapsed() <= DOUBLE_CLICK_INTERVAL
+                        {
+                            if !multiple {
+                                break Selection::Selected(vec![clicked]);
+                            }
+                            toggled[clicked] = !toggled[clicked];
+                            last_click = None;
+                            continue;
+                        }
+                        cursor = position;
+                        last_click = Some((clicked, Instant::now()));
+                    }
+                    // the wheel moves the viewport by whole options
+                    MouseEventKind::ScrollUp => {
+                        options_offset_rows = starts
+                            .iter()
+                            .rev()
+                            .find(|&&start| start < options_offset_rows)
+                            .copied()
+                            .unwrap_or(0);
+                    }
+                    MouseEventKind::ScrollDown => {
+                        options_offset_rows = starts
+                            .iter()
+                            .find(|&&start| start > options_offset_rows)
+                            .copied()
+                            .unwrap_or(options_offset_rows);
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            Event::Resize(..) => {
+                resized = true;
+                continue;
+            }
+            _ => continue,
+        };
         if key.kind != event::KeyEventKind::Press {
             continue;
         }
+        let control = key.modifiers.contains(event::KeyModifiers::CONTROL);
+        if filtering {
+            // typed text goes to the filter, other keys work as usual
+            match key.code {
+                KeyCode::Char(c) if !control => filter.push(c),
+                KeyCode::Backspace => {
+                    filter.pop();
+                    filtering = !filter.is_empty();
+                }
+                KeyCode::Esc => {
+                    filter.clear();
+                    filtering = false;
+                }
+                _ => {}
+            }
+            if matches!(
+                key.code,
+                KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Esc
+            ) && !control
+            {
+                cursor = 0;
+                options_offset_rows = 0;
+                continue;
+            }
+        }
         match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => break None,
-            KeyCode::Char('c') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
-                break None;
+            // typing a number and Enter moves to the option with that index
+            KeyCode::Char(c @ '0'..='9') => {
+                jump.push(c);
+                continue;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                selected_index = selected_index.saturating_sub(1);
-                if (selected_index * per_option_height as usize) < options_offset_rows {
-                    options_offset_rows = selected_index * per_option_height as usize;
+            KeyCode::Backspace if !jump.is_empty() => {
+                jump.pop();
+                continue;
+            }
+            KeyCode::Esc if !jump.is_empty() => {
+                jump.clear();
+                continue;
+            }
+            KeyCode::Enter if !jump.is_empty() => {
+                if let Some(index) = jump_target(&jump, options_len)
+                    && let Some(position) = shown.iter().position(|&i| i == index)
+                {
+                    cursor = position;
                 }
+                jump.clear();
+            }
+            KeyCode::Char('?') => {
+                show_help(terminal_columns, origin, terminal_rows, multiple);
+                continue;
+            }
+            KeyCode::Char('q') | KeyCode::Esc => break Selection::Cancelled,
+            KeyCode::Char('c') if control => {
+                break Selection::Cancelled;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                cursor = cursor.saturating_sub(1);
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                selected_index = min(selected_index + 1, options_len - 1);
-                if (selected_index + 1) * per_option_height as usize - options_offset_rows
-                    >= scroll_height as usize
-                {
-                    options_offset_rows =
-                        (selected_index + 1) * per_option_height as usize - scroll_height as usize;
+                cursor = min(cursor + 1, shown_len.saturating_sub(1));
+            }
+            KeyCode::PageUp if shown_len > 0 => {
+                let row = starts[cursor].saturating_sub(scroll_height as usize);
+                cursor = option_containing(&starts, row).min(cursor.saturating_sub(1));
+            }
+            KeyCode::PageDown if shown_len > 0 => {
+                let row = starts[cursor] + scroll_height as usize;
+                cursor = option_containing(&starts, row)
+                    .max(cursor + 1)
+                    .min(shown_len - 1);
+            }
+            KeyCode::Left if has_prev_page => break Selection::PrevPage,
+            KeyCode::Right if has_next_page => break Selection::NextPage,
+            KeyCode::Home | KeyCode::Char('g') => cursor = 0,
+            KeyCode::End | KeyCode::Char('G') => cursor = shown_len.saturating_sub(1),
+            KeyCode::Char(' ') if multiple => {
+                if let Some(&selected) = shown.get(cursor) {
+                    toggled[selected] = !toggled[selected];
                 }
             }
             KeyCode::Enter => {
-                break Some(selected_index);
+                let chosen = (0..options_len).filter(|&i| toggled[i]).collect::<Vec<_>>();
+                if !chosen.is_empty() {
+                    break Selection::Selected(chosen);
+                }
+                if let Some(&selected) = shown.get(cursor) {
+                    break Selection::Selected(vec![selected]);
+                }
+                continue;
+            }
+            // any other text starts filtering, "/" also for the keys above
+            KeyCode::Char(c) if !control => {
+                filtering = true;
+                if c != '/' {
+                    filter.push(c);
+                }
+                cursor = 0;
+                options_offset_rows = 0;
+                continue;
             }
             _ => continue,
         }
+        if shown_len > 0 {
+            options_offset_rows =
+                scroll_offset(cursor, &starts, scroll_height as usize, options_offset_rows);
+        }
     };
-    execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show).unwrap();
+    if inline {
+        // leave the prompt and the choice in the scrollback
+        let choice = match &result {
+            Selection::Selected(selected) => selected
+                .iter()
+                .map(|&i| texts[i].lines().next().unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join(", "),
+            Selection::NextPage => tr!("next-page").dimmed().to_string(),
+            Selection::PrevPage => tr!("previous-page").dimmed().to_string(),
+            Selection::Cancelled => tr!("cancelled").dimmed().to_string(),
+        };
+        execute!(
+            stdout,
+            MoveTo(0, origin),
+            terminal::Clear(ClearType::FromCursorDown),
+            Print(format!(
+                "{} {}\r\n",
+                prompt.lines().next().unwrap_or_default().white().bold(),
+                choice
+            )),
+            DisableMouseCapture,
+            cursor::Show
+        )
+        .unwrap();
+    } else {
+        execute!(
+            stdout,
+            DisableMouseCapture,
+            terminal::LeaveAlternateScreen,
+            cursor::Show
+        )
+        .unwrap();
+    }
     terminal::disable_raw_mode().unwrap();
     result
 }
+
+/// Draw selectors below the cursor instead of on the alternate screen.
+static INLINE: AtomicBool = AtomicBool::new(false);
+
+/// Rows taken by an inline selector, at most.
+const INLINE_ROWS: u16 = 15;
+
+/// Makes selectors render inline in the normal screen buffer, leaving the
+/// prompt and the final choice in the scrollback.
+pub fn set_inline(inline: bool) {
+    INLINE.store(inline, Ordering::Relaxed);
+}
+
+/// Marks the case-insensitive occurrences of `needle` in a styled `line`
+/// with reverse video, leaving its own styling in place.
+pub fn highlight_matches(line: &str, needle: &str) -> String {
+    if needle.is_empty() {
+        return line.to_string();
+    }
+    // visible characters, each with the styling that precedes it
+    let mut pieces: Vec<(String, char)> = vec![];
+    let mut styling = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            styling.push(c);
+            copy_escape_sequence(&mut chars, &mut styling);
+            continue;
+        }
+        pieces.push((std::mem::take(&mut styling), c));
+    }
+    let needle = needle
+        .chars()
+        .flat_map(char::to_lowercase)
+        .collect::<Vec<_>>();
+    let haystack = pieces
+        .iter()
+        .map(|(_, c)| c.to_lowercase().next().unwrap_or(*c))
+        .collect::<Vec<_>>();
+    let mut matched = vec![false; pieces.len()];
+    let mut i = 0;
+    while i + needle.len() <= haystack.len() {
+        if haystack[i..i + needle.len()] == needle[..] {
+            matched[i..i + needle.len()].fill(true);
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    let mut output = String::new();
+    for (i, (styling, c)) in pieces.iter().enumerate() {
+        output.push_str(styling);
+        // a reset within the match would end the highlight too
+        if matched[i] && (i == 0 || !matched[i - 1] || !styling.is_empty()) {
+            output.push_str("\x1b[7m");
+        }
+        output.push(*c);
+        if matched[i] && (i + 1 == pieces.len() || !matched[i + 1]) {
+            output.push_str("\x1b[27m");
+        }
+    }
+    output.push_str(&styling);
+    output
+}
+
+/// Where the user is, shown at the bottom of interactive screens so the
+/// context is kept while drilling through menus.
+#[derive(Default, Clone)]
+pub struct StatusBar {
+    /// Email of the saved account.
+    pub user: Option<String>,
+    pub group: Option<String>,
+    pub probset: Option<String>,
+    /// Current and last page.
+    pub page: Option<(u32, u32)>,
+    /// Submissions still waiting for a verdict.
+    pub pending: usize,
+}
+
+impl StatusBar {
+    /// The bar `width` columns wide, context on the left and `hint` on the
+    /// right. The context is cut short if both do not fit.
+    pub fn line(&self, hint: &str, width: usize) -> String {
+        let hint_width = display_width(hint);
+        let context = truncate(&format!(" {}", self), width.saturating_sub(hint_width + 2));
+        format!(
+            "{}{} ",
+            pad(&context, width.saturating_sub(hint_width + 1), Align::Left),
+            hint
+        )
+    }
+}
+
+impl std::fmt::Display for StatusBar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = vec![self.user.clone().unwrap_or_else(|| tr!("not-logged-in"))];
+        match (&self.group, &self.probset) {
+            (Some(group), Some(probset)) => parts.push(format!("{}/{}", group, probset)),
+            (Some(group), None) => parts.push(group.clone()),
+            _ => {}
+        }
+        if let Some((page, max_page)) = self.page {
+            parts.push(tr!("status-page", page = page, max = max_page));
+        }
+        if self.pending > 0 {
+            parts.push(tr!("status-pending", count = self.pending));
+        }
+        write!(f, "{}", parts.join(&format!(" {} ", vertical_bar())))
+    }
+}
+
+/// Keys of the selector and what they do, listed by `?`.
+fn key_help(multiple: bool) -> Vec<(String, String)> {
+    let mut keys = vec![
+        ("↑ k / ↓ j".to_string(), "key-move"),
+        ("PgUp / PgDn".to_string(), "key-move-page"),
+        ("Home g / End G".to_string(), "key-first-last"),
+        ("← / →".to_string(), "key-page"),
+        ("0-9 Enter".to_string(), "key-jump"),
+        (tr!("key-name-other"), "key-filter"),
+        (tr!("key-name-escape-filtering"), "key-clear-filter"),
+        (tr!("key-name-click"), "key-highlight"),
+        (tr!("key-name-wheel"), "key-scroll"),
+    ];
+    if multiple {
+        keys.extend([
+            ("Space".to_string(), "key-toggle-highlighted"),
+            (tr!("key-name-double-click"), "key-toggle"),
+            ("Enter".to_string(), "key-confirm-toggled"),
+        ]);
+    } else {
+        keys.extend([
+            (tr!("key-name-double-click"), "key-confirm"),
+            ("Enter".to_string(), "key-confirm-highlighted"),
+        ]);
+    }
+    keys.extend([
+        ("q / Esc / Ctrl-C".to_string(), "key-cancel"),
+        ("?".to_string(), "key-help"),
+    ]);
+    keys.into_iter()
+        .map(|(key, action)| (key, translate(action, None)))
+        .collect()
+}
+
+/// Draws the key help in a box over the list and waits for a key or click,
+/// after which the caller redraws the list.
+fn show_help(terminal_columns: u16, origin: u16, terminal_rows: u16, multiple: bool) {
+    let keys = key_help(multiple);
+    let key_width = keys
+        .iter()
+        .map(|(key, _)| display_width(key))
+        .max()
+        .unwrap_or(0);
+    let mut lines = keys
+        .iter()
+        .map(|(key, action)| format!("{}  {}", pad(key, key_width, Align::Left), action))
+        .collect::<Vec<_>>();
+    lines.push(String::new());
+    lines.push(tr!("press-any-key"));
+    let inner_width = lines
+        .iter()
+        .map(|line| display_width(line))
+        .max()
+        .unwrap_or(0);
+    let box_width = (inner_width + 4) as u16;
+    let box_height = (lines.len() + 2) as u16;
+    let left = terminal_columns.saturating_sub(box_width) / 2;
+    let top = origin + terminal_rows.saturating_sub(box_height) / 2;
+    let mut stdout = stdout();
+    let (top_corners, bottom_corners, horizontal) = if screen_reader() {
+        (("+", "+"), ("+", "+"), "-")
+    } else {
+        (("┌", "┐"), ("└", "┘"), "─")
+    };
+    let horizontal = horizontal.repeat(inner_width + 2);
+    queue!(
+        stdout,
+        MoveTo(left, top),
+        Print(format!("{}{}{}", top_corners.0, horizontal, top_corners.1))
+    )
+    .unwrap();
+    for (i, line) in lines.iter().enumerate() {
+        queue!(
+            stdout,
+            MoveTo(left, top + 1 + i as u16),
+            Print(format!(
+                "{} {} {}",
+                vertical_bar(),
+                pad(line, inner_width, Align::Left),
+                vertical_bar()
+            ))
+        )
+        .unwrap();
+    }
+    queue!(
+        stdout,
+        MoveTo(left, top + box_height - 1),
+        Print(format!(
+            "{}{}{}",
+            bottom_corners.0, horizontal, bottom_corners.1
+        ))
+    )
+    .unwrap();
+    stdout.flush().unwrap();
+    loop {
+        match event::read().unwrap() {
+            Event::Key(key) if key.kind == event::KeyEventKind::Press => break,
+            Event::Mouse(mouse) if matches!(mouse.kind, MouseEventKind::Down(_)) => break,
+            _ => {}
+        }
+    }
+}
+
+/// 1-based index in front of the first line of an option, right-aligned to
+/// the widest index.
+fn index_label(index: usize, options_len: usize) -> String {
+    let width = options_len.to_string().len();
+    format!("{:>width$}. ", index + 1)
+}
+
+/// Option picked by typing its 1-based index, if there is one.
+pub fn jump_target(typed: &str, options_len: usize) -> Option<usize> {
+    typed
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| index.checked_sub(1))
+        .filter(|&index| index < options_len)
+}
+
+/// Checkbox in front of an option in multi-select mode, `None` giving the
+/// indentation of its continuation lines.
+fn mark(multiple: bool, toggled: impl Into<Option<bool>>) -> &'static str {
+    match (multiple, toggled.into()) {
+        (false, _) => "",
+        (true, Some(true)) => "[x] ",
+        (true, Some(false)) => "[ ] ",
+        (true, None) => "    ",
+    }
+}
+
+/// Top row of each option when laid out one after another, followed by the
+/// total number of rows.
+pub fn row_starts(heights: impl IntoIterator<Item = usize>) -> Vec<usize> {
+    let mut starts = vec![0];
+    for height in heights {
+        starts.push(starts[starts.len() - 1] + height);
+    }
+    starts
+}
+
+/// Index of the option covering list `row`, or of the last option if the row
+/// is past the end.
+fn option_containing(starts: &[usize], row: usize) -> usize {
+    starts
+        .partition_point(|&start| start <= row)
+        .saturating_sub(1)
+        .min(starts.len().saturating_sub(2))
+}
+
+/// Row offset of the viewport that keeps the selected option fully visible,
+/// scrolling as little as possible from `offset`. Options taller than the
+/// viewport are shown from their top.
+pub fn scroll_offset(
+    selected_index: usize,
+    starts: &[usize],
+    scroll_height: usize,
+    offset: usize,
+) -> usize {
+    let top = starts[selected_index];
+    let bottom = starts[selected_index + 1];
+    if top < offset || bottom - top > scroll_height {
+        top
+    } else if bottom > offset + scroll_height {
+        bottom - scroll_height
+    } else {
+        offset
+    }
+}
+
+/// Index of the option drawn on terminal `row`, if the row is inside the
+/// viewport that starts at `display_offset_rows` and shows list rows from
+/// `offset` on.
+pub fn option_at_row(
+    row: u16,
+    display_offset_rows: u16,
+    scroll_height: u16,
+    offset: usize,
+    starts: &[usize],
+) -> Option<usize> {
+    let row = row.checked_sub(display_offset_rows)?;
+    if row >= scroll_height {
+        return None;
+    }
+    let row = offset + row as usize;
+    (row < starts[starts.len() - 1]).then(|| option_containing(starts, row))
+}
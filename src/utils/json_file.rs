@@ -0,0 +1,35 @@
+use std::{fs, io::ErrorKind, path::Path};
+
+use anyhow::Result;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Reads the JSON state file at `path`, or the default value if it does not
+/// exist yet.
+pub fn load_json<T>(path: impl AsRef<Path>) -> Result<T>
+where
+    T: DeserializeOwned + Default,
+{
+    match fs::read_to_string(path.as_ref()) {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Writes `value` to the JSON state file at `path`. The JSON goes to a
+/// temporary file next to it first and is renamed over `path`, so a crash or a
+/// concurrent reader never sees a half-written file.
+pub fn save_json<T>(path: impl AsRef<Path>, value: &T) -> Result<()>
+where
+    T: Serialize,
+{
+    let path = path.as_ref();
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(format!(".{}.tmp", std::process::id()));
+    fs::write(&temp, serde_json::to_string(value)?)?;
+    if let Err(e) = fs::rename(&temp, path) {
+        let _ = fs::remove_file(&temp);
+        return Err(e.into());
+    }
+    Ok(())
+}
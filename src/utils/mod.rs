@@ -1,2 +1,8 @@
+pub mod clipboard;
 pub mod html;
 pub mod interactions;
+pub mod json_file;
+pub mod progress;
+pub mod table;
+pub mod terminal;
+pub mod width;
@@ -0,0 +1,55 @@
+use std::{borrow::Cow, future::Future, time::Duration};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::terminal::screen_reader;
+
+/// Spinner shown on stderr while waiting on the network, with the elapsed
+/// time. It clears itself when finished or dropped, so results printed after
+/// it are not mixed with spinner frames. Nothing is drawn if stderr is not a
+/// terminal. For screen readers the message is printed once instead.
+pub struct Spinner {
+    bar: ProgressBar,
+}
+
+impl Spinner {
+    pub fn start(message: impl Into<Cow<'static, str>>) -> Self {
+        if screen_reader() {
+            let message = message.into();
+            if !message.is_empty() {
+                eprintln!("{}...", message);
+            }
+            return Self {
+                bar: ProgressBar::hidden(),
+            };
+        }
+        let bar = ProgressBar::new_spinner().with_message(message);
+        bar.set_style(
+            ProgressStyle::with_template("{spinner:.cyan} {msg} {elapsed:.dim}")
+                .expect("Spinner template is valid."),
+        );
+        bar.enable_steady_tick(Duration::from_millis(100));
+        Self { bar }
+    }
+
+    pub fn set_message(&self, message: impl Into<Cow<'static, str>>) {
+        self.bar.set_message(message);
+    }
+
+    /// Prints `line` to stdout above the spinner, which keeps spinning below it.
+    pub fn println(&self, line: impl AsRef<str>) {
+        self.bar.suspend(|| println!("{}", line.as_ref()));
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+/// Awaits `future` with a spinner showing `message`.
+pub async fn spin<F: Future>(message: impl Into<Cow<'static, str>>, future: F) -> F::Output {
+    let _spinner = Spinner::start(message);
+    future.await
+}
@@ -0,0 +1,128 @@
+use std::fmt::Display;
+
+use colored::Colorize;
+
+use super::{
+    terminal::screen_reader,
+    width::{Align, display_width, pad},
+};
+
+/// A table drawn with box-drawing borders. Columns are sized by display
+/// width, so wide (CJK) characters and ANSI styling in cells line up. For
+/// screen readers each row is a line of `header: cell` pairs instead.
+pub struct Table {
+    headers: Vec<String>,
+    right_aligned: Vec<bool>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let headers = headers
+            .into_iter()
+            .map(|header| header.to_string())
+            .collect::<Vec<_>>();
+        Self {
+            right_aligned: vec![false; headers.len()],
+            headers,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Aligns `column` to the right, e.g. for numbers.
+    pub fn align_right(mut self, column: usize) -> Self {
+        self.right_aligned[column] = true;
+        self
+    }
+
+    /// Adds a row, missing cells are left blank and extra cells are dropped.
+    pub fn push_row<I, S>(&mut self, row: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let mut row = row
+            .into_iter()
+            .map(|cell| cell.to_string())
+            .collect::<Vec<_>>();
+        row.resize(self.headers.len(), String::new());
+        self.rows.push(row);
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        (0..self.headers.len())
+            .map(|column| {
+                std::iter::once(&self.headers[column])
+                    .chain(self.rows.iter().map(|row| &row[column]))
+                    .map(|cell| display_width(cell))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    fn write_border(
+        f: &mut std::fmt::Formatter<'_>,
+        widths: &[usize],
+        (left, middle, right): (&str, &str, &str),
+    ) -> std::fmt::Result {
+        let segments = widths
+            .iter()
+            .map(|width| "─".repeat(width + 2))
+            .collect::<Vec<_>>();
+        writeln!(f, "{}{}{}", left, segments.join(middle), right)
+    }
+
+    fn write_row(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        widths: &[usize],
+        cells: &[String],
+    ) -> std::fmt::Result {
+        write!(f, "│")?;
+        for (column, cell) in cells.iter().enumerate() {
+            let align = if self.right_aligned[column] {
+                Align::Right
+            } else {
+                Align::Left
+            };
+            write!(f, " {} │", pad(cell, widths[column], align))?;
+        }
+        writeln!(f)
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if screen_reader() {
+            for row in &self.rows {
+                let pairs = self
+                    .headers
+                    .iter()
+                    .zip(row)
+                    .filter(|(_, cell)| !cell.is_empty())
+                    .map(|(header, cell)| format!("{}: {}", header, cell))
+                    .collect::<Vec<_>>();
+                writeln!(f, "{}", pairs.join(", "))?;
+            }
+            return Ok(());
+        }
+        let widths = self.column_widths();
+        let headers = self
+            .headers
+            .iter()
+            .map(|header| header.bold().to_string())
+            .collect::<Vec<_>>();
+        Self::write_border(f, &widths, ("┌", "┬", "┐"))?;
+        self.write_row(f, &widths, &headers)?;
+        Self::write_border(f, &widths, ("├", "┼", "┤"))?;
+        for row in &self.rows {
+            self.write_row(f, &widths, row)?;
+        }
+        Self::write_border(f, &widths, ("└", "┴", "┘"))
+    }
+}
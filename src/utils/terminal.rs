@@ -0,0 +1,148 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use super::html::GraphicsProtocol;
+
+static SCREEN_READER: AtomicBool = AtomicBool::new(false);
+
+/// Makes output linear and explicit for screen readers: no images, spinners
+/// or box-drawing, and words wherever color alone would carry meaning.
+pub fn set_screen_reader(enabled: bool) {
+    SCREEN_READER.store(enabled, Ordering::Relaxed);
+}
+
+pub fn screen_reader() -> bool {
+    SCREEN_READER.load(Ordering::Relaxed)
+}
+
+/// Separator of gutters and columns, read aloud as a plain bar by screen
+/// readers.
+pub fn vertical_bar() -> &'static str {
+    if screen_reader() { "|" } else { "│" }
+}
+
+/// Asks whether the terminal understands the kitty graphics protocol, using a
+/// 1x1 query image which is never displayed.
+const KITTY_QUERY: &str = "\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\";
+/// Primary device attributes. Every terminal answers it, so its reply marks
+/// the end of the answers to the queries sent before it.
+const DA1_QUERY: &str = "\x1b[c";
+
+/// How long to wait for the terminal to answer the queries.
+pub const PROBE_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Terminal multiplexer the program runs in, which sits between it and the
+/// real terminal.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Multiplexer {
+    Tmux,
+    Screen,
+}
+
+pub fn multiplexer() -> Option<Multiplexer> {
+    if std::env::var_os("TMUX").is_some() {
+        return Some(Multiplexer::Tmux);
+    }
+    if std::env::var_os("STY").is_some()
+        || std::env::var("TERM").is_ok_and(|term| term.starts_with("screen"))
+    {
+        return Some(Multiplexer::Screen);
+    }
+    None
+}
+
+/// Wraps `sequence` so that tmux forwards it to the outer terminal instead of
+/// interpreting it. This needs `set -g allow-passthrough on` in tmux.
+pub fn passthrough(sequence: &str, multiplexer: Option<Multiplexer>) -> String {
+    match multiplexer {
+        Some(Multiplexer::Tmux) => {
+            format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+        }
+        _ => sequence.to_string(),
+    }
+}
+
+/// Queries the terminal for its graphics support. `None` if the terminal did
+/// not answer in time, or supports neither kitty graphics nor sixel.
+#[cfg(unix)]
+pub fn probe_graphics(timeout: Duration) -> Option<GraphicsProtocol> {
+    use std::io::{IsTerminal, Write};
+
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return None;
+    }
+    crossterm::terminal::enable_raw_mode().ok()?;
+    let response = (|| {
+        let mut stdout = std::io::stdout();
+        // tmux answers DA1 itself, but the kitty query must reach the terminal
+        let kitty_query = passthrough(KITTY_QUERY, multiplexer());
+        stdout.write_all(kitty_query.as_bytes()).ok()?;
+        stdout.write_all(DA1_QUERY.as_bytes()).ok()?;
+        stdout.flush().ok()?;
+        Some(read_until_device_attributes(timeout))
+    })();
+    let _ = crossterm::terminal::disable_raw_mode();
+    parse_probe_response(&response?)
+}
+
+#[cfg(not(unix))]
+pub fn probe_graphics(_timeout: Duration) -> Option<GraphicsProtocol> {
+    None
+}
+
+/// Reads stdin until the reply to [`DA1_QUERY`] arrives or `timeout` passes.
+#[cfg(unix)]
+fn read_until_device_attributes(timeout: Duration) -> String {
+    let deadline = std::time::Instant::now() + timeout;
+    let mut response = Vec::new();
+    loop {
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        let mut fds = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: `fds` is a single valid pollfd for the duration of the call.
+        let ready = unsafe { libc::poll(&mut fds, 1, remaining.as_millis() as libc::c_int) };
+        if ready <= 0 {
+            break;
+        }
+        let mut chunk = [0u8; 256];
+        // SAFETY: `chunk` is valid for writes of its whole length.
+        let read =
+            unsafe { libc::read(libc::STDIN_FILENO, chunk.as_mut_ptr().cast(), chunk.len()) };
+        if read <= 0 {
+            break;
+        }
+        response.extend_from_slice(&chunk[..read as usize]);
+        if device_attributes(&String::from_utf8_lossy(&response)).is_some() {
+            break;
+        }
+    }
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+/// Parameters of a `ESC [ ? ... c` device attributes reply in `response`.
+fn device_attributes(response: &str) -> Option<Vec<&str>> {
+    let start = response.find("\x1b[?")? + 3;
+    let end = start + response[start..].find('c')?;
+    Some(response[start..end].split(';').collect())
+}
+
+/// Picks a protocol from the terminal's answers to the probe queries.
+pub fn parse_probe_response(response: &str) -> Option<GraphicsProtocol> {
+    if response.contains("\x1b_Gi=31;OK") {
+        return Some(GraphicsProtocol::Kitty);
+    }
+    // attribute 4 advertises sixel graphics
+    let sixel = device_attributes(response).is_some_and(|attributes| attributes.contains(&"4"));
+    if sixel && cfg!(feature = "sixel") {
+        return Some(GraphicsProtocol::Sixel);
+    }
+    None
+}
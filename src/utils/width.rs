@@ -0,0 +1,87 @@
+use unicode_width::UnicodeWidthChar;
+
+/// Side of a column text is aligned to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Columns `text` occupies on a terminal, ignoring ANSI styling.
+pub fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    let mut skipped = String::new();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            copy_escape_sequence(&mut chars, &mut skipped);
+            continue;
+        }
+        width += c.width().unwrap_or(0);
+    }
+    width
+}
+
+/// `text` without its ANSI styling.
+pub fn strip_styling(text: &str) -> String {
+    let mut output = String::new();
+    let mut chars = text.chars();
+    let mut skipped = String::new();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            copy_escape_sequence(&mut chars, &mut skipped);
+            continue;
+        }
+        output.push(c);
+    }
+    output
+}
+
+/// Copies the rest of a CSI sequence, such as a color change, after its ESC.
+pub(crate) fn copy_escape_sequence(chars: &mut std::str::Chars, output: &mut String) {
+    let mut lookahead = chars.clone();
+    if lookahead.next() != Some('[') {
+        return;
+    }
+    output.push(chars.next().unwrap());
+    for c in chars.by_ref() {
+        output.push(c);
+        if ('\x40'..='\x7e').contains(&c) {
+            break;
+        }
+    }
+}
+
+/// Pads `text` with spaces to `width` terminal columns. Unlike `{:<width$}`,
+/// wide characters such as CJK count as two columns and styling as none.
+/// Text wider than `width` is returned unchanged.
+pub fn pad(text: &str, width: usize, align: Align) -> String {
+    let padding = " ".repeat(width.saturating_sub(display_width(text)));
+    match align {
+        Align::Left => format!("{}{}", text, padding),
+        Align::Right => format!("{}{}", padding, text),
+    }
+}
+
+/// Cuts unstyled `text` to at most `width` terminal columns, ending it with
+/// `…` if anything was cut.
+pub fn truncate(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    let mut output = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        // keep a column for the ellipsis
+        if used + char_width + 1 > width {
+            break;
+        }
+        output.push(c);
+        used += char_width;
+    }
+    if width > 0 {
+        output.push('…');
+    }
+    output
+}